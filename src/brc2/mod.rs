@@ -0,0 +1,93 @@
+use crate::{AEADAlgorithms, BSVErrors, PrivateKey, PublicKey, AES, ECDH};
+use rand_core::{OsRng, RngCore};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+pub const BRC2_IV_LENGTH: usize = 32;
+
+/**
+ * BRC-2 data encryption between two BRC-42 identities.
+ * Both parties derive child keys for the invoice number (BRC-43 format, eg. "2-message encryption-<key ID>"),
+ * the X coordinate of their ECDH point is the AES-256-GCM key. Ciphertexts are IV (32 bytes) | ciphertext | tag.
+ */
+#[wasm_bindgen]
+pub struct BRC2 {}
+
+impl BRC2 {
+    pub(crate) fn derive_symmetric_key_impl(private_key: &PrivateKey, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        let child_private_key = private_key.derive_child_impl(counterparty_pub_key, invoice_number)?;
+        let counterparty_child_pub_key = counterparty_pub_key.derive_child_impl(private_key, invoice_number)?;
+
+        ECDH::derive_shared_key_impl(&child_private_key, &counterparty_child_pub_key)
+    }
+
+    pub(crate) fn encrypt_impl(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        let key = Zeroizing::new(BRC2::derive_symmetric_key_impl(sender_priv_key, recipient_pub_key, invoice_number)?);
+
+        let mut iv = [0; BRC2_IV_LENGTH];
+        OsRng.fill_bytes(&mut iv);
+
+        let mut buffer = iv.to_vec();
+        buffer.extend_from_slice(&AES::encrypt_aead_impl(&key, &iv, message, &[], AEADAlgorithms::AES256_GCM)?);
+        Ok(buffer)
+    }
+
+    pub(crate) fn decrypt_impl(ciphertext: &[u8], recipient_priv_key: &PrivateKey, sender_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        if ciphertext.len() < BRC2_IV_LENGTH {
+            return Err(BSVErrors::BRCError(format!("Ciphertext is too short: {} bytes", ciphertext.len())));
+        }
+
+        let key = Zeroizing::new(BRC2::derive_symmetric_key_impl(recipient_priv_key, sender_pub_key, invoice_number)?);
+        let (iv, ciphertext) = ciphertext.split_at(BRC2_IV_LENGTH);
+
+        AES::decrypt_aead_impl(&key, iv, ciphertext, &[], AEADAlgorithms::AES256_GCM)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl BRC2 {
+    /**
+     * The 32 byte AES-256-GCM key shared with the counterparty for this invoice number.
+     */
+    #[wasm_bindgen(js_name = deriveSymmetricKey)]
+    pub fn derive_symmetric_key(private_key: &PrivateKey, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, JsValue> {
+        match BRC2::derive_symmetric_key_impl(private_key, counterparty_pub_key, invoice_number) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    pub fn encrypt(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, JsValue> {
+        match BRC2::encrypt_impl(message, sender_priv_key, recipient_pub_key, invoice_number) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    pub fn decrypt(ciphertext: &[u8], recipient_priv_key: &PrivateKey, sender_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, JsValue> {
+        match BRC2::decrypt_impl(ciphertext, recipient_priv_key, sender_pub_key, invoice_number) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BRC2 {
+    /**
+     * The 32 byte AES-256-GCM key shared with the counterparty for this invoice number.
+     */
+    pub fn derive_symmetric_key(private_key: &PrivateKey, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        BRC2::derive_symmetric_key_impl(private_key, counterparty_pub_key, invoice_number)
+    }
+
+    pub fn encrypt(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        BRC2::encrypt_impl(message, sender_priv_key, recipient_pub_key, invoice_number)
+    }
+
+    pub fn decrypt(ciphertext: &[u8], recipient_priv_key: &PrivateKey, sender_pub_key: &PublicKey, invoice_number: &str) -> Result<Vec<u8>, BSVErrors> {
+        BRC2::decrypt_impl(ciphertext, recipient_priv_key, sender_pub_key, invoice_number)
+    }
+}
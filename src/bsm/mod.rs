@@ -2,10 +2,14 @@ use crate::BSVErrors;
 use crate::Hash;
 use std::io::Write;
 
-use crate::{P2PKHAddress, PrivateKey, Signature, SigningHash, VarInt, ECDSA};
+#[cfg(target_arch = "wasm32")]
+use crate::JsSigner;
+use crate::{P2PKHAddress, PrivateKey, Signature, Signer, SigningHash, VarInt, ECDSA};
 use thiserror::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{throw_str, JsValue};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_futures::future_to_promise;
 
 /**
  * Bitcoin Signed Message
@@ -31,17 +35,48 @@ impl BSM {
      * Sign a message with the intention of verifying with this same Address.
      * Used when using Bitcoin Signed Messages ()
      */
-    pub(crate) fn sign_impl(priv_key: &PrivateKey, message: &[u8]) -> Result<Signature, BSVErrors> {
+    pub(crate) fn sign_impl(signer: &dyn Signer, message: &[u8]) -> Result<Signature, BSVErrors> {
         let magic_message = BSM::prepend_magic_bytes(message)?;
         // let magic_message = message;
-        ECDSA::sign_with_deterministic_k_impl(priv_key, &magic_message, SigningHash::Sha256d, false)
+        ECDSA::sign_with_signer_impl(signer, &magic_message, SigningHash::Sha256d, false)
+    }
+
+    /**
+     * Sign a message and return the base64 compact signature used by Electrum, bsv.js and HandCash.
+     * The header records whether the signer's public key is compressed so verifiers derive the matching address.
+     */
+    pub(crate) fn sign_base64_impl(signer: &dyn Signer, message: &[u8]) -> Result<String, BSVErrors> {
+        let signature = BSM::sign_impl(signer, message)?;
+        Ok(signature.to_compact_base64_with_compression(signer.get_public_key()?.is_compressed()))
+    }
+
+    /**
+     * Like sign_impl, for a JS signing callback that may return a Promise.
+     */
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) async fn sign_async_impl(signer: &JsSigner, message: &[u8]) -> Result<Signature, BSVErrors> {
+        let magic_message = BSM::prepend_magic_bytes(message)?;
+        ECDSA::sign_with_js_signer_async_impl(signer, &magic_message, SigningHash::Sha256d).await
     }
 
     pub(crate) fn verify_message_impl(message: &[u8], signature: &Signature, address: &P2PKHAddress) -> Result<bool, BSVErrors> {
+        BSM::verify_recovered_address(message, signature, true, address)
+    }
+
+    pub(crate) fn verify_message_base64_impl(message: &[u8], signature: &str, address: &P2PKHAddress) -> Result<bool, BSVErrors> {
+        let compact_bytes = base64::decode(signature)?;
+        let (signature, is_compressed) = Signature::from_compact_with_compression_impl(&compact_bytes)?;
+
+        BSM::verify_recovered_address(message, &signature, is_compressed, address)
+    }
+
+    fn verify_recovered_address(message: &[u8], signature: &Signature, is_compressed: bool, address: &P2PKHAddress) -> Result<bool, BSVErrors> {
         let magic_message = BSM::prepend_magic_bytes(message)?;
-        // let magic_message = message;
 
-        let public_key = signature.get_public_key(&magic_message, SigningHash::Sha256d)?;
+        let public_key = match is_compressed {
+            true => signature.get_public_key(&magic_message, SigningHash::Sha256d)?,
+            false => signature.get_public_key(&magic_message, SigningHash::Sha256d)?.to_decompressed_impl()?,
+        };
         let verify_p2pkh = P2PKHAddress::from_pubkey_impl(&public_key)?;
 
         let verify_address = verify_p2pkh.to_address_string_impl()?;
@@ -69,6 +104,16 @@ impl BSM {
     pub fn is_valid_message(message: &[u8], signature: &Signature, address: &P2PKHAddress) -> bool {
         BSM::verify_message_impl(message, signature, address).is_ok()
     }
+
+    /**
+     * Verifies a base64 compact signature, as produced by signMessageBase64 or other wallets, against an address.
+     *
+     * Returns boolean
+     */
+    #[wasm_bindgen(js_name = isValidMessageBase64)]
+    pub fn is_valid_message_base64(message: &[u8], signature: &str, address: &P2PKHAddress) -> bool {
+        BSM::verify_message_base64_impl(message, signature, address).is_ok()
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -89,6 +134,76 @@ impl BSM {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    #[wasm_bindgen(js_name = verifyMessageBase64)]
+    pub fn verify_message_base64(message: &[u8], signature: &str, address: &P2PKHAddress) -> Result<bool, JsValue> {
+        match BSM::verify_message_base64_impl(message, signature, address) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = signMessageBase64)]
+    pub fn sign_message_base64(priv_key: &PrivateKey, message: &[u8]) -> Result<String, JsValue> {
+        match BSM::sign_base64_impl(priv_key, message) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs with a JS signing callback, eg. a browser extension.
+     */
+    #[wasm_bindgen(js_name = signMessageWithSigner)]
+    pub fn sign_message_with_signer(signer: &JsSigner, message: &[u8]) -> Result<Signature, JsValue> {
+        match BSM::sign_impl(signer, message) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = signMessageBase64WithSigner)]
+    pub fn sign_message_base64_with_signer(signer: &JsSigner, message: &[u8]) -> Result<String, JsValue> {
+        match BSM::sign_base64_impl(signer, message) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs with a JS signing callback that may return a Promise, eg. a hardware wallet.
+     * Returns a Promise of the Signature.
+     */
+    #[wasm_bindgen(js_name = signMessageWithSignerAsync)]
+    pub fn sign_message_with_signer_async(signer: &JsSigner, message: &[u8]) -> js_sys::Promise {
+        let signer = signer.clone();
+        let message = message.to_vec();
+
+        future_to_promise(async move {
+            match BSM::sign_async_impl(&signer, &message).await {
+                Ok(v) => Ok(v.into()),
+                Err(e) => Err(JsValue::from_str(&e.to_string())),
+            }
+        })
+    }
+
+    /**
+     * Returns a Promise of the base64 compact signature.
+     */
+    #[wasm_bindgen(js_name = signMessageBase64WithSignerAsync)]
+    pub fn sign_message_base64_with_signer_async(signer: &JsSigner, message: &[u8]) -> js_sys::Promise {
+        let signer = signer.clone();
+        let message = message.to_vec();
+
+        future_to_promise(async move {
+            let is_compressed = signer.get_public_key().is_compressed();
+
+            match BSM::sign_async_impl(&signer, &message).await {
+                Ok(v) => Ok(v.to_compact_base64_with_compression(is_compressed).into()),
+                Err(e) => Err(JsValue::from_str(&e.to_string())),
+            }
+        })
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -100,4 +215,23 @@ impl BSM {
     pub fn sign_message(priv_key: &PrivateKey, message: &[u8]) -> Result<Signature, BSVErrors> {
         BSM::sign_impl(priv_key, message)
     }
+
+    pub fn verify_message_base64(message: &[u8], signature: &str, address: &P2PKHAddress) -> Result<bool, BSVErrors> {
+        BSM::verify_message_base64_impl(message, signature, address)
+    }
+
+    pub fn sign_message_base64(priv_key: &PrivateKey, message: &[u8]) -> Result<String, BSVErrors> {
+        BSM::sign_base64_impl(priv_key, message)
+    }
+
+    /**
+     * Signs with any Signer, eg. an external or hardware-backed key.
+     */
+    pub fn sign_message_with_signer(signer: &dyn Signer, message: &[u8]) -> Result<Signature, BSVErrors> {
+        BSM::sign_impl(signer, message)
+    }
+
+    pub fn sign_message_base64_with_signer(signer: &dyn Signer, message: &[u8]) -> Result<String, BSVErrors> {
+        BSM::sign_base64_impl(signer, message)
+    }
 }
@@ -0,0 +1,174 @@
+use crate::BSVErrors;
+use crate::{PublicKey, Signature, ECDSA};
+use ecdsa::hazmat::VerifyPrimitive;
+use k256::{PublicKey as K256PublicKey, Scalar};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+/**
+ * Below this many signatures per thread, spawning threads costs more than it saves.
+ */
+#[cfg(not(target_arch = "wasm32"))]
+const MIN_SIGNATURES_PER_THREAD: usize = 64;
+
+impl ECDSA {
+    /**
+     * Verifies every (digest, public key, signature) tuple and returns the indices of the ones that fail, empty if all are valid.
+     * Digests are the 32 byte message hashes, eg. the SHA256d of a sighash preimage. High S signatures are accepted, like verify_digest.
+     *
+     * ECDSA signatures only commit to the x coordinate of R, so they cannot be combined into a single multi-scalar check.
+     * Instead natively the batch is split across threads; on wasm32 it is verified sequentially.
+     */
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn verify_batch_impl(digests: &[[u8; 32]], pub_keys: &[PublicKey], signatures: &[Signature]) -> Result<Vec<usize>, BSVErrors> {
+        if digests.len() != pub_keys.len() || digests.len() != signatures.len() {
+            return Err(BSVErrors::MessageVerification(format!(
+                "Batch needs the same number of digests ({}), public keys ({}) and signatures ({})",
+                digests.len(),
+                pub_keys.len(),
+                signatures.len()
+            )));
+        }
+
+        Ok(ECDSA::failed_indices(digests, pub_keys, signatures, 0))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn failed_indices(digests: &[[u8; 32]], pub_keys: &[PublicKey], signatures: &[Signature], offset: usize) -> Vec<usize> {
+        ECDSA::failed_indices_sequential(digests, pub_keys, signatures, offset)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn failed_indices(digests: &[[u8; 32]], pub_keys: &[PublicKey], signatures: &[Signature], offset: usize) -> Vec<usize> {
+        let threads = std::thread::available_parallelism().map_or(1, |x| x.get()).min(digests.len() / MIN_SIGNATURES_PER_THREAD);
+        if threads <= 1 {
+            return ECDSA::failed_indices_sequential(digests, pub_keys, signatures, offset);
+        }
+
+        let chunk_size = digests.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..digests.len())
+                .step_by(chunk_size)
+                .map(|start| {
+                    let end = (start + chunk_size).min(digests.len());
+                    scope.spawn(move || ECDSA::failed_indices_sequential(&digests[start..end], &pub_keys[start..end], &signatures[start..end], offset + start))
+                })
+                .collect();
+
+            // Chunks are joined in order, so the indices stay sorted
+            handles.into_iter().flat_map(|x| x.join().unwrap_or_else(|e| std::panic::resume_unwind(e))).collect()
+        })
+    }
+
+    fn failed_indices_sequential(digests: &[[u8; 32]], pub_keys: &[PublicKey], signatures: &[Signature], offset: usize) -> Vec<usize> {
+        digests
+            .iter()
+            .zip(pub_keys.iter())
+            .zip(signatures.iter())
+            .enumerate()
+            .filter(|(_, ((digest, pub_key), signature))| !ECDSA::verify_prehashed_digest(digest, pub_key, signature))
+            .map(|(i, _)| offset + i)
+            .collect()
+    }
+
+    fn verify_prehashed_digest(digest: &[u8; 32], pub_key: &PublicKey, signature: &Signature) -> bool {
+        let key = match pub_key.to_bytes_impl().ok().and_then(|x| K256PublicKey::from_sec1_bytes(&x).ok()) {
+            Some(v) => v,
+            None => return false,
+        };
+        let signature = match signature.normalise_s_impl() {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+
+        let z = Scalar::from_bytes_reduced(&(*digest).into());
+        key.as_affine().verify_prehashed(&z, &signature.sig).is_ok()
+    }
+}
+
+/**
+ * Collects (digest, public key, signature) tuples to verify together, eg. every input of a block or BEEF bundle.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct SignatureBatch {
+    digests: Vec<[u8; 32]>,
+    pub_keys: Vec<PublicKey>,
+    signatures: Vec<Signature>,
+}
+
+impl SignatureBatch {
+    pub(crate) fn add_impl(&mut self, digest: &[u8], pub_key: &PublicKey, signature: &Signature) -> Result<(), BSVErrors> {
+        if digest.len() != 32 {
+            return Err(BSVErrors::MessageVerification(format!("Digest must be 32 bytes, got {}", digest.len())));
+        }
+
+        let mut digest_bytes = [0; 32];
+        digest_bytes.copy_from_slice(digest);
+
+        self.digests.push(digest_bytes);
+        self.pub_keys.push(pub_key.clone());
+        self.signatures.push(signature.clone());
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl SignatureBatch {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SignatureBatch {
+        SignatureBatch::default()
+    }
+
+    #[wasm_bindgen(js_name = getLength)]
+    pub fn get_length(&self) -> usize {
+        self.digests.len()
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /**
+     * Returns the indices, in the order they were added, of the signatures that fail. Empty if all are valid.
+     */
+    pub fn verify(&self) -> Vec<usize> {
+        ECDSA::failed_indices(&self.digests, &self.pub_keys, &self.signatures, 0)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl SignatureBatch {
+    /**
+     * Adds a 32 byte message digest with the public key and signature to check it against.
+     */
+    pub fn add(&mut self, digest: &[u8], pub_key: &PublicKey, signature: &Signature) -> Result<(), JsValue> {
+        match self.add_impl(digest, pub_key, signature) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SignatureBatch {
+    /**
+     * Adds a 32 byte message digest with the public key and signature to check it against.
+     */
+    pub fn add(&mut self, digest: &[u8], pub_key: &PublicKey, signature: &Signature) -> Result<(), BSVErrors> {
+        self.add_impl(digest, pub_key, signature)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECDSA {
+    /**
+     * Verifies every (digest, public key, signature) tuple, returning the indices of the invalid ones or an empty Vec if all are valid.
+     * Errors if the slices have different lengths.
+     */
+    pub fn verify_batch(digests: &[[u8; 32]], pub_keys: &[PublicKey], signatures: &[Signature]) -> Result<Vec<usize>, BSVErrors> {
+        ECDSA::verify_batch_impl(digests, pub_keys, signatures)
+    }
+}
@@ -1,7 +1,10 @@
+pub mod batch;
 pub mod ecdh;
+pub mod nonce;
 pub mod sign;
 pub mod verify;
 
+pub use batch::*;
 pub use ecdh::*;
 
 use wasm_bindgen::prelude::*;
@@ -12,9 +15,18 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 pub struct ECDSA {}
 
+/**
+ * The hash of the preimage that is signed. Bitcoin uses Sha256d (transactions) and Sha256 (messages),
+ * the others are for signing data from other systems. Deterministic nonces always use HMAC-SHA256.
+ */
 #[wasm_bindgen]
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum SigningHash {
     Sha256,
     Sha256d,
+    Sha3_256,
+    Keccak256,
+    Sha512_256,
+    Blake2b256,
+    Blake3,
 }
@@ -0,0 +1,63 @@
+use crate::{BSVErrors, DRBGHashes, HmacDrbg, PrivateKey, ECDSA};
+use k256::{FieldBytes, Scalar};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+const SCALAR_LENGTH: usize = 32;
+
+impl ECDSA {
+    /**
+     * RFC 6979 (section 3.2) nonce k over secp256k1 with HMAC-SHA256, for a message digest of any length.
+     * ECDSA.sign with reverse_k = false uses this nonce for the SHA256 or SHA256d digest of the preimage.
+     */
+    pub(crate) fn rfc6979_nonce_impl(private_key: &PrivateKey, digest: &[u8], extra_entropy: &[u8]) -> Result<PrivateKey, BSVErrors> {
+        if digest.is_empty() {
+            return Err(BSVErrors::DRBGError("Digest cannot be empty".into()));
+        }
+
+        // bits2octets: the leftmost 256 bits of the digest, reduced mod n
+        let mut h1 = [0; SCALAR_LENGTH];
+        let digest_bits = &digest[..digest.len().min(SCALAR_LENGTH)];
+        h1[SCALAR_LENGTH - digest_bits.len()..].copy_from_slice(digest_bits);
+        let h1 = Scalar::from_bytes_reduced(FieldBytes::from_slice(&h1)).to_bytes();
+
+        let x = Zeroizing::new(private_key.to_bytes());
+        let mut drbg = HmacDrbg::new(DRBGHashes::SHA256, &x, &[&h1[..], extra_entropy].concat(), &[]);
+
+        // Out of range candidates (probability ~2^-128) are skipped by generating again
+        loop {
+            let candidate = Zeroizing::new(drbg.generate_impl(SCALAR_LENGTH, &[])?);
+            if let Ok(k) = PrivateKey::from_bytes_impl(&candidate) {
+                return Ok(k);
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl ECDSA {
+    /**
+     * RFC 6979 nonce k for the private key and message digest, with optional extra entropy (empty for plain RFC 6979).
+     * Use with signWithK to reproduce deterministic signatures from other libraries.
+     */
+    #[wasm_bindgen(js_name = rfc6979Nonce)]
+    pub fn rfc6979_nonce(private_key: &PrivateKey, digest: &[u8], extra_entropy: &[u8]) -> Result<PrivateKey, JsValue> {
+        match ECDSA::rfc6979_nonce_impl(private_key, digest, extra_entropy) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECDSA {
+    /**
+     * RFC 6979 nonce k for the private key and message digest, with optional extra entropy (empty for plain RFC 6979).
+     * Use with sign_with_k to reproduce deterministic signatures from other libraries.
+     */
+    pub fn rfc6979_nonce(private_key: &PrivateKey, digest: &[u8], extra_entropy: &[u8]) -> Result<PrivateKey, BSVErrors> {
+        ECDSA::rfc6979_nonce_impl(private_key, digest, extra_entropy)
+    }
+}
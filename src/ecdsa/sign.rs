@@ -10,8 +10,9 @@ use ecdsa::{
     hazmat::{FromDigest, RecoverableSignPrimitive},
     rfc6979::{self, generate_k},
 };
+use elliptic_curve::sec1::ToEncodedPoint;
 use k256::FieldBytes;
-use k256::{ecdsa::Signature as SecpSignature, Scalar, SecretKey};
+use k256::{ecdsa::Signature as SecpSignature, ProjectivePoint, Scalar, SecretKey};
 use rand_core::OsRng;
 use rand_core::RngCore;
 use wasm_bindgen::prelude::*;
@@ -62,7 +63,8 @@ impl ECDSA {
 
         let signature = Signature::from_der_impl(sig.to_der().as_bytes(), is_recoverable)?;
 
-        Ok(signature)
+        // Miners reject high S signatures as non-standard
+        signature.normalise_s_impl()
     }
 
     /**
@@ -76,13 +78,68 @@ impl ECDSA {
 
         let signature = Signature::from_der_impl(sig.to_der().as_bytes(), is_recoverable)?;
 
-        Ok(signature)
+        // Miners reject high S signatures as non-standard
+        signature.normalise_s_impl()
+    }
+
+    /**
+     * Signs with a caller supplied nonce k, normalised to low S like the other signing functions.
+     * Anyone who knows k and a signature can recover the private key, only use this with well-known keys.
+     */
+    pub(crate) fn sign_with_k_impl(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        // Miners reject high S signatures as non-standard
+        ECDSA::sign_with_k_unnormalised_impl(private_key, k, preimage, hash_algo)?.normalise_s_impl()
+    }
+
+    /**
+     * Signs with a caller supplied nonce k. S is NOT normalised to low S, for OP_PUSH_TX where the script recomputes it exactly.
+     */
+    pub(crate) fn sign_with_k_unnormalised_impl(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        let digest = get_hash_digest(hash_algo, preimage);
+        let msg_scalar = Scalar::from_digest(digest);
+
+        let priv_scalar = private_key.secret_key.to_secret_scalar();
+        let k_scalar = k.secret_key.to_secret_scalar();
+
+        let r_point = (ProjectivePoint::generator() * *k_scalar).to_affine().to_encoded_point(true);
+        let r = Scalar::from_bytes_reduced(FieldBytes::from_slice(&r_point.as_bytes()[1..33]));
+
+        let k_inverse = Option::<Scalar>::from(k_scalar.invert()).ok_or(ecdsa::Error::new())?;
+        let s = k_inverse * (msg_scalar + r * *priv_scalar);
+
+        Ok(Signature {
+            sig: SecpSignature::from_scalars(r, s)?,
+            recovery_i: (r_point.as_bytes()[0] == 0x03) as u8,
+        })
     }
 }
 
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 impl ECDSA {
+    /**
+     * Signs with a caller supplied nonce k, normalised to low S.
+     * Anyone who knows k and a signature can recover the private key, only use this with well-known keys.
+     */
+    #[wasm_bindgen(js_name = signWithK)]
+    pub fn sign_with_k(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, JsValue> {
+        match ECDSA::sign_with_k_impl(private_key, k, preimage, hash_algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs with a caller supplied nonce k. S is NOT normalised, eg. for OP_PUSH_TX where the script recomputes it exactly.
+     */
+    #[wasm_bindgen(js_name = signWithKUnnormalised)]
+    pub fn sign_with_k_unnormalised(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, JsValue> {
+        match ECDSA::sign_with_k_unnormalised_impl(private_key, k, preimage, hash_algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
     #[wasm_bindgen(js_name = signWithRandomK)]
     pub fn sign_with_random_k(private_key: &PrivateKey, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, JsValue> {
         match ECDSA::sign_with_random_k_impl(private_key, preimage, hash_algo, reverse_k) {
@@ -102,6 +159,21 @@ impl ECDSA {
 
 #[cfg(not(target_arch = "wasm32"))]
 impl ECDSA {
+    /**
+     * Signs with a caller supplied nonce k, normalised to low S.
+     * Anyone who knows k and a signature can recover the private key, only use this with well-known keys.
+     */
+    pub fn sign_with_k(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        ECDSA::sign_with_k_impl(private_key, k, preimage, hash_algo)
+    }
+
+    /**
+     * Signs with a caller supplied nonce k. S is NOT normalised, eg. for OP_PUSH_TX where the script recomputes it exactly.
+     */
+    pub fn sign_with_k_unnormalised(private_key: &PrivateKey, k: &PrivateKey, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        ECDSA::sign_with_k_unnormalised_impl(private_key, k, preimage, hash_algo)
+    }
+
     pub fn sign_with_random_k(private_key: &PrivateKey, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, BSVErrors> {
         ECDSA::sign_with_random_k_impl(private_key, preimage, hash_algo, reverse_k)
     }
@@ -7,7 +7,27 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::{throw_str, JsValue};
 
 impl ECDSA {
+    /**
+     * Accepts both low and high S signatures, like consensus.
+     */
     pub(crate) fn verify_digest_impl(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, BSVErrors> {
+        // k256 only verifies low S signatures
+        let low_s_signature = signature.normalise_s_impl()?;
+        ECDSA::verify_low_s_digest(message, pub_key, &low_s_signature, hash_algo)
+    }
+
+    /**
+     * Rejects high S signatures with HighSSignature, like the standardness rules miners apply to transactions.
+     */
+    pub(crate) fn verify_digest_strict_impl(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, BSVErrors> {
+        if !signature.is_low_s() {
+            return Err(BSVErrors::HighSSignature);
+        }
+
+        ECDSA::verify_low_s_digest(message, pub_key, signature, hash_algo)
+    }
+
+    fn verify_low_s_digest(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, BSVErrors> {
         let pub_key_bytes = pub_key.to_bytes_impl()?;
         let point = EncodedPoint::from_bytes(pub_key_bytes)?;
         let key = VerifyingKey::from_encoded_point(&point)?;
@@ -28,6 +48,17 @@ impl ECDSA {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    /**
+     * Like verify, but throws if S is high.
+     */
+    #[wasm_bindgen(js_name = verifyStrict)]
+    pub fn verify_digest_strict(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, JsValue> {
+        match ECDSA::verify_digest_strict_impl(message, pub_key, signature, hash_algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -35,4 +66,11 @@ impl ECDSA {
     pub fn verify_digest(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, BSVErrors> {
         ECDSA::verify_digest_impl(message, pub_key, signature, hash_algo)
     }
+
+    /**
+     * Like verify_digest, but errors with HighSSignature if S is high.
+     */
+    pub fn verify_digest_strict(message: &[u8], pub_key: &PublicKey, signature: &Signature, hash_algo: SigningHash) -> Result<bool, BSVErrors> {
+        ECDSA::verify_digest_strict_impl(message, pub_key, signature, hash_algo)
+    }
 }
@@ -12,6 +12,26 @@ pub struct ECIESCiphertext {
 
 const PUB_KEY_OFFSET: u8 = 4;
 const PUB_KEY_END: u8 = PUB_KEY_OFFSET + 33;
+const MAGIC_BYTES: &[u8] = b"BIE1";
+const HMAC_LENGTH: usize = 32;
+const AES_BLOCK_LENGTH: usize = 16;
+
+/**
+ * ECIES flavours. Both use the BIE1 format: "BIE1" | public key (33 bytes, optional) | AES-128-CBC ciphertext | HMAC-SHA256,
+ * with iv | kE | kM taken from the SHA512 of the compressed ECDH point.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ECIESMode {
+    /**
+     * bsv.js Ecies: the sender may use their own key and may leave it out of the ciphertext.
+     */
+    BIE1,
+    /**
+     * Electrum (SV): always a fresh ephemeral key that is always included, the recipient decrypts with it.
+     */
+    Electrum,
+}
 
 impl ECIESCiphertext {
     pub(crate) fn extract_public_key_impl(&self) -> Result<PublicKey, BSVErrors> {
@@ -20,6 +40,17 @@ impl ECIESCiphertext {
     }
 
     pub(crate) fn from_bytes_impl(buffer: &[u8], has_pub_key: bool) -> Result<ECIESCiphertext, BSVErrors> {
+        let header_length = match has_pub_key {
+            true => PUB_KEY_END as usize,
+            false => PUB_KEY_OFFSET as usize,
+        };
+        if buffer.len() < header_length + AES_BLOCK_LENGTH + HMAC_LENGTH {
+            return Err(BSVErrors::ECIESError(format!("Ciphertext is too short: {} bytes", buffer.len())));
+        }
+        if &buffer[..PUB_KEY_OFFSET as usize] != MAGIC_BYTES {
+            return Err(BSVErrors::ECIESError("Ciphertext does not start with BIE1".into()));
+        }
+
         let pub_key = match has_pub_key {
             true => {
                 let pub_key_buf = &buffer[PUB_KEY_OFFSET as usize..PUB_KEY_END as usize];
@@ -29,11 +60,8 @@ impl ECIESCiphertext {
             false => None,
         };
 
-        let hmac = &buffer[buffer.len() - 32..buffer.len()];
-        let ciphertext = match has_pub_key {
-            true => &buffer[PUB_KEY_END as usize..buffer.len() - 32],
-            false => &buffer[4..buffer.len() - 32],
-        };
+        let hmac = &buffer[buffer.len() - HMAC_LENGTH..buffer.len()];
+        let ciphertext = &buffer[header_length..buffer.len() - HMAC_LENGTH];
 
         Ok(ECIESCiphertext {
             public_key_bytes: pub_key,
@@ -41,6 +69,24 @@ impl ECIESCiphertext {
             ciphertext_bytes: ciphertext.into(),
         })
     }
+
+    /**
+     * Electrum ciphertexts always include the public key. For BIE1 it is detected from the length,
+     * as the AES ciphertext is a whole number of blocks and the public key is 33 bytes.
+     */
+    pub(crate) fn from_bytes_with_mode_impl(buffer: &[u8], mode: ECIESMode) -> Result<ECIESCiphertext, BSVErrors> {
+        let has_pub_key = match mode {
+            ECIESMode::Electrum => true,
+            ECIESMode::BIE1 => !buffer.len().saturating_sub(PUB_KEY_OFFSET as usize + HMAC_LENGTH).is_multiple_of(AES_BLOCK_LENGTH),
+        };
+
+        ECIESCiphertext::from_bytes_impl(buffer, has_pub_key)
+    }
+
+    pub(crate) fn from_base64_impl(base64_str: &str, mode: ECIESMode) -> Result<ECIESCiphertext, BSVErrors> {
+        let buffer = base64::decode(base64_str)?;
+        ECIESCiphertext::from_bytes_with_mode_impl(&buffer, mode)
+    }
 }
 
 #[wasm_bindgen]
@@ -58,7 +104,7 @@ impl ECIESCiphertext {
     #[wasm_bindgen(js_name = toBytes)]
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buffer = vec![];
-        buffer.extend_from_slice(b"BIE1");
+        buffer.extend_from_slice(MAGIC_BYTES);
         if let Some(bytes) = self.public_key_bytes.clone() {
             buffer.extend_from_slice(&bytes);
         }
@@ -67,6 +113,14 @@ impl ECIESCiphertext {
 
         buffer
     }
+
+    /**
+     * Base64 of toBytes, as Electrum encodes encrypted messages.
+     */
+    #[wasm_bindgen(js_name = toBase64)]
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.to_bytes())
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -81,6 +135,22 @@ impl ECIESCiphertext {
     pub fn from_bytes(buffer: &[u8], has_pub_key: bool) -> Result<ECIESCiphertext, JsValue> {
         ECIESCiphertext::from_bytes_impl(buffer, has_pub_key).map_err(|e| throw_str(&e.to_string()))
     }
+
+    #[wasm_bindgen(js_name = fromBytesWithMode)]
+    pub fn from_bytes_with_mode(buffer: &[u8], mode: ECIESMode) -> Result<ECIESCiphertext, JsValue> {
+        match ECIESCiphertext::from_bytes_with_mode_impl(buffer, mode) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromBase64)]
+    pub fn from_base64(base64_str: &str, mode: ECIESMode) -> Result<ECIESCiphertext, JsValue> {
+        match ECIESCiphertext::from_base64_impl(base64_str, mode) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -92,4 +162,12 @@ impl ECIESCiphertext {
     pub fn from_bytes(buffer: &[u8], has_pub_key: bool) -> Result<ECIESCiphertext, BSVErrors> {
         ECIESCiphertext::from_bytes_impl(buffer, has_pub_key)
     }
+
+    pub fn from_bytes_with_mode(buffer: &[u8], mode: ECIESMode) -> Result<ECIESCiphertext, BSVErrors> {
+        ECIESCiphertext::from_bytes_with_mode_impl(buffer, mode)
+    }
+
+    pub fn from_base64(base64_str: &str, mode: ECIESMode) -> Result<ECIESCiphertext, BSVErrors> {
+        ECIESCiphertext::from_base64_impl(base64_str, mode)
+    }
 }
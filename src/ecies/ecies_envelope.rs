@@ -0,0 +1,292 @@
+use crate::{AEADAlgorithms, BSVErrors, ECIESCiphertext, Hash, PrivateKey, PublicKey, VarInt, AEAD_NONCE_LENGTH, AES, ECIES};
+use rand_core::{OsRng, RngCore};
+use std::io::{Cursor, Read, Write};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+const ENVELOPE_MAGIC_BYTES: &[u8] = b"ENV1";
+const CONTENT_KEY_LENGTH: usize = 32;
+const PUB_KEY_HASH_LENGTH: usize = 20;
+
+#[derive(Debug, Clone)]
+struct EnvelopeRecipient {
+    pub_key_hash: Vec<u8>,
+    wrapped_key: Vec<u8>,
+}
+
+/**
+ * Encrypts a payload once for many recipients. A random content key encrypts the payload with AES-256-GCM
+ * and is wrapped for each recipient with ECIES (a fresh ephemeral key per recipient), found by the Hash160 of their compressed public key.
+ *
+ * Serialised as "ENV1" | VarInt recipient count | (public key hash | VarInt length | ECIES ciphertext)... | nonce | ciphertext | tag
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ECIESEnvelope {
+    recipients: Vec<EnvelopeRecipient>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl ECIESEnvelope {
+    fn pub_key_hash(pub_key: &PublicKey) -> Result<Vec<u8>, BSVErrors> {
+        Ok(Hash::hash_160(&pub_key.to_compressed_impl()?.to_bytes_impl()?).to_bytes())
+    }
+
+    fn wrap_content_key(&mut self, content_key: &[u8], recipient_pub_key: &PublicKey) -> Result<(), BSVErrors> {
+        let pub_key_hash = ECIESEnvelope::pub_key_hash(recipient_pub_key)?;
+        if self.recipients.iter().any(|x| x.pub_key_hash == pub_key_hash) {
+            return Ok(());
+        }
+
+        let wrapped_key = ECIES::encrypt_with_ephemeral_private_key_impl(content_key, recipient_pub_key)?.to_bytes();
+        self.recipients.push(EnvelopeRecipient { pub_key_hash, wrapped_key });
+        Ok(())
+    }
+
+    fn unwrap_content_key(&self, recipient_priv_key: &PrivateKey) -> Result<Zeroizing<Vec<u8>>, BSVErrors> {
+        let pub_key_hash = ECIESEnvelope::pub_key_hash(&recipient_priv_key.get_public_key_impl()?)?;
+        let recipient = match self.recipients.iter().find(|x| x.pub_key_hash == pub_key_hash) {
+            Some(v) => v,
+            None => return Err(BSVErrors::ECIESError(format!("{} is not a recipient of this envelope", hex::encode(pub_key_hash)))),
+        };
+
+        let wrapped_key = ECIESCiphertext::from_bytes_impl(&recipient.wrapped_key, true)?;
+        let content_key = Zeroizing::new(ECIES::decrypt_electrum_impl(&wrapped_key, recipient_priv_key)?);
+        if content_key.len() != CONTENT_KEY_LENGTH {
+            return Err(BSVErrors::ECIESError("Envelope content key has the wrong length".into()));
+        }
+
+        Ok(content_key)
+    }
+
+    pub(crate) fn encrypt_impl(message: &[u8], recipients: &[PublicKey]) -> Result<ECIESEnvelope, BSVErrors> {
+        if recipients.is_empty() {
+            return Err(BSVErrors::ECIESError("An envelope needs at least one recipient".into()));
+        }
+
+        let mut content_key = Zeroizing::new(vec![0; CONTENT_KEY_LENGTH]);
+        OsRng.fill_bytes(&mut content_key);
+        let mut nonce = vec![0; AEAD_NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut envelope = ECIESEnvelope {
+            recipients: vec![],
+            ciphertext: AES::encrypt_aead_impl(&content_key, &nonce, message, &[], AEADAlgorithms::AES256_GCM)?,
+            nonce,
+        };
+        for recipient in recipients {
+            envelope.wrap_content_key(&content_key, recipient)?;
+        }
+
+        Ok(envelope)
+    }
+
+    pub(crate) fn decrypt_impl(&self, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        let content_key = self.unwrap_content_key(recipient_priv_key)?;
+        AES::decrypt_aead_impl(&content_key, &self.nonce, &self.ciphertext, &[], AEADAlgorithms::AES256_GCM)
+    }
+
+    /**
+     * Any recipient can share the envelope with another public key without re-encrypting the payload.
+     */
+    pub(crate) fn add_recipient_impl(&mut self, recipient_priv_key: &PrivateKey, new_recipient_pub_key: &PublicKey) -> Result<(), BSVErrors> {
+        let content_key = self.unwrap_content_key(recipient_priv_key)?;
+        self.wrap_content_key(&content_key, new_recipient_pub_key)
+    }
+
+    /**
+     * Returns whether the recipient was in the envelope.
+     */
+    pub(crate) fn remove_recipient_impl(&mut self, pub_key: &PublicKey) -> Result<bool, BSVErrors> {
+        let pub_key_hash = ECIESEnvelope::pub_key_hash(pub_key)?;
+        let count = self.recipients.len();
+        self.recipients.retain(|x| x.pub_key_hash != pub_key_hash);
+
+        Ok(self.recipients.len() != count)
+    }
+
+    pub(crate) fn has_recipient_impl(&self, pub_key: &PublicKey) -> Result<bool, BSVErrors> {
+        let pub_key_hash = ECIESEnvelope::pub_key_hash(pub_key)?;
+        Ok(self.recipients.iter().any(|x| x.pub_key_hash == pub_key_hash))
+    }
+
+    pub(crate) fn to_bytes_impl(&self) -> Result<Vec<u8>, BSVErrors> {
+        let mut buffer = ENVELOPE_MAGIC_BYTES.to_vec();
+        buffer.write_varint(self.recipients.len() as u64)?;
+        for recipient in &self.recipients {
+            buffer.write_all(&recipient.pub_key_hash)?;
+            buffer.write_varint(recipient.wrapped_key.len() as u64)?;
+            buffer.write_all(&recipient.wrapped_key)?;
+        }
+        buffer.write_all(&self.nonce)?;
+        buffer.write_all(&self.ciphertext)?;
+
+        Ok(buffer)
+    }
+
+    pub(crate) fn from_bytes_impl(bytes: &[u8]) -> Result<ECIESEnvelope, BSVErrors> {
+        let read_error = |field: &str, e: std::io::Error| BSVErrors::ECIESError(format!("Unable to read envelope {}: {}", field, e));
+        let mut cursor = Cursor::new(bytes.to_vec());
+
+        let mut magic = [0; 4];
+        cursor.read_exact(&mut magic).map_err(|e| read_error("magic bytes", e))?;
+        if magic != ENVELOPE_MAGIC_BYTES {
+            return Err(BSVErrors::ECIESError("Envelope does not start with ENV1".into()));
+        }
+
+        let recipient_count = cursor.read_varint().map_err(|e| read_error("recipient count", e))?;
+        let mut recipients = vec![];
+        for _ in 0..recipient_count {
+            let mut pub_key_hash = vec![0; PUB_KEY_HASH_LENGTH];
+            cursor.read_exact(&mut pub_key_hash).map_err(|e| read_error("public key hash", e))?;
+
+            let wrapped_key_length = cursor.read_varint().map_err(|e| read_error("wrapped key length", e))?;
+            if wrapped_key_length > bytes.len() as u64 - cursor.position() {
+                return Err(BSVErrors::ECIESError(format!("Envelope wrapped key length {} is longer than the remaining bytes", wrapped_key_length)));
+            }
+            let mut wrapped_key = vec![0; wrapped_key_length as usize];
+            cursor.read_exact(&mut wrapped_key).map_err(|e| read_error("wrapped key", e))?;
+
+            recipients.push(EnvelopeRecipient { pub_key_hash, wrapped_key });
+        }
+
+        let mut nonce = vec![0; AEAD_NONCE_LENGTH];
+        cursor.read_exact(&mut nonce).map_err(|e| read_error("nonce", e))?;
+        let mut ciphertext = vec![];
+        cursor.read_to_end(&mut ciphertext)?;
+
+        Ok(ECIESEnvelope { recipients, nonce, ciphertext })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn pub_keys_from_hex(pub_keys: &[JsValue]) -> Result<Vec<PublicKey>, BSVErrors> {
+        pub_keys
+            .iter()
+            .map(|x| match x.as_string() {
+                Some(hex) => PublicKey::from_hex_impl(&hex),
+                None => Err(BSVErrors::ECIESError("Recipients must be given as hex public keys".into())),
+            })
+            .collect()
+    }
+}
+
+/**
+ * Platform Agnostic Functions
+ * ie. Don't need Result<T, E>
+ */
+#[wasm_bindgen]
+impl ECIESEnvelope {
+    #[wasm_bindgen(js_name = getRecipientCount)]
+    pub fn get_recipient_count(&self) -> usize {
+        self.recipients.len()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl ECIESEnvelope {
+    /**
+     * Encrypts the message once for an array of hex encoded recipient public keys.
+     */
+    pub fn encrypt(message: &[u8], recipients: Box<[JsValue]>) -> Result<ECIESEnvelope, JsValue> {
+        match ECIESEnvelope::pub_keys_from_hex(&recipients).and_then(|x| ECIESEnvelope::encrypt_impl(message, &x)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    pub fn decrypt(&self, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, JsValue> {
+        match self.decrypt_impl(recipient_priv_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Any recipient can share the envelope with another public key without re-encrypting the payload.
+     */
+    #[wasm_bindgen(js_name = addRecipient)]
+    pub fn add_recipient(&mut self, recipient_priv_key: &PrivateKey, new_recipient_pub_key: &PublicKey) -> Result<(), JsValue> {
+        match self.add_recipient_impl(recipient_priv_key, new_recipient_pub_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Removes the recipient's wrapped key. They can still decrypt any copy of the envelope they already have.
+     */
+    #[wasm_bindgen(js_name = removeRecipient)]
+    pub fn remove_recipient(&mut self, pub_key: &PublicKey) -> Result<bool, JsValue> {
+        match self.remove_recipient_impl(pub_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = hasRecipient)]
+    pub fn has_recipient(&self, pub_key: &PublicKey) -> Result<bool, JsValue> {
+        match self.has_recipient_impl(pub_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        match self.to_bytes_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<ECIESEnvelope, JsValue> {
+        match ECIESEnvelope::from_bytes_impl(bytes) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECIESEnvelope {
+    /**
+     * Encrypts the message once for all of the recipients.
+     */
+    pub fn encrypt(message: &[u8], recipients: &[PublicKey]) -> Result<ECIESEnvelope, BSVErrors> {
+        ECIESEnvelope::encrypt_impl(message, recipients)
+    }
+
+    pub fn decrypt(&self, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        self.decrypt_impl(recipient_priv_key)
+    }
+
+    /**
+     * Any recipient can share the envelope with another public key without re-encrypting the payload.
+     */
+    pub fn add_recipient(&mut self, recipient_priv_key: &PrivateKey, new_recipient_pub_key: &PublicKey) -> Result<(), BSVErrors> {
+        self.add_recipient_impl(recipient_priv_key, new_recipient_pub_key)
+    }
+
+    /**
+     * Removes the recipient's wrapped key. They can still decrypt any copy of the envelope they already have.
+     */
+    pub fn remove_recipient(&mut self, pub_key: &PublicKey) -> Result<bool, BSVErrors> {
+        self.remove_recipient_impl(pub_key)
+    }
+
+    pub fn has_recipient(&self, pub_key: &PublicKey) -> Result<bool, BSVErrors> {
+        self.has_recipient_impl(pub_key)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BSVErrors> {
+        self.to_bytes_impl()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<ECIESEnvelope, BSVErrors> {
+        ECIESEnvelope::from_bytes_impl(bytes)
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{AEADAlgorithms, BSVErrors, PrivateKey, PublicKey, AEAD_NONCE_LENGTH, AEAD_TAG_LENGTH, AES, ECIES};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+const STREAM_MAGIC_BYTES: &[u8] = b"BIES";
+const STREAM_HEADER_LENGTH: usize = 4 + 33 + 4;
+const NONCE_PREFIX_LENGTH: usize = 7;
+
+pub const ECIES_STREAM_DEFAULT_CHUNK_SIZE: u32 = 64 * 1024;
+pub const ECIES_STREAM_MAX_CHUNK_SIZE: u32 = 16 * 1024 * 1024;
+
+/**
+ * Keys and nonces for one ECIES stream. Chunk n is sealed with AES-128-GCM under kE, with the nonce iv[0..7] | n (u32 BE) | last chunk flag
+ * and the header as associated data, so chunks cannot be reordered, dropped or truncated without failing authentication.
+ */
+struct StreamKeys {
+    key: Zeroizing<Vec<u8>>,
+    nonce_prefix: [u8; NONCE_PREFIX_LENGTH],
+    header: Vec<u8>,
+    chunk_size: usize,
+    counter: u32,
+}
+
+impl StreamKeys {
+    fn new(priv_key: &PrivateKey, pub_key: &PublicKey, header: Vec<u8>, chunk_size: usize) -> Result<StreamKeys, BSVErrors> {
+        let cipher_keys = ECIES::derive_cipher_keys_impl(priv_key, pub_key)?;
+        let mut nonce_prefix = [0; NONCE_PREFIX_LENGTH];
+        nonce_prefix.copy_from_slice(&cipher_keys.iv[..NONCE_PREFIX_LENGTH]);
+
+        Ok(StreamKeys {
+            key: Zeroizing::new(cipher_keys.ke.clone()),
+            nonce_prefix,
+            header,
+            chunk_size,
+            counter: 0,
+        })
+    }
+
+    fn next_nonce(&mut self, is_last: bool) -> Result<[u8; AEAD_NONCE_LENGTH], BSVErrors> {
+        let mut nonce = [0; AEAD_NONCE_LENGTH];
+        nonce[..NONCE_PREFIX_LENGTH].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_LENGTH..AEAD_NONCE_LENGTH - 1].copy_from_slice(&self.counter.to_be_bytes());
+        nonce[AEAD_NONCE_LENGTH - 1] = is_last as u8;
+
+        self.counter = self.counter.checked_add(1).ok_or_else(|| BSVErrors::ECIESError("Stream has too many chunks".into()))?;
+        Ok(nonce)
+    }
+
+    fn seal(&mut self, chunk: &[u8], is_last: bool) -> Result<Vec<u8>, BSVErrors> {
+        let nonce = self.next_nonce(is_last)?;
+        AES::encrypt_aead_impl(&self.key, &nonce, chunk, &self.header, AEADAlgorithms::AES128_GCM)
+    }
+
+    fn open(&mut self, sealed_chunk: &[u8], is_last: bool) -> Result<Vec<u8>, BSVErrors> {
+        let nonce = self.next_nonce(is_last)?;
+        AES::decrypt_aead_impl(&self.key, &nonce, sealed_chunk, &self.header, AEADAlgorithms::AES128_GCM)
+    }
+}
+
+fn finished_error() -> BSVErrors {
+    BSVErrors::ECIESError("Stream has already been finalised".into())
+}
+
+/**
+ * Encrypts a stream to a public key in authenticated chunks, with a fresh ephemeral key.
+ * Output is "BIES" | ephemeral public key | chunk size (u32 LE), then each chunk's ciphertext | tag. Only the last chunk may be shorter than the chunk size.
+ */
+#[wasm_bindgen]
+pub struct ECIESStreamEncryptor {
+    keys: StreamKeys,
+    buffer: Vec<u8>,
+    header_written: bool,
+    finished: bool,
+}
+
+impl ECIESStreamEncryptor {
+    pub(crate) fn new_impl(recipient_pub_key: &PublicKey, chunk_size: Option<u32>) -> Result<ECIESStreamEncryptor, BSVErrors> {
+        let chunk_size = chunk_size.unwrap_or(ECIES_STREAM_DEFAULT_CHUNK_SIZE);
+        if chunk_size == 0 || chunk_size > ECIES_STREAM_MAX_CHUNK_SIZE {
+            return Err(BSVErrors::ECIESError(format!("Chunk size must be between 1 and {} bytes", ECIES_STREAM_MAX_CHUNK_SIZE)));
+        }
+
+        let ephemeral_key = PrivateKey::from_random();
+        let mut header = STREAM_MAGIC_BYTES.to_vec();
+        header.extend_from_slice(&ephemeral_key.get_public_key_impl()?.to_bytes_impl()?);
+        header.extend_from_slice(&chunk_size.to_le_bytes());
+
+        Ok(ECIESStreamEncryptor {
+            keys: StreamKeys::new(&ephemeral_key, recipient_pub_key, header, chunk_size as usize)?,
+            buffer: vec![],
+            header_written: false,
+            finished: false,
+        })
+    }
+
+    fn take_header(&mut self) -> Vec<u8> {
+        match self.header_written {
+            true => vec![],
+            false => {
+                self.header_written = true;
+                self.keys.header.clone()
+            }
+        }
+    }
+
+    /**
+     * Returns the header and any chunks that are complete. A full chunk is held back until more data arrives, as it might be the last.
+     */
+    pub(crate) fn update_impl(&mut self, data: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        if self.finished {
+            return Err(finished_error());
+        }
+
+        self.buffer.extend_from_slice(data);
+        let mut output = self.take_header();
+
+        let mut offset = 0;
+        while self.buffer.len() - offset > self.keys.chunk_size {
+            let sealed_chunk = self.keys.seal(&self.buffer[offset..offset + self.keys.chunk_size], false)?;
+            output.extend_from_slice(&sealed_chunk);
+            offset += self.keys.chunk_size;
+        }
+        self.buffer.drain(..offset);
+
+        Ok(output)
+    }
+
+    pub(crate) fn finalise_impl(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        if self.finished {
+            return Err(finished_error());
+        }
+
+        let mut output = self.take_header();
+        output.extend_from_slice(&self.keys.seal(&self.buffer, true)?);
+        self.buffer.clear();
+        self.finished = true;
+
+        Ok(output)
+    }
+}
+
+/**
+ * Decrypts an ECIES stream from chunks of any size. Each chunk is authenticated before its plaintext is returned,
+ * but only finalise can confirm the stream was not truncated.
+ */
+#[wasm_bindgen]
+pub struct ECIESStreamDecryptor {
+    recipient_priv_key: PrivateKey,
+    keys: Option<StreamKeys>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl ECIESStreamDecryptor {
+    pub(crate) fn new_impl(recipient_priv_key: &PrivateKey) -> ECIESStreamDecryptor {
+        ECIESStreamDecryptor {
+            recipient_priv_key: recipient_priv_key.clone(),
+            keys: None,
+            buffer: vec![],
+            finished: false,
+        }
+    }
+
+    fn read_header(&mut self) -> Result<(), BSVErrors> {
+        let header = self.buffer[..STREAM_HEADER_LENGTH].to_vec();
+        if &header[..4] != STREAM_MAGIC_BYTES {
+            return Err(BSVErrors::ECIESError("Stream does not start with BIES".into()));
+        }
+
+        let ephemeral_pub_key = PublicKey::from_bytes_impl(&header[4..37])?;
+        let chunk_size = u32::from_le_bytes([header[37], header[38], header[39], header[40]]);
+        if chunk_size == 0 || chunk_size > ECIES_STREAM_MAX_CHUNK_SIZE {
+            return Err(BSVErrors::ECIESError(format!("Invalid stream chunk size {}", chunk_size)));
+        }
+
+        self.keys = Some(StreamKeys::new(&self.recipient_priv_key, &ephemeral_pub_key, header, chunk_size as usize)?);
+        self.buffer.drain(..STREAM_HEADER_LENGTH);
+        Ok(())
+    }
+
+    /**
+     * Returns the plaintext of every chunk that is complete.
+     */
+    pub(crate) fn update_impl(&mut self, data: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        if self.finished {
+            return Err(finished_error());
+        }
+
+        self.buffer.extend_from_slice(data);
+        if self.keys.is_none() {
+            if self.buffer.len() < STREAM_HEADER_LENGTH {
+                return Ok(vec![]);
+            }
+            self.read_header()?;
+        }
+
+        let keys = self.keys.as_mut().ok_or_else(finished_error)?;
+        let sealed_chunk_size = keys.chunk_size + AEAD_TAG_LENGTH;
+        let mut output = vec![];
+
+        let mut offset = 0;
+        while self.buffer.len() - offset > sealed_chunk_size {
+            output.extend_from_slice(&keys.open(&self.buffer[offset..offset + sealed_chunk_size], false)?);
+            offset += sealed_chunk_size;
+        }
+        self.buffer.drain(..offset);
+
+        Ok(output)
+    }
+
+    /**
+     * Returns the plaintext of the last chunk. Errors if the stream was truncated or modified.
+     */
+    pub(crate) fn finalise_impl(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        if self.finished {
+            return Err(finished_error());
+        }
+        self.finished = true;
+
+        let keys = match self.keys.as_mut() {
+            Some(v) => v,
+            None => return Err(BSVErrors::ECIESError("Stream ended before the header".into())),
+        };
+        if self.buffer.len() < AEAD_TAG_LENGTH {
+            return Err(BSVErrors::ECIESError("Stream is truncated".into()));
+        }
+
+        let plaintext = keys.open(&self.buffer, true)?;
+        self.buffer.clear();
+        Ok(plaintext)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl ECIESStreamEncryptor {
+    /**
+     * chunk_size defaults to 64 KiB.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new(recipient_pub_key: &PublicKey, chunk_size: Option<u32>) -> Result<ECIESStreamEncryptor, JsValue> {
+        match ECIESStreamEncryptor::new_impl(recipient_pub_key, chunk_size) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the bytes to write so far, possibly empty.
+     */
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+        match self.update_impl(data) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the last chunk, which must be written for the stream to decrypt.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, JsValue> {
+        match self.finalise_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl ECIESStreamDecryptor {
+    #[wasm_bindgen(constructor)]
+    pub fn new(recipient_priv_key: &PrivateKey) -> ECIESStreamDecryptor {
+        ECIESStreamDecryptor::new_impl(recipient_priv_key)
+    }
+
+    /**
+     * Returns the plaintext of every chunk that is complete, possibly empty.
+     */
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+        match self.update_impl(data) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the plaintext of the last chunk. Throws if the stream was truncated or modified.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, JsValue> {
+        match self.finalise_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECIESStreamEncryptor {
+    /**
+     * chunk_size defaults to 64 KiB.
+     */
+    pub fn new(recipient_pub_key: &PublicKey, chunk_size: Option<u32>) -> Result<ECIESStreamEncryptor, BSVErrors> {
+        ECIESStreamEncryptor::new_impl(recipient_pub_key, chunk_size)
+    }
+
+    /**
+     * Returns the bytes to write so far, possibly empty.
+     */
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        self.update_impl(data)
+    }
+
+    /**
+     * Returns the last chunk, which must be written for the stream to decrypt.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        self.finalise_impl()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECIESStreamDecryptor {
+    pub fn new(recipient_priv_key: &PrivateKey) -> ECIESStreamDecryptor {
+        ECIESStreamDecryptor::new_impl(recipient_priv_key)
+    }
+
+    /**
+     * Returns the plaintext of every chunk that is complete, possibly empty.
+     */
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        self.update_impl(data)
+    }
+
+    /**
+     * Returns the plaintext of the last chunk. Errors if the stream was truncated or modified.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        self.finalise_impl()
+    }
+}
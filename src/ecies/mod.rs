@@ -3,14 +3,22 @@ use elliptic_curve::sec1::ToEncodedPoint;
 use k256::ecdh::{self, *};
 use k256::{ProjectivePoint, PublicKey as K256PublicKey, SecretKey};
 use rand_core::OsRng;
+use subtle::{Choice, ConstantTimeEq};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{throw_str, JsValue};
+use zeroize::{Zeroize, Zeroizing};
 
 pub mod ecies_ciphertext;
 pub use ecies_ciphertext::*;
 
+pub mod ecies_envelope;
+pub use ecies_envelope::*;
+
+pub mod ecies_stream;
+pub use ecies_stream::*;
+
 /**
- * Electrum compatible ECIES implementation.
+ * Electrum compatible ECIES implementation, see ECIESMode.
  * Comparable to Ecies.electrumEncrypt in BSV.JS
  */
 pub struct ECIES {}
@@ -22,6 +30,28 @@ pub struct CipherKeys {
     pub(crate) km: Vec<u8>,
 }
 
+impl Drop for CipherKeys {
+    fn drop(&mut self) {
+        self.iv.zeroize();
+        self.ke.zeroize();
+        self.km.zeroize();
+    }
+}
+
+impl ConstantTimeEq for CipherKeys {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.iv.ct_eq(&other.iv) & self.ke.ct_eq(&other.ke) & self.km.ct_eq(&other.km)
+    }
+}
+
+impl PartialEq for CipherKeys {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for CipherKeys {}
+
 #[wasm_bindgen]
 impl CipherKeys {
     pub fn get_iv(&self) -> Vec<u8> {
@@ -35,9 +65,20 @@ impl CipherKeys {
     pub fn get_km(&self) -> Vec<u8> {
         self.km.clone()
     }
+
+    /**
+     * Constant time comparison of two sets of cipher keys.
+     */
+    pub fn equals(&self, other: &CipherKeys) -> bool {
+        self == other
+    }
 }
 
 impl ECIES {
+    /**
+     * BIE1 encryption with the sender's own key. There is no ECIESMode parameter: Electrum mode fixes both of the choices
+     * this exposes (a fresh ephemeral key that is always included), so it has its own encrypt_electrum_impl instead.
+     */
     pub(crate) fn encrypt_impl(message: &[u8], private_key: &PrivateKey, recipient_pub_key: &PublicKey, exclude_pub_key: bool) -> Result<ECIESCiphertext, BSVErrors> {
         let cipher = ECIES::derive_cipher_keys_impl(private_key, recipient_pub_key)?;
         let cipher_text = AES::encrypt_impl(&cipher.ke, &cipher.iv, message, crate::AESAlgorithms::AES128_CBC)?;
@@ -73,6 +114,25 @@ impl ECIES {
         ECIES::encrypt_impl(message, &private_key, recipient_pub_key, false)
     }
 
+    /**
+     * Electrum encryption: a fresh ephemeral key, always included in the ciphertext.
+     */
+    pub(crate) fn encrypt_electrum_impl(message: &[u8], recipient_pub_key: &PublicKey) -> Result<ECIESCiphertext, BSVErrors> {
+        ECIES::encrypt_with_ephemeral_private_key_impl(message, recipient_pub_key)
+    }
+
+    /**
+     * Electrum decryption: the ECDH is done with the ephemeral public key included in the ciphertext.
+     */
+    pub(crate) fn decrypt_electrum_impl(ciphertext: &ECIESCiphertext, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        let ephemeral_pub_key = ciphertext.extract_public_key_impl()?;
+        ECIES::decrypt_impl(ciphertext, recipient_priv_key, &ephemeral_pub_key)
+    }
+
+    /**
+     * Decrypts with the given sender public key, which may be left out of BIE1 ciphertexts.
+     * Electrum mode always takes it from the ciphertext, see decrypt_electrum_impl.
+     */
     pub(crate) fn decrypt_impl(ciphertext: &ECIESCiphertext, recipient_priv_key: &PrivateKey, sender_pub_key: &PublicKey) -> Result<Vec<u8>, BSVErrors> {
         let cipher_keys = ECIES::derive_cipher_keys_impl(recipient_priv_key, sender_pub_key)?;
 
@@ -86,7 +146,7 @@ impl ECIES {
 
         let verify_hmac = Hash::sha_256_hmac(&preimage, &cipher_keys.km);
 
-        if hmac != &verify_hmac.to_bytes() {
+        if !bool::from(hmac.ct_eq(&verify_hmac.0)) {
             return Err(BSVErrors::ECIESError("Invalid Checksum".into()));
         }
 
@@ -95,14 +155,16 @@ impl ECIES {
     }
 
     pub(crate) fn derive_cipher_keys_impl(priv_key: &PrivateKey, pub_key: &PublicKey) -> Result<CipherKeys, BSVErrors> {
-        let private_scalar = *priv_key.secret_key.to_secret_scalar();
         let pub_key_point = K256PublicKey::from_sec1_bytes(&pub_key.to_bytes_impl()?)?.to_projective();
 
+        let mut private_scalar = *priv_key.secret_key.to_secret_scalar();
         let shared_point = pub_key_point * private_scalar;
+        private_scalar.zeroize();
+
         let shared_pub = K256PublicKey::from_affine(shared_point.to_affine())?;
 
         let shared_mod_point = shared_pub.to_encoded_point(true);
-        let hash = Hash::sha_512(shared_mod_point.as_bytes()).to_bytes();
+        let hash = Zeroizing::new(Hash::sha_512(shared_mod_point.as_bytes()).0);
 
         Ok(CipherKeys {
             iv: hash[0..16].into(),
@@ -141,6 +203,28 @@ impl ECIES {
         }
     }
 
+    /**
+     * Encrypts the way Electrum does, with an ephemeral key that is included in the ciphertext. Use toBase64 for Electrum's encoding.
+     */
+    #[wasm_bindgen(js_name = encryptElectrum)]
+    pub fn encrypt_electrum(message: &[u8], recipient_pub_key: &PublicKey) -> Result<ECIESCiphertext, JsValue> {
+        match ECIES::encrypt_electrum_impl(message, recipient_pub_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Decrypts an Electrum message, eg. ECIESCiphertext.fromBase64(message, ECIESMode.Electrum).
+     */
+    #[wasm_bindgen(js_name = decryptElectrum)]
+    pub fn decrypt_electrum(ciphertext: &ECIESCiphertext, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, JsValue> {
+        match ECIES::decrypt_electrum_impl(ciphertext, recipient_priv_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
     #[wasm_bindgen(js_name = deriveCipherKeys)]
     pub fn derive_cipher_keys(priv_key: &PrivateKey, pub_key: &PublicKey) -> Result<CipherKeys, JsValue> {
         match ECIES::derive_cipher_keys_impl(priv_key, pub_key) {
@@ -168,6 +252,20 @@ impl ECIES {
         ECIES::decrypt_impl(ciphertext, recipient_priv_key, sender_pub_key)
     }
 
+    /**
+     * Encrypts the way Electrum does, with an ephemeral key that is included in the ciphertext. Use to_base64 for Electrum's encoding.
+     */
+    pub fn encrypt_electrum(message: &[u8], recipient_pub_key: &PublicKey) -> Result<ECIESCiphertext, BSVErrors> {
+        ECIES::encrypt_electrum_impl(message, recipient_pub_key)
+    }
+
+    /**
+     * Decrypts an Electrum message, eg. ECIESCiphertext::from_base64(message, ECIESMode::Electrum).
+     */
+    pub fn decrypt_electrum(ciphertext: &ECIESCiphertext, recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        ECIES::decrypt_electrum_impl(ciphertext, recipient_priv_key)
+    }
+
     pub fn derive_cipher_keys(priv_key: &PrivateKey, pub_key: &PublicKey) -> Result<CipherKeys, BSVErrors> {
         ECIES::derive_cipher_keys_impl(priv_key, pub_key)
     }
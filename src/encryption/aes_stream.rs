@@ -0,0 +1,263 @@
+use crate::encryption::AesGcm;
+use crate::BSVErrors;
+use aes::{
+    cipher::{NewCipher, StreamCipher},
+    Aes128, Aes128Ctr, Aes256, Aes256Ctr,
+};
+use block_modes::InvalidKeyIvLength;
+use subtle::ConstantTimeEq;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+/**
+ * Modes that can encrypt a message in chunks. CTR is not authenticated, GCM finalises with a 16 byte tag.
+ */
+#[wasm_bindgen]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum AESStreamAlgorithms {
+    AES128_CTR,
+    AES256_CTR,
+    AES128_GCM,
+    AES256_GCM,
+}
+
+enum StreamState {
+    Aes128Ctr(Box<Aes128Ctr>),
+    Aes256Ctr(Box<Aes256Ctr>),
+    Aes128Gcm(Box<AesGcm<Aes128>>),
+    Aes256Gcm(Box<AesGcm<Aes256>>),
+}
+
+impl StreamState {
+    fn new(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<StreamState, BSVErrors> {
+        if !aad.is_empty() && matches!(algo, AESStreamAlgorithms::AES128_CTR | AESStreamAlgorithms::AES256_CTR) {
+            return Err(BSVErrors::AESStreamError("CTR cannot authenticate associated data, use GCM".into()));
+        }
+
+        let state = match algo {
+            AESStreamAlgorithms::AES128_CTR => StreamState::Aes128Ctr(Box::new(Aes128Ctr::new_from_slices(key, iv).map_err(|_| InvalidKeyIvLength)?)),
+            AESStreamAlgorithms::AES256_CTR => StreamState::Aes256Ctr(Box::new(Aes256Ctr::new_from_slices(key, iv).map_err(|_| InvalidKeyIvLength)?)),
+            AESStreamAlgorithms::AES128_GCM if !iv.is_empty() => StreamState::Aes128Gcm(Box::new(AesGcm::new_128(key, iv, aad).ok_or(InvalidKeyIvLength)?)),
+            AESStreamAlgorithms::AES256_GCM if !iv.is_empty() => StreamState::Aes256Gcm(Box::new(AesGcm::new_256(key, iv, aad).ok_or(InvalidKeyIvLength)?)),
+            _ => return Err(InvalidKeyIvLength.into()),
+        };
+
+        Ok(state)
+    }
+
+    fn update(&mut self, chunk: &[u8], encrypt: bool) -> Vec<u8> {
+        match self {
+            StreamState::Aes128Ctr(c) => StreamState::ctr_update(c.as_mut(), chunk),
+            StreamState::Aes256Ctr(c) => StreamState::ctr_update(c.as_mut(), chunk),
+            StreamState::Aes128Gcm(c) if encrypt => c.encrypt_update(chunk),
+            StreamState::Aes128Gcm(c) => c.decrypt_update(chunk),
+            StreamState::Aes256Gcm(c) if encrypt => c.encrypt_update(chunk),
+            StreamState::Aes256Gcm(c) => c.decrypt_update(chunk),
+        }
+    }
+
+    fn ctr_update<T: StreamCipher>(cipher: &mut T, chunk: &[u8]) -> Vec<u8> {
+        let mut buffer = chunk.to_vec();
+        cipher.apply_keystream(&mut buffer);
+        buffer
+    }
+
+    /**
+     * The GCM tag, empty for CTR.
+     */
+    fn finalise(self) -> Vec<u8> {
+        match self {
+            StreamState::Aes128Ctr(_) | StreamState::Aes256Ctr(_) => vec![],
+            StreamState::Aes128Gcm(c) => c.finalise().to_vec(),
+            StreamState::Aes256Gcm(c) => c.finalise().to_vec(),
+        }
+    }
+}
+
+/**
+ * Encrypts a message chunk by chunk, so large files never need to be in memory at once.
+ * Call update with each chunk, in order, then finalise for the GCM tag.
+ */
+#[wasm_bindgen]
+pub struct AESEncryptor {
+    state: Option<StreamState>,
+}
+
+/**
+ * Decrypts a message chunk by chunk, then checks the GCM tag in finalise.
+ * Until finalise succeeds the plaintext is unauthenticated: do not act on it before then.
+ */
+#[wasm_bindgen]
+pub struct AESDecryptor {
+    state: Option<StreamState>,
+}
+
+fn finished_error() -> BSVErrors {
+    BSVErrors::AESStreamError("Stream has already been finalised".into())
+}
+
+impl AESEncryptor {
+    /**
+     * aad must be empty for CTR.
+     */
+    pub(crate) fn new_impl(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESEncryptor, BSVErrors> {
+        Ok(AESEncryptor {
+            state: Some(StreamState::new(key, iv, aad, algo)?),
+        })
+    }
+
+    pub(crate) fn update_impl(&mut self, chunk: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        match &mut self.state {
+            Some(v) => Ok(v.update(chunk, true)),
+            None => Err(finished_error()),
+        }
+    }
+
+    pub(crate) fn finalise_impl(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        match self.state.take() {
+            Some(v) => Ok(v.finalise()),
+            None => Err(finished_error()),
+        }
+    }
+}
+
+impl AESDecryptor {
+    /**
+     * aad must be empty for CTR.
+     */
+    pub(crate) fn new_impl(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESDecryptor, BSVErrors> {
+        Ok(AESDecryptor {
+            state: Some(StreamState::new(key, iv, aad, algo)?),
+        })
+    }
+
+    pub(crate) fn update_impl(&mut self, chunk: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        match &mut self.state {
+            Some(v) => Ok(v.update(chunk, false)),
+            None => Err(finished_error()),
+        }
+    }
+
+    /**
+     * Errors with InvalidAuthenticationTag unless the tag matches. CTR takes an empty tag.
+     */
+    pub(crate) fn finalise_impl(&mut self, tag: &[u8]) -> Result<(), BSVErrors> {
+        let expected_tag = match self.state.take() {
+            Some(v) => v.finalise(),
+            None => return Err(finished_error()),
+        };
+
+        if !bool::from(expected_tag.ct_eq(tag)) {
+            return Err(BSVErrors::InvalidAuthenticationTag);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl AESEncryptor {
+    #[wasm_bindgen(constructor)]
+    pub fn new(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESEncryptor, JsValue> {
+        match AESEncryptor::new_impl(key, iv, aad, algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the ciphertext of this chunk.
+     */
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, JsValue> {
+        match self.update_impl(chunk) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the 16 byte GCM tag, or an empty array for CTR.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, JsValue> {
+        match self.finalise_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl AESDecryptor {
+    #[wasm_bindgen(constructor)]
+    pub fn new(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESDecryptor, JsValue> {
+        match AESDecryptor::new_impl(key, iv, aad, algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the unauthenticated plaintext of this chunk.
+     */
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, JsValue> {
+        match self.update_impl(chunk) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Throws unless the tag matches. CTR takes an empty tag.
+     */
+    pub fn finalise(&mut self, tag: &[u8]) -> Result<(), JsValue> {
+        match self.finalise_impl(tag) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AESEncryptor {
+    pub fn new(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESEncryptor, BSVErrors> {
+        AESEncryptor::new_impl(key, iv, aad, algo)
+    }
+
+    /**
+     * Returns the ciphertext of this chunk.
+     */
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        self.update_impl(chunk)
+    }
+
+    /**
+     * Returns the 16 byte GCM tag, or an empty Vec for CTR.
+     */
+    pub fn finalise(&mut self) -> Result<Vec<u8>, BSVErrors> {
+        self.finalise_impl()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AESDecryptor {
+    pub fn new(key: &[u8], iv: &[u8], aad: &[u8], algo: AESStreamAlgorithms) -> Result<AESDecryptor, BSVErrors> {
+        AESDecryptor::new_impl(key, iv, aad, algo)
+    }
+
+    /**
+     * Returns the unauthenticated plaintext of this chunk.
+     */
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        self.update_impl(chunk)
+    }
+
+    /**
+     * Errors with InvalidAuthenticationTag unless the tag matches. CTR takes an empty tag.
+     */
+    pub fn finalise(&mut self, tag: &[u8]) -> Result<(), BSVErrors> {
+        self.finalise_impl(tag)
+    }
+}
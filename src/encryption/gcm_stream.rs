@@ -0,0 +1,141 @@
+use aes::{
+    cipher::{consts::U16, generic_array::ArrayLength, BlockCipher, BlockEncrypt, FromBlockCipher, NewBlockCipher, StreamCipher},
+    Aes128, Aes256, Block,
+};
+use ctr::Ctr32BE;
+use ghash::{
+    universal_hash::{NewUniversalHash, UniversalHash},
+    GHash,
+};
+
+use crate::encryption::{AEAD_NONCE_LENGTH, AEAD_TAG_LENGTH};
+
+/**
+ * Incremental GCM (NIST SP 800-38D) over AES with a 128 bit tag, for AESEncryptor and AESDecryptor.
+ * The aes-gcm crate only encrypts whole messages, so this drives the same GHASH and CTR32 primitives it is built on.
+ * Nonces are usually 96 bits, other lengths are hashed into the initial counter block.
+ *
+ * Each AesGcm encrypts or decrypts one message: update with chunks of any size, then finalise for the tag.
+ */
+pub(crate) struct AesGcm<C>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt,
+    C::ParBlocks: ArrayLength<Block>,
+{
+    ctr: Ctr32BE<C>,
+    ghash: GHash,
+    tag_mask: [u8; 16],
+    ghash_block: [u8; 16],
+    ghash_offset: usize,
+    aad_length: u64,
+    ciphertext_length: u64,
+}
+
+impl AesGcm<Aes128> {
+    pub(crate) fn new_128(key: &[u8], nonce: &[u8], aad: &[u8]) -> Option<Self> {
+        Aes128::new_from_slice(key).ok().map(|x| AesGcm::from_cipher(x, nonce, aad))
+    }
+}
+
+impl AesGcm<Aes256> {
+    pub(crate) fn new_256(key: &[u8], nonce: &[u8], aad: &[u8]) -> Option<Self> {
+        Aes256::new_from_slice(key).ok().map(|x| AesGcm::from_cipher(x, nonce, aad))
+    }
+}
+
+impl<C> AesGcm<C>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt,
+    C::ParBlocks: ArrayLength<Block>,
+{
+    fn from_cipher(cipher: C, nonce: &[u8], aad: &[u8]) -> Self {
+        let mut h = Block::default();
+        cipher.encrypt_block(&mut h);
+        let ghash = GHash::new(&h);
+
+        // J0: nonce | 1 for 96 bit nonces, otherwise GHASH(nonce | lengths)
+        let initial_counter_block = match nonce.len() == AEAD_NONCE_LENGTH {
+            true => {
+                let mut block = Block::default();
+                block[..AEAD_NONCE_LENGTH].copy_from_slice(nonce);
+                block[15] = 1;
+                block
+            }
+            false => {
+                let mut nonce_hash = ghash.clone();
+                nonce_hash.update_padded(nonce);
+                let mut lengths = Block::default();
+                lengths[8..].copy_from_slice(&(nonce.len() as u64 * 8).to_be_bytes());
+                nonce_hash.update(&lengths);
+                nonce_hash.finalize().into_bytes()
+            }
+        };
+
+        // The first keystream block masks the tag, the message starts at the next counter
+        let mut ctr = Ctr32BE::from_block_cipher(cipher, &initial_counter_block);
+        let mut tag_mask = [0; 16];
+        ctr.apply_keystream(&mut tag_mask);
+
+        let mut gcm = AesGcm {
+            ctr,
+            ghash,
+            tag_mask,
+            ghash_block: [0; 16],
+            ghash_offset: 0,
+            aad_length: aad.len() as u64,
+            ciphertext_length: 0,
+        };
+        gcm.ghash.update_padded(aad);
+        gcm
+    }
+
+    /**
+     * Buffers the ciphertext into whole blocks for GHASH, as chunks can be any size.
+     */
+    fn absorb(&mut self, data: &[u8]) {
+        for x in data {
+            self.ghash_block[self.ghash_offset] = *x;
+            self.ghash_offset += 1;
+
+            if self.ghash_offset == 16 {
+                self.ghash.update(Block::from_slice(&self.ghash_block));
+                self.ghash_offset = 0;
+            }
+        }
+    }
+
+    pub(crate) fn encrypt_update(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let mut buffer = plaintext.to_vec();
+        self.ctr.apply_keystream(&mut buffer);
+        self.absorb(&buffer);
+        self.ciphertext_length += buffer.len() as u64;
+
+        buffer
+    }
+
+    pub(crate) fn decrypt_update(&mut self, ciphertext: &[u8]) -> Vec<u8> {
+        self.absorb(ciphertext);
+        self.ciphertext_length += ciphertext.len() as u64;
+
+        let mut buffer = ciphertext.to_vec();
+        self.ctr.apply_keystream(&mut buffer);
+        buffer
+    }
+
+    /**
+     * The tag over the AAD and all of the ciphertext so far.
+     */
+    pub(crate) fn finalise(mut self) -> [u8; AEAD_TAG_LENGTH] {
+        self.ghash.update_padded(&self.ghash_block[..self.ghash_offset]);
+
+        let mut lengths = Block::default();
+        lengths[..8].copy_from_slice(&(self.aad_length * 8).to_be_bytes());
+        lengths[8..].copy_from_slice(&(self.ciphertext_length * 8).to_be_bytes());
+        self.ghash.update(&lengths);
+
+        let mut tag = [0; AEAD_TAG_LENGTH];
+        tag.copy_from_slice(&self.ghash.finalize().into_bytes());
+        tag.iter_mut().zip(self.tag_mask.iter()).for_each(|(x, mask)| *x ^= mask);
+        tag
+    }
+}
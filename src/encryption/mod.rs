@@ -1,11 +1,29 @@
 use crate::BSVErrors;
 use aes::{
-    cipher::{NewCipher, StreamCipher, StreamCipherSeek},
-    Aes128, Aes128Ctr, Aes256, Aes256Ctr,
+    cipher::{
+        consts::{U12, U16, U32},
+        generic_array::{ArrayLength, GenericArray},
+        BlockCipher, BlockEncrypt, NewBlockCipher, NewCipher, StreamCipher, StreamCipherSeek,
+    },
+    Aes128, Aes128Ctr, Aes256, Aes256Ctr, Block,
 };
-use block_modes::{block_padding::Pkcs7, BlockMode, Cbc, Cfb, Ige, Pcbc};
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    AesGcm as AesGcmAead,
+};
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc, Cfb, Ige, InvalidKeyIvLength, Pcbc};
+use chacha20poly1305::ChaCha20Poly1305;
 use wasm_bindgen::{prelude::*, throw_str};
 
+mod gcm_stream;
+pub(crate) use gcm_stream::*;
+
+mod aes_stream;
+pub use aes_stream::*;
+
+pub const AEAD_NONCE_LENGTH: usize = 12;
+pub const AEAD_TAG_LENGTH: usize = 16;
+
 #[wasm_bindgen]
 pub struct AES {}
 
@@ -19,6 +37,19 @@ pub enum AESAlgorithms {
     AES256_CTR,
 }
 
+/**
+ * Authenticated encryption with associated data, from the RustCrypto aes-gcm and chacha20poly1305 crates. All append a 16 byte tag to the ciphertext.
+ * Nonces are 12 bytes, GCM also accepts 16 and 32 (eg. the IVs of BRC-2). Never reuse a nonce with the same key.
+ */
+#[wasm_bindgen]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum AEADAlgorithms {
+    AES128_GCM,
+    AES256_GCM,
+    ChaCha20_Poly1305,
+}
+
 impl AES {
     pub fn encrypt_impl(key: &[u8], iv: &[u8], message: &[u8], algo: AESAlgorithms) -> Result<Vec<u8>, BSVErrors> {
         let result = match algo {
@@ -41,6 +72,60 @@ impl AES {
         Ok(result)
     }
 
+    /**
+     * Returns ciphertext | tag. The associated data is authenticated but not encrypted.
+     */
+    pub(crate) fn encrypt_aead_impl(key: &[u8], nonce: &[u8], message: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, BSVErrors> {
+        match algo {
+            AEADAlgorithms::AES128_GCM => AES::aes_gcm::<Aes128>(key, nonce, message, aad, true),
+            AEADAlgorithms::AES256_GCM => AES::aes_gcm::<Aes256>(key, nonce, message, aad, true),
+            AEADAlgorithms::ChaCha20_Poly1305 if nonce.len() == AEAD_NONCE_LENGTH => AES::aead::<ChaCha20Poly1305>(key, nonce, message, aad, true),
+            _ => Err(InvalidKeyIvLength.into()),
+        }
+    }
+
+    /**
+     * Takes ciphertext | tag, errors with InvalidAuthenticationTag if the ciphertext or associated data was modified or the key is wrong.
+     */
+    pub(crate) fn decrypt_aead_impl(key: &[u8], nonce: &[u8], ciphertext: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, BSVErrors> {
+        match algo {
+            AEADAlgorithms::AES128_GCM => AES::aes_gcm::<Aes128>(key, nonce, ciphertext, aad, false),
+            AEADAlgorithms::AES256_GCM => AES::aes_gcm::<Aes256>(key, nonce, ciphertext, aad, false),
+            AEADAlgorithms::ChaCha20_Poly1305 if nonce.len() == AEAD_NONCE_LENGTH => AES::aead::<ChaCha20Poly1305>(key, nonce, ciphertext, aad, false),
+            _ => Err(InvalidKeyIvLength.into()),
+        }
+    }
+
+    /**
+     * aes-gcm fixes the nonce length at compile time, so this picks the instance for the nonce given.
+     */
+    fn aes_gcm<C>(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8], encrypt: bool) -> Result<Vec<u8>, BSVErrors>
+    where
+        C: NewBlockCipher + BlockCipher<BlockSize = U16> + BlockEncrypt,
+        C::ParBlocks: ArrayLength<Block>,
+    {
+        match nonce.len() {
+            12 => AES::aead::<AesGcmAead<C, U12>>(key, nonce, data, aad, encrypt),
+            16 => AES::aead::<AesGcmAead<C, U16>>(key, nonce, data, aad, encrypt),
+            32 => AES::aead::<AesGcmAead<C, U32>>(key, nonce, data, aad, encrypt),
+            _ => Err(InvalidKeyIvLength.into()),
+        }
+    }
+
+    /**
+     * The nonce must already be the cipher's nonce length.
+     */
+    fn aead<A: NewAead + Aead>(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8], encrypt: bool) -> Result<Vec<u8>, BSVErrors> {
+        let cipher = A::new_from_slice(key).map_err(|_| InvalidKeyIvLength)?;
+        let nonce = GenericArray::from_slice(nonce);
+        let payload = Payload { msg: data, aad };
+
+        match encrypt {
+            true => cipher.encrypt(nonce, payload).map_err(|_| BSVErrors::AEADMessageTooLong),
+            false => cipher.decrypt(nonce, payload).map_err(|_| BSVErrors::InvalidAuthenticationTag),
+        }
+    }
+
     fn aes_ctr<T: NewCipher + StreamCipherSeek + StreamCipher>(key: &[u8], iv: &[u8], message: &[u8]) -> Vec<u8> {
         let data = &mut message.to_vec();
         let mut cipher = T::new(key.into(), iv.into());
@@ -68,6 +153,28 @@ impl AES {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    /**
+     * Encrypts with an authenticated mode, returns ciphertext | tag. Pass an empty aad if there is no associated data.
+     */
+    #[wasm_bindgen(js_name = encryptAEAD)]
+    pub fn encrypt_aead(key: &[u8], nonce: &[u8], message: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, JsValue> {
+        match AES::encrypt_aead_impl(key, nonce, message, aad, algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Decrypts ciphertext | tag, throws if the tag does not verify.
+     */
+    #[wasm_bindgen(js_name = decryptAEAD)]
+    pub fn decrypt_aead(key: &[u8], nonce: &[u8], ciphertext: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, JsValue> {
+        match AES::decrypt_aead_impl(key, nonce, ciphertext, aad, algo) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -79,4 +186,18 @@ impl AES {
     pub fn decrypt(key: &[u8], iv: &[u8], message: &[u8], algo: AESAlgorithms) -> Result<Vec<u8>, BSVErrors> {
         AES::decrypt_impl(key, iv, message, algo)
     }
+
+    /**
+     * Encrypts with an authenticated mode, returns ciphertext | tag. Pass an empty aad if there is no associated data.
+     */
+    pub fn encrypt_aead(key: &[u8], nonce: &[u8], message: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, BSVErrors> {
+        AES::encrypt_aead_impl(key, nonce, message, aad, algo)
+    }
+
+    /**
+     * Decrypts ciphertext | tag, errors with InvalidAuthenticationTag if the tag does not verify.
+     */
+    pub fn decrypt_aead(key: &[u8], nonce: &[u8], ciphertext: &[u8], aad: &[u8], algo: AEADAlgorithms) -> Result<Vec<u8>, BSVErrors> {
+        AES::decrypt_aead_impl(key, nonce, ciphertext, aad, algo)
+    }
 }
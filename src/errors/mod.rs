@@ -32,6 +32,13 @@ pub enum BSVErrors {
         bs58::decode::Error,
     ),
 
+    #[error("{0}")]
+    Base64Decode(
+        #[source]
+        #[from]
+        base64::DecodeError,
+    ),
+
     #[error("{0}")]
     Io(
         #[source]
@@ -67,6 +74,21 @@ pub enum BSVErrors {
         block_modes::InvalidKeyIvLength,
     ),
 
+    #[error("Authentication tag does not match, the ciphertext or associated data was modified or the key is wrong")]
+    InvalidAuthenticationTag,
+
+    #[error("Message is too long to encrypt under one nonce")]
+    AEADMessageTooLong,
+
+    #[error("Stream encryption error: {0}")]
+    AESStreamError(String),
+
+    #[error("Invalid KDF parameters: {0}")]
+    KDFError(String),
+
+    #[error("HMAC-DRBG error: {0}")]
+    DRBGError(String),
+
     #[error("{0}")]
     BlockModeError(
         #[source]
@@ -119,6 +141,50 @@ pub enum BSVErrors {
     #[error("{0}")]
     ECIESError(String),
 
+    #[error("{0}")]
+    BRCError(String),
+
+    #[error("Vault error: {0}")]
+    VaultError(String),
+
+    #[error("Shamir secret sharing error: {0}")]
+    ShamirError(String),
+
+    #[error("Signer error: {0}")]
+    SignerError(String),
+
+    #[error("Unable to produce OP_PUSH_TX signature: {0}")]
+    PushTxSigning(String),
+
+    //=========== Extended Key Errors ==============
+    #[error("Invalid extended key length: expected 82 bytes, got {0}")]
+    InvalidExtendedKeyLength(usize),
+
+    #[error("Invalid extended key checksum: expected {0}, got {1}")]
+    InvalidExtendedKeyChecksum(String, String),
+
+    #[error("Unsupported extended key version bytes: {0:#010x}")]
+    InvalidExtendedKeyVersion(u32),
+
+    #[error("Inconsistent extended key: {0}")]
+    InconsistentExtendedKey(String),
+
+    #[error("Invalid extended key data prefix: {0:#04x}")]
+    InvalidExtendedKeyPrefix(u8),
+
+    //=========== Signature Validation Errors ==============
+    #[error("Signature is not strict DER (BIP66): {0}")]
+    NonStrictDERSignature(String),
+
+    #[error("Signature S value is greater than half the curve order")]
+    HighSSignature,
+
+    #[error("Invalid compact signature: {0}")]
+    InvalidCompactSignature(String),
+
+    #[error("Undefined sighash type: {0:#04x}")]
+    UndefinedSighashType(u8),
+
     //=========== Serialisation Errors ==============
     #[error("Error deserialising transaction field {0}: {1}")]
     DeserialiseTransaction(String, #[source] std::io::Error),
@@ -138,6 +204,12 @@ pub enum BSVErrors {
     #[error("Error serialising TxIn field {0}: {1}")]
     SerialiseTxIn(String, #[source] std::io::Error),
 
+    #[error("Error deserialising sighash preimage field {0}: {1}")]
+    DeserialiseSighashPreimage(String, #[source] std::io::Error),
+
+    #[error("Invalid sighash preimage: {0}")]
+    InvalidSighashPreimage(String),
+
     #[error("Error deserialising TxOut field {0}: {1}")]
     DeserialiseTxOut(String, #[source] std::io::Error),
 
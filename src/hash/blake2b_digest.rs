@@ -0,0 +1,52 @@
+use blake2::VarBlake2b;
+use digest::{
+    consts::{U128, U32},
+    BlockInput, FixedOutputDirty, Reset, Update, VariableOutputDirty,
+};
+
+pub use blake2::Blake2b as Blake2b512;
+
+/**
+ * BLAKE2b keys are 1 to 64 bytes.
+ */
+pub const BLAKE2B_MAX_KEY_LENGTH: usize = 64;
+
+/**
+ * BLAKE2b-256 (RFC 7693). The blake2 crate only offers a 32 byte digest as VarBlake2b, this makes it a fixed output Digest.
+ */
+#[derive(Clone)]
+pub struct Blake2b256 {
+    engine: VarBlake2b,
+}
+
+impl Default for Blake2b256 {
+    fn default() -> Self {
+        Blake2b256 {
+            engine: VarBlake2b::new_keyed(&[], 32),
+        }
+    }
+}
+
+impl BlockInput for Blake2b256 {
+    type BlockSize = U128;
+}
+
+impl Update for Blake2b256 {
+    fn update(&mut self, input: impl AsRef<[u8]>) {
+        self.engine.update(input)
+    }
+}
+
+impl FixedOutputDirty for Blake2b256 {
+    type OutputSize = U32;
+
+    fn finalize_into_dirty(&mut self, out: &mut digest::Output<Self>) {
+        self.engine.finalize_variable_dirty(|result| out.copy_from_slice(result))
+    }
+}
+
+impl Reset for Blake2b256 {
+    fn reset(&mut self) {
+        self.engine.reset()
+    }
+}
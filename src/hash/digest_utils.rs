@@ -1,12 +1,93 @@
-use digest::{consts::U32, Digest};
+use digest::{
+    consts::{U32, U64},
+    BlockInput, Digest, FixedOutput, Reset, Update,
+};
+use sha2::Sha512Trunc256;
 
-use crate::{ReversibleDigest, Sha256r, SigningHash};
+use crate::{Blake2b256, Blake3, Keccak256, ReversibleDigest, Sha256r, Sha3_256, SigningHash};
 
 pub trait Digest32 = digest::FixedOutput<OutputSize = digest::consts::U32> + digest::BlockInput + Clone + Default + digest::Reset + digest::Update + crate::ReversibleDigest;
 
-pub fn get_hash_digest(hash_algo: SigningHash, preimage: &[u8]) -> impl Digest32 {
+/**
+ * The message digest for signing. SHA256 and SHA256d are computed by the engine as before,
+ * the other algorithms are hashed up front and returned as is.
+ *
+ * The default (reset) state is plain SHA256, so RFC 6979 nonces are always derived with HMAC-SHA256
+ * whichever algorithm hashed the message.
+ */
+#[derive(Clone, Default)]
+pub struct SigningDigest {
+    engine: Sha256r,
+    prehashed: Option<[u8; 32]>,
+    reverse: bool,
+}
+
+impl SigningDigest {
+    fn prehashed<D: Digest<OutputSize = U32>>(preimage: &[u8]) -> Self {
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&D::digest(preimage));
+        SigningDigest {
+            prehashed: Some(digest),
+            ..Default::default()
+        }
+    }
+}
+
+impl ReversibleDigest for SigningDigest {
+    fn reverse(&self) -> Self {
+        SigningDigest {
+            engine: self.engine.reverse(),
+            prehashed: self.prehashed,
+            reverse: true,
+        }
+    }
+}
+
+impl BlockInput for SigningDigest {
+    type BlockSize = U64;
+}
+
+impl Update for SigningDigest {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        Update::update(&mut self.engine, data);
+    }
+}
+
+impl FixedOutput for SigningDigest {
+    type OutputSize = U32;
+
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        match self.prehashed {
+            Some(digest) => {
+                out.copy_from_slice(&digest);
+                if self.reverse {
+                    out.reverse()
+                }
+            }
+            None => self.engine.finalize_into(out),
+        }
+    }
+
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        self.clone().finalize_into(out);
+        Reset::reset(self);
+    }
+}
+
+impl Reset for SigningDigest {
+    fn reset(&mut self) {
+        *self = SigningDigest::default();
+    }
+}
+
+pub fn get_hash_digest(hash_algo: SigningHash, preimage: &[u8]) -> SigningDigest {
     match hash_algo {
-        SigningHash::Sha256 => Digest::chain(Sha256r::default(), preimage),
-        SigningHash::Sha256d => Digest::chain(Sha256r::default(), Sha256r::digest(preimage)),
+        SigningHash::Sha256 => Digest::chain(SigningDigest::default(), preimage),
+        SigningHash::Sha256d => Digest::chain(SigningDigest::default(), Sha256r::digest(preimage)),
+        SigningHash::Sha3_256 => SigningDigest::prehashed::<Sha3_256>(preimage),
+        SigningHash::Keccak256 => SigningDigest::prehashed::<Keccak256>(preimage),
+        SigningHash::Sha512_256 => SigningDigest::prehashed::<Sha512Trunc256>(preimage),
+        SigningHash::Blake2b256 => SigningDigest::prehashed::<Blake2b256>(preimage),
+        SigningHash::Blake3 => SigningDigest::prehashed::<Blake3>(preimage),
     }
 }
@@ -0,0 +1,176 @@
+use crate::hash::{Blake2b256, Blake2b512, Blake3, Hash, Hash160, Keccak256, Sha256d, Sha3_256, Sha3_512};
+use digest::{BlockInput, DynDigest, FixedOutput, Reset, Update};
+use hmac::{Hmac, Mac, NewMac};
+use ripemd160::Ripemd160;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512, Sha512Trunc256};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroize;
+
+/**
+ * The algorithms a Hasher can compute, matching the one shot functions on Hash.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithms {
+    SHA1,
+    SHA256,
+    SHA256d,
+    SHA512,
+    RIPEMD160,
+    Hash160,
+    SHA512_256,
+    SHA3_256,
+    SHA3_512,
+    Keccak256,
+    BLAKE2b256,
+    BLAKE2b512,
+    BLAKE3,
+}
+
+trait HashEngine {
+    fn update(&mut self, data: &[u8]);
+    fn finalise_reset(&mut self) -> Vec<u8>;
+    fn reset(&mut self);
+}
+
+struct DigestEngine(Box<dyn DynDigest>);
+
+impl HashEngine for DigestEngine {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    fn finalise_reset(&mut self) -> Vec<u8> {
+        self.0.finalize_reset().to_vec()
+    }
+
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+struct HmacEngine<T: Update + BlockInput + FixedOutput + Reset + Default + Clone>(Hmac<T>);
+
+impl<T: Update + BlockInput + FixedOutput + Reset + Default + Clone> HashEngine for HmacEngine<T> {
+    fn update(&mut self, data: &[u8]) {
+        Mac::update(&mut self.0, data)
+    }
+
+    fn finalise_reset(&mut self) -> Vec<u8> {
+        let mut output = self.0.finalize_reset().into_bytes();
+        let bytes = output.to_vec();
+        output[..].zeroize();
+        bytes
+    }
+
+    fn reset(&mut self) {
+        Mac::reset(&mut self.0)
+    }
+}
+
+/**
+ * Hashes a message chunk by chunk, so large files and transaction streams never need to be concatenated first.
+ * update with each chunk in order, then finalise. finalise and reset both return the hasher to its initial state
+ * (keyed with the same key for HMAC), ready for the next message.
+ */
+#[wasm_bindgen]
+pub struct Hasher {
+    algorithm: HashAlgorithms,
+    is_hmac: bool,
+    engine: Box<dyn HashEngine>,
+}
+
+impl Hasher {
+    fn hmac_engine(algorithm: HashAlgorithms, key: &[u8]) -> Box<dyn HashEngine> {
+        fn engine<T: Update + BlockInput + FixedOutput + Reset + Default + Clone + 'static>(key: &[u8]) -> Box<dyn HashEngine> {
+            // HMAC accepts keys of any length, longer keys are hashed
+            Box::new(HmacEngine(Hmac::<T>::new_from_slice(key).unwrap()))
+        }
+
+        match algorithm {
+            HashAlgorithms::SHA1 => engine::<Sha1>(key),
+            HashAlgorithms::SHA256 => engine::<Sha256>(key),
+            HashAlgorithms::SHA256d => engine::<Sha256d>(key),
+            HashAlgorithms::SHA512 => engine::<Sha512>(key),
+            HashAlgorithms::RIPEMD160 => engine::<Ripemd160>(key),
+            HashAlgorithms::Hash160 => engine::<Hash160>(key),
+            HashAlgorithms::SHA512_256 => engine::<Sha512Trunc256>(key),
+            HashAlgorithms::SHA3_256 => engine::<Sha3_256>(key),
+            HashAlgorithms::SHA3_512 => engine::<Sha3_512>(key),
+            HashAlgorithms::Keccak256 => engine::<Keccak256>(key),
+            HashAlgorithms::BLAKE2b256 => engine::<Blake2b256>(key),
+            HashAlgorithms::BLAKE2b512 => engine::<Blake2b512>(key),
+            HashAlgorithms::BLAKE3 => engine::<Blake3>(key),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Hasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: HashAlgorithms) -> Hasher {
+        let digest: Box<dyn DynDigest> = match algorithm {
+            HashAlgorithms::SHA1 => Box::new(Sha1::default()),
+            HashAlgorithms::SHA256 => Box::new(Sha256::default()),
+            HashAlgorithms::SHA256d => Box::new(Sha256d::default()),
+            HashAlgorithms::SHA512 => Box::new(Sha512::default()),
+            HashAlgorithms::RIPEMD160 => Box::new(Ripemd160::default()),
+            HashAlgorithms::Hash160 => Box::new(Hash160::default()),
+            HashAlgorithms::SHA512_256 => Box::new(Sha512Trunc256::default()),
+            HashAlgorithms::SHA3_256 => Box::new(Sha3_256::default()),
+            HashAlgorithms::SHA3_512 => Box::new(Sha3_512::default()),
+            HashAlgorithms::Keccak256 => Box::new(Keccak256::default()),
+            HashAlgorithms::BLAKE2b256 => Box::new(Blake2b256::default()),
+            HashAlgorithms::BLAKE2b512 => Box::new(Blake2b512::default()),
+            HashAlgorithms::BLAKE3 => Box::new(Blake3::default()),
+        };
+
+        Hasher {
+            algorithm,
+            is_hmac: false,
+            engine: Box::new(DigestEngine(digest)),
+        }
+    }
+
+    /**
+     * An HMAC over the algorithm, matching Hash.sha256Hmac etc.
+     * For BLAKE2b and BLAKE3 this is HMAC, not their keyed modes (Hash.blake2b512Mac, Hash.blake3Keyed).
+     */
+    #[wasm_bindgen(js_name = newHmac)]
+    pub fn new_hmac(algorithm: HashAlgorithms, key: &[u8]) -> Hasher {
+        Hasher {
+            algorithm,
+            is_hmac: true,
+            engine: Hasher::hmac_engine(algorithm, key),
+        }
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.engine.update(chunk)
+    }
+
+    /**
+     * Returns the hash of everything since creation or the last finalise/reset, then resets.
+     */
+    pub fn finalise(&mut self) -> Hash {
+        Hash(self.engine.finalise_reset())
+    }
+
+    /**
+     * Discards everything hashed so far.
+     */
+    pub fn reset(&mut self) {
+        self.engine.reset()
+    }
+
+    #[wasm_bindgen(js_name = getAlgorithm)]
+    pub fn get_algorithm(&self) -> HashAlgorithms {
+        self.algorithm
+    }
+
+    #[wasm_bindgen(js_name = isHmac)]
+    pub fn is_hmac(&self) -> bool {
+        self.is_hmac
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{BSVErrors, Hash};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroize;
+
+/**
+ * NIST SP 800-90A caps a single generate request at 2^19 bits.
+ */
+pub const HMAC_DRBG_MAX_REQUEST_BYTES: usize = 1 << 16;
+
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DRBGHashes {
+    SHA256,
+    SHA512,
+}
+
+impl DRBGHashes {
+    fn hmac(&self, input: &[u8], key: &[u8]) -> Vec<u8> {
+        match self {
+            DRBGHashes::SHA256 => Hash::sha_256_hmac(input, key).0,
+            DRBGHashes::SHA512 => Hash::sha_512_hmac(input, key).0,
+        }
+    }
+
+    fn output_length(&self) -> usize {
+        match self {
+            DRBGHashes::SHA256 => 32,
+            DRBGHashes::SHA512 => 64,
+        }
+    }
+}
+
+/**
+ * HMAC_DRBG (NIST SP 800-90A). The same inputs always produce the same output stream,
+ * so it is only as unpredictable as the entropy it is seeded with.
+ *
+ * This is the generator RFC 6979 uses for deterministic ECDSA nonces, see ECDSA::rfc6979_nonce.
+ */
+#[wasm_bindgen]
+pub struct HmacDrbg {
+    hash: DRBGHashes,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    reseed_counter: u64,
+}
+
+impl Drop for HmacDrbg {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
+
+impl HmacDrbg {
+    /**
+     * The HMAC_DRBG_Update function: mixes provided data into K and V.
+     */
+    fn update(&mut self, provided_data: &[u8]) {
+        for separator in [0x00, 0x01] {
+            let mut input = [&self.v[..], &[separator], provided_data].concat();
+            let k = self.hash.hmac(&input, &self.k);
+            input.zeroize();
+            self.k.zeroize();
+            self.k = k;
+            self.v = self.hash.hmac(&self.v, &self.k);
+
+            if provided_data.is_empty() {
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn generate_impl(&mut self, length: usize, additional_input: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        if length > HMAC_DRBG_MAX_REQUEST_BYTES {
+            return Err(BSVErrors::DRBGError(format!(
+                "Cannot generate more than {} bytes per request, got {}",
+                HMAC_DRBG_MAX_REQUEST_BYTES, length
+            )));
+        }
+
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(BSVErrors::DRBGError("Reseed required".into()));
+        }
+
+        if !additional_input.is_empty() {
+            self.update(additional_input);
+        }
+
+        let mut output = Vec::with_capacity(length + self.hash.output_length());
+        while output.len() < length {
+            self.v = self.hash.hmac(&self.v, &self.k);
+            output.extend_from_slice(&self.v);
+        }
+        output.truncate(length);
+
+        self.update(additional_input);
+        self.reseed_counter += 1;
+
+        Ok(output)
+    }
+}
+
+#[wasm_bindgen]
+impl HmacDrbg {
+    /**
+     * Instantiates from entropy_input | nonce | personalisation_string. Seed with at least the security strength
+     * (32 bytes for SHA-256, 64 for SHA-512) of real entropy, unless deterministic output is the point.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash: DRBGHashes, entropy_input: &[u8], nonce: &[u8], personalisation_string: &[u8]) -> HmacDrbg {
+        let mut drbg = HmacDrbg {
+            hash,
+            k: vec![0x00; hash.output_length()],
+            v: vec![0x01; hash.output_length()],
+            reseed_counter: 1,
+        };
+
+        let mut seed_material = [entropy_input, nonce, personalisation_string].concat();
+        drbg.update(&seed_material);
+        seed_material.zeroize();
+
+        drbg
+    }
+
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        let mut seed_material = [entropy_input, additional_input].concat();
+        self.update(&seed_material);
+        seed_material.zeroize();
+        self.reseed_counter = 1;
+    }
+
+    #[wasm_bindgen(js_name = getHash)]
+    pub fn get_hash(&self) -> DRBGHashes {
+        self.hash
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl HmacDrbg {
+    /**
+     * Returns the next length bytes, at most 65536 per call.
+     */
+    pub fn generate(&mut self, length: usize, additional_input: &[u8]) -> Result<Vec<u8>, JsValue> {
+        match self.generate_impl(length, additional_input) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HmacDrbg {
+    /**
+     * Returns the next length bytes, at most 65536 per call.
+     */
+    pub fn generate(&mut self, length: usize, additional_input: &[u8]) -> Result<Vec<u8>, BSVErrors> {
+        self.generate_impl(length, additional_input)
+    }
+}
@@ -1,17 +1,27 @@
+pub mod blake2b_digest;
 pub mod digest_utils;
 pub mod hash160_digest;
+pub mod hasher;
+pub mod hmac_drbg;
 pub mod reverse_digest;
 pub mod sha256d_digest;
 pub mod sha256r_digest;
 
+pub use blake2b_digest::*;
 pub use digest_utils::*;
 pub use hash160_digest::*;
+pub use hasher::*;
+pub use hmac_drbg::*;
 pub use reverse_digest::*;
 pub use sha256d_digest::*;
 pub use sha256r_digest::*;
 
+pub use blake3::Hasher as Blake3;
+pub use sha3::{Keccak256, Sha3_256, Sha3_512};
+
 use crate::utils::{from_hex, to_hex};
-use crate::ToHex;
+use crate::{BSVErrors, ToHex};
+use block_modes::InvalidKeyIvLength;
 use digest::Digest;
 use hmac::crypto_mac::Key;
 use hmac::digest::{BlockInput, FixedOutput, Reset, Update};
@@ -19,9 +29,10 @@ use hmac::{Hmac, Mac, NewMac};
 use ripemd160::Ripemd160;
 use serde::*;
 use sha1::Sha1;
-use sha2::{Sha256, Sha512};
+use sha2::{Sha256, Sha512, Sha512Trunc256};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroize;
 
 use self::hash160_digest::Hash160;
 use self::sha256d_digest::Sha256d;
@@ -80,6 +91,47 @@ impl Hash {
     pub fn sha_512(input: &[u8]) -> Self {
         Hash((&*Sha512::digest(input)).to_vec())
     }
+
+    /**
+     * SHA-512/256: SHA-512 with its own initial values, truncated to 32 bytes
+     */
+    #[wasm_bindgen(js_name = sha512_256)]
+    pub fn sha_512_256(input: &[u8]) -> Self {
+        Hash(Sha512Trunc256::digest(input).to_vec())
+    }
+
+    #[wasm_bindgen(js_name = sha3_256)]
+    pub fn sha3_256(input: &[u8]) -> Self {
+        Hash(Sha3_256::digest(input).to_vec())
+    }
+
+    #[wasm_bindgen(js_name = sha3_512)]
+    pub fn sha3_512(input: &[u8]) -> Self {
+        Hash(Sha3_512::digest(input).to_vec())
+    }
+
+    /**
+     * Keccak-256 with the original padding, as used by Ethereum. Not the same as SHA3-256.
+     */
+    #[wasm_bindgen(js_name = keccak256)]
+    pub fn keccak_256(input: &[u8]) -> Self {
+        Hash(Keccak256::digest(input).to_vec())
+    }
+
+    #[wasm_bindgen(js_name = blake2b256)]
+    pub fn blake2b_256(input: &[u8]) -> Self {
+        Hash(Blake2b256::digest(input).to_vec())
+    }
+
+    #[wasm_bindgen(js_name = blake2b512)]
+    pub fn blake2b_512(input: &[u8]) -> Self {
+        Hash(Blake2b512::digest(input).to_vec())
+    }
+
+    #[wasm_bindgen(js_name = blake3)]
+    pub fn blake3(input: &[u8]) -> Self {
+        Hash(Blake3::digest(input).to_vec())
+    }
 }
 
 /**
@@ -88,55 +140,139 @@ impl Hash {
 #[wasm_bindgen]
 impl Hash {
     // D::BlockSize: ArrayLength<u8>
-    fn hmac<T>(input: &[u8], key: &[u8]) -> Hmac<T>
+    fn hmac<T>(input: &[u8], key: &[u8]) -> Vec<u8>
     where
         T: Update + BlockInput + FixedOutput + Reset + Default + Clone,
     {
         // Should fix this unwrap, but really shouldnt error.
         let mut engine = Hmac::<T>::new_from_slice(key).unwrap();
         engine.update(input);
-        engine
+
+        // HMAC outputs are often key material (eg. BIP32), don't leave a copy behind
+        let mut output = engine.finalize().into_bytes();
+        let bytes = output.to_vec();
+        output[..].zeroize();
+        bytes
     }
 
     #[wasm_bindgen(js_name = sha512Hmac)]
     pub fn sha_512_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Sha512>(input, key);
-
-        Self(hmac.finalize().into_bytes().to_vec())
+        Self(Hash::hmac::<Sha512>(input, key))
     }
 
     #[wasm_bindgen(js_name = sha256Hmac)]
     pub fn sha_256_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Sha256>(input, key);
-
-        Self(hmac.finalize().into_bytes().to_vec())
+        Self(Hash::hmac::<Sha256>(input, key))
     }
 
     #[wasm_bindgen(js_name = sha256dHmac)]
     pub fn sha_256d_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Sha256d>(input, key);
-
-        Self(hmac.finalize().into_bytes().to_vec())
+        Self(Hash::hmac::<Sha256d>(input, key))
     }
 
     #[wasm_bindgen(js_name = sha1Hmac)]
     pub fn sha_1_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Sha1>(input, key);
-
-        Self(hmac.finalize().into_bytes().to_vec())
+        Self(Hash::hmac::<Sha1>(input, key))
     }
 
     #[wasm_bindgen(js_name = ripemd160Hmac)]
     pub fn ripemd_160_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Ripemd160>(input, key);
-
-        Self(hmac.finalize().into_bytes().to_vec())
+        Self(Hash::hmac::<Ripemd160>(input, key))
     }
 
     #[wasm_bindgen(js_name = hash160Hmac)]
     pub fn hash_160_hmac(input: &[u8], key: &[u8]) -> Self {
-        let hmac = Hash::hmac::<Hash160>(input, key);
+        Self(Hash::hmac::<Hash160>(input, key))
+    }
+
+    #[wasm_bindgen(js_name = sha512_256Hmac)]
+    pub fn sha_512_256_hmac(input: &[u8], key: &[u8]) -> Self {
+        Self(Hash::hmac::<Sha512Trunc256>(input, key))
+    }
+
+    #[wasm_bindgen(js_name = sha3_256Hmac)]
+    pub fn sha3_256_hmac(input: &[u8], key: &[u8]) -> Self {
+        Self(Hash::hmac::<Sha3_256>(input, key))
+    }
+
+    #[wasm_bindgen(js_name = sha3_512Hmac)]
+    pub fn sha3_512_hmac(input: &[u8], key: &[u8]) -> Self {
+        Self(Hash::hmac::<Sha3_512>(input, key))
+    }
+
+    #[wasm_bindgen(js_name = keccak256Hmac)]
+    pub fn keccak_256_hmac(input: &[u8], key: &[u8]) -> Self {
+        Self(Hash::hmac::<Keccak256>(input, key))
+    }
+}
+
+/**
+ * Keyed Hash Methods - BLAKE2b and BLAKE3 have their own MAC modes instead of HMAC
+ */
+impl Hash {
+    pub(crate) fn blake2b_512_mac_impl(input: &[u8], key: &[u8]) -> Result<Hash, BSVErrors> {
+        if key.is_empty() || key.len() > BLAKE2B_MAX_KEY_LENGTH {
+            return Err(InvalidKeyIvLength.into());
+        }
+
+        let mut engine = Blake2b512::with_params(key, &[], &[]);
+        Update::update(&mut engine, input);
+        Ok(Hash(engine.finalize_fixed().to_vec()))
+    }
+
+    pub(crate) fn blake3_keyed_impl(input: &[u8], key: &[u8]) -> Result<Hash, BSVErrors> {
+        if key.len() != blake3::KEY_LEN {
+            return Err(InvalidKeyIvLength.into());
+        }
+
+        let mut key_bytes = [0; blake3::KEY_LEN];
+        key_bytes.copy_from_slice(key);
+        let output = blake3::keyed_hash(&key_bytes, input);
+        key_bytes.zeroize();
+
+        Ok(Hash(output.as_bytes().to_vec()))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl Hash {
+    /**
+     * Keyed BLAKE2b-512, the key is 1 to 64 bytes
+     */
+    #[wasm_bindgen(js_name = blake2b512Mac)]
+    pub fn blake2b_512_mac(input: &[u8], key: &[u8]) -> Result<Hash, JsValue> {
+        match Hash::blake2b_512_mac_impl(input, key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * BLAKE3 keyed hash mode, the key is 32 bytes
+     */
+    #[wasm_bindgen(js_name = blake3Keyed)]
+    pub fn blake3_keyed(input: &[u8], key: &[u8]) -> Result<Hash, JsValue> {
+        match Hash::blake3_keyed_impl(input, key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Hash {
+    /**
+     * Keyed BLAKE2b-512, the key is 1 to 64 bytes
+     */
+    pub fn blake2b_512_mac(input: &[u8], key: &[u8]) -> Result<Hash, BSVErrors> {
+        Hash::blake2b_512_mac_impl(input, key)
+    }
 
-        Self(hmac.finalize().into_bytes().to_vec())
+    /**
+     * BLAKE3 keyed hash mode, the key is 32 bytes
+     */
+    pub fn blake3_keyed(input: &[u8], key: &[u8]) -> Result<Hash, BSVErrors> {
+        Hash::blake3_keyed_impl(input, key)
     }
 }
@@ -0,0 +1,200 @@
+use crate::{hash::Hash, BSVErrors, KDF, KDF_MAX_MEMORY_BYTES};
+use argon2::{Algorithm, Argon2, ParamsBuilder, Version};
+use rand_core::{OsRng, RngCore};
+use serde::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+/**
+ * Argon2id (RFC 9106) cost parameters: memory in KiB, passes over that memory, and lanes.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+/**
+ * RFC 9106's second recommended option: 64 MiB, 3 passes and 4 lanes.
+ */
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Argon2Params {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Argon2Params {
+    #[wasm_bindgen(constructor)]
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Argon2Params {
+        Argon2Params { memory_kib, iterations, parallelism }
+    }
+
+    #[wasm_bindgen(js_name = getMemoryKiB)]
+    pub fn get_memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    #[wasm_bindgen(js_name = getIterations)]
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    #[wasm_bindgen(js_name = getParallelism)]
+    pub fn get_parallelism(&self) -> u32 {
+        self.parallelism
+    }
+}
+
+const MIN_SALT_LENGTH: usize = 8;
+const MIN_OUTPUT_LENGTH: usize = 4;
+
+/**
+ * The longest associated data Argon2id accepts here, the limit of the argon2 crate.
+ */
+pub const ARGON2_MAX_ASSOCIATED_DATA_LENGTH: usize = argon2::Params::MAX_DATA_LEN;
+
+impl Argon2Params {
+    fn validate(&self, salt: &[u8], output_length: usize) -> Result<(), BSVErrors> {
+        if self.parallelism == 0 || self.parallelism >= 1 << 24 {
+            return Err(BSVErrors::KDFError(format!("Argon2 parallelism must be between 1 and 2^24 - 1, got {}", self.parallelism)));
+        }
+
+        if (self.memory_kib as u64) < 8 * self.parallelism as u64 {
+            return Err(BSVErrors::KDFError(format!(
+                "Argon2 needs at least 8 KiB of memory per lane, got {} KiB for {} lanes",
+                self.memory_kib, self.parallelism
+            )));
+        }
+
+        if self.memory_kib as u64 * 1024 > KDF_MAX_MEMORY_BYTES {
+            return Err(BSVErrors::KDFError(format!(
+                "Argon2 memory must be at most {} KiB, got {} KiB",
+                KDF_MAX_MEMORY_BYTES / 1024,
+                self.memory_kib
+            )));
+        }
+
+        if self.iterations == 0 {
+            return Err(BSVErrors::KDFError("Argon2 needs at least one iteration".into()));
+        }
+
+        if salt.len() < MIN_SALT_LENGTH {
+            return Err(BSVErrors::KDFError(format!("Argon2 salt must be at least {} bytes, got {}", MIN_SALT_LENGTH, salt.len())));
+        }
+
+        if output_length < MIN_OUTPUT_LENGTH || output_length > u32::MAX as usize {
+            return Err(BSVErrors::KDFError(format!("Argon2 output length must be at least {} bytes, got {}", MIN_OUTPUT_LENGTH, output_length)));
+        }
+
+        Ok(())
+    }
+}
+
+impl KDF {
+    pub(crate) fn argon2id_impl(password: &[u8], salt: &[u8], params: &Argon2Params, output_length: usize) -> Result<KDF, BSVErrors> {
+        KDF::argon2id_with_secret_impl(password, salt, &[], &[], params, output_length)
+    }
+
+    /**
+     * Argon2id with the optional secret key (pepper) and associated data inputs, either may be empty.
+     * Associated data is limited to ARGON2_MAX_ASSOCIATED_DATA_LENGTH bytes.
+     */
+    pub(crate) fn argon2id_with_secret_impl(password: &[u8], salt: &[u8], secret: &[u8], associated_data: &[u8], params: &Argon2Params, output_length: usize) -> Result<KDF, BSVErrors> {
+        params.validate(salt, output_length)?;
+
+        if associated_data.len() > ARGON2_MAX_ASSOCIATED_DATA_LENGTH {
+            return Err(BSVErrors::KDFError(format!(
+                "Argon2 associated data must be at most {} bytes, got {}",
+                ARGON2_MAX_ASSOCIATED_DATA_LENGTH,
+                associated_data.len()
+            )));
+        }
+
+        let mut builder = ParamsBuilder::new();
+        builder.m_cost(params.memory_kib).map_err(argon2_error)?;
+        builder.t_cost(params.iterations).map_err(argon2_error)?;
+        builder.p_cost(params.parallelism).map_err(argon2_error)?;
+        builder.data(associated_data).map_err(argon2_error)?;
+        let argon2_params = builder.params().map_err(argon2_error)?;
+
+        let argon2 = match secret.is_empty() {
+            true => Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params),
+            false => Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, argon2_params).map_err(argon2_error)?,
+        };
+
+        let mut result = vec![0; output_length];
+        argon2.hash_password_into(password, salt, &mut result).map_err(argon2_error)?;
+
+        Ok(KDF {
+            hash: Hash(result),
+            salt: salt.to_vec(),
+        })
+    }
+
+    pub(crate) fn argon2id_random_salt_impl(password: &[u8], params: &Argon2Params, output_length: usize) -> Result<KDF, BSVErrors> {
+        let mut salt = [0; 16];
+        OsRng.fill_bytes(&mut salt);
+        KDF::argon2id_impl(password, &salt, params, output_length)
+    }
+}
+
+fn argon2_error(e: argon2::Error) -> BSVErrors {
+    BSVErrors::KDFError(format!("Argon2 {}", e))
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl KDF {
+    /**
+     * Argon2id (RFC 9106) - when None is specified for salt, a random 16 byte salt will be generated
+     */
+    pub fn argon2id(password: &[u8], salt: Option<Vec<u8>>, params: &Argon2Params, output_length: usize) -> Result<KDF, JsValue> {
+        let result = match salt {
+            Some(s) => KDF::argon2id_impl(password, &s, params, output_length),
+            None => KDF::argon2id_random_salt_impl(password, params, output_length),
+        };
+
+        match result {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Argon2id (RFC 9106) keyed with a secret (pepper) kept apart from the stored salt and parameters, and bound to associated data
+     */
+    #[wasm_bindgen(js_name = argon2idWithSecret)]
+    pub fn argon2id_with_secret(password: &[u8], salt: &[u8], secret: &[u8], associated_data: &[u8], params: &Argon2Params, output_length: usize) -> Result<KDF, JsValue> {
+        match KDF::argon2id_with_secret_impl(password, salt, secret, associated_data, params, output_length) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KDF {
+    /**
+     * Argon2id (RFC 9106) - when None is specified for salt, a random 16 byte salt will be generated
+     */
+    pub fn argon2id(password: &[u8], salt: Option<Vec<u8>>, params: &Argon2Params, output_length: usize) -> Result<KDF, BSVErrors> {
+        match salt {
+            Some(s) => KDF::argon2id_impl(password, &s, params, output_length),
+            None => KDF::argon2id_random_salt_impl(password, params, output_length),
+        }
+    }
+
+    /**
+     * Argon2id (RFC 9106) keyed with a secret (pepper) kept apart from the stored salt and parameters, and bound to associated data
+     */
+    pub fn argon2id_with_secret(password: &[u8], salt: &[u8], secret: &[u8], associated_data: &[u8], params: &Argon2Params, output_length: usize) -> Result<KDF, BSVErrors> {
+        KDF::argon2id_with_secret_impl(password, salt, secret, associated_data, params, output_length)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{hash::Hash, BSVErrors, KDF};
+use hkdf::Hkdf;
+use serde::*;
+use sha2::{Sha256, Sha512};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HKDFHashes {
+    SHA256,
+    SHA512,
+}
+
+impl HKDFHashes {
+    fn output_length(&self) -> usize {
+        match self {
+            HKDFHashes::SHA256 => 32,
+            HKDFHashes::SHA512 => 64,
+        }
+    }
+}
+
+impl KDF {
+    /**
+     * HKDF (RFC 5869) extract then expand. An empty salt is the RFC's default of HashLen zero bytes,
+     * and info separates keys derived from the same input keying material.
+     */
+    pub(crate) fn hkdf_impl(ikm: &[u8], salt: &[u8], info: &[u8], hash_algo: HKDFHashes, output_length: usize) -> Result<KDF, BSVErrors> {
+        if output_length == 0 || output_length > 255 * hash_algo.output_length() {
+            return Err(BSVErrors::KDFError(format!(
+                "HKDF output length must be between 1 and {} bytes, got {}",
+                255 * hash_algo.output_length(),
+                output_length
+            )));
+        }
+
+        let mut result = vec![0; output_length];
+        let expanded = match hash_algo {
+            HKDFHashes::SHA256 => Hkdf::<Sha256>::new(Some(salt), ikm).expand(info, &mut result),
+            HKDFHashes::SHA512 => Hkdf::<Sha512>::new(Some(salt), ikm).expand(info, &mut result),
+        };
+        expanded.map_err(|e| BSVErrors::KDFError(e.to_string()))?;
+
+        Ok(KDF {
+            hash: Hash(result),
+            salt: salt.to_vec(),
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl KDF {
+    /**
+     * HKDF (RFC 5869) - derives output_length bytes from high entropy input keying material, not from passwords
+     */
+    pub fn hkdf(ikm: &[u8], salt: &[u8], info: &[u8], hash_algo: HKDFHashes, output_length: usize) -> Result<KDF, JsValue> {
+        match KDF::hkdf_impl(ikm, salt, info, hash_algo, output_length) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KDF {
+    /**
+     * HKDF (RFC 5869) - derives output_length bytes from high entropy input keying material, not from passwords
+     */
+    pub fn hkdf(ikm: &[u8], salt: &[u8], info: &[u8], hash_algo: HKDFHashes, output_length: usize) -> Result<KDF, BSVErrors> {
+        KDF::hkdf_impl(ikm, salt, info, hash_algo, output_length)
+    }
+}
@@ -1,11 +1,24 @@
+pub mod argon2_kdf;
+pub mod hkdf_kdf;
 pub mod pbkdf2_kdf;
+pub mod scrypt_kdf;
+
+pub use argon2_kdf::*;
+pub use hkdf_kdf::*;
 pub use pbkdf2_kdf::*;
+pub use scrypt_kdf::*;
 
 use crate::utils::{from_hex, to_hex};
 use crate::Hash;
 use serde::*;
 use wasm_bindgen::prelude::*;
 
+/**
+ * The most memory scrypt (128 * r * N bytes) or Argon2id (memory_kib) may be asked to use: 2 GiB, enough for
+ * RFC 9106's first recommended Argon2id option. Costlier parameters are rejected before anything is allocated.
+ */
+pub const KDF_MAX_MEMORY_BYTES: u64 = 2 << 30;
+
 #[wasm_bindgen]
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KDF {
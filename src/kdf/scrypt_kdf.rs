@@ -0,0 +1,132 @@
+use crate::{hash::Hash, BSVErrors, KDF, KDF_MAX_MEMORY_BYTES};
+use rand_core::{OsRng, RngCore};
+use serde::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+/**
+ * scrypt (RFC 7914) cost parameters: N = 2^log_n, block size r and parallelism p.
+ * Memory use is 128 * r * N bytes.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+/**
+ * N = 2^14, r = 8, p = 1: 16 MiB, the usual interactive login cost.
+ */
+impl Default for ScryptParams {
+    fn default() -> Self {
+        ScryptParams { log_n: 14, r: 8, p: 1 }
+    }
+}
+
+#[wasm_bindgen]
+impl ScryptParams {
+    #[wasm_bindgen(constructor)]
+    pub fn new(log_n: u8, r: u32, p: u32) -> ScryptParams {
+        ScryptParams { log_n, r, p }
+    }
+
+    /**
+     * The parameters BIP38 encrypts keys with: N = 16384, r = 8, p = 8.
+     */
+    pub fn bip38() -> ScryptParams {
+        ScryptParams { log_n: 14, r: 8, p: 8 }
+    }
+
+    #[wasm_bindgen(js_name = getLogN)]
+    pub fn get_log_n(&self) -> u8 {
+        self.log_n
+    }
+
+    #[wasm_bindgen(js_name = getR)]
+    pub fn get_r(&self) -> u32 {
+        self.r
+    }
+
+    #[wasm_bindgen(js_name = getP)]
+    pub fn get_p(&self) -> u32 {
+        self.p
+    }
+}
+
+impl ScryptParams {
+    fn validate(&self, output_length: usize) -> Result<(), BSVErrors> {
+        if self.log_n == 0 || self.log_n >= 32 {
+            return Err(BSVErrors::KDFError(format!("scrypt log_n must be between 1 and 31, got {}", self.log_n)));
+        }
+
+        if self.r == 0 || self.p == 0 || (self.r as u64) * (self.p as u64) >= 1 << 30 {
+            return Err(BSVErrors::KDFError(format!("scrypt needs r > 0, p > 0 and r * p < 2^30, got r = {} p = {}", self.r, self.p)));
+        }
+
+        let memory = (128 * self.r as u64).saturating_mul(1 << self.log_n);
+        if memory > KDF_MAX_MEMORY_BYTES {
+            return Err(BSVErrors::KDFError(format!("scrypt parameters need {} bytes of memory, more than {}", memory, KDF_MAX_MEMORY_BYTES)));
+        }
+
+        if output_length == 0 {
+            return Err(BSVErrors::KDFError("Output length must be greater than zero".into()));
+        }
+
+        Ok(())
+    }
+}
+
+impl KDF {
+    pub(crate) fn scrypt_impl(password: &[u8], salt: &[u8], params: &ScryptParams, output_length: usize) -> Result<KDF, BSVErrors> {
+        params.validate(output_length)?;
+
+        let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p).map_err(|e| BSVErrors::KDFError(e.to_string()))?;
+        let mut result = vec![0; output_length];
+        scrypt::scrypt(password, salt, &scrypt_params, &mut result).map_err(|e| BSVErrors::KDFError(e.to_string()))?;
+
+        Ok(KDF {
+            hash: Hash(result),
+            salt: salt.to_vec(),
+        })
+    }
+
+    pub(crate) fn scrypt_random_salt_impl(password: &[u8], params: &ScryptParams, output_length: usize) -> Result<KDF, BSVErrors> {
+        let mut salt = [0; 16];
+        OsRng.fill_bytes(&mut salt);
+        KDF::scrypt_impl(password, &salt, params, output_length)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl KDF {
+    /**
+     * scrypt (RFC 7914) - when None is specified for salt, a random 16 byte salt will be generated
+     */
+    pub fn scrypt(password: &[u8], salt: Option<Vec<u8>>, params: &ScryptParams, output_length: usize) -> Result<KDF, JsValue> {
+        let result = match salt {
+            Some(s) => KDF::scrypt_impl(password, &s, params, output_length),
+            None => KDF::scrypt_random_salt_impl(password, params, output_length),
+        };
+
+        match result {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KDF {
+    /**
+     * scrypt (RFC 7914) - when None is specified for salt, a random 16 byte salt will be generated
+     */
+    pub fn scrypt(password: &[u8], salt: Option<Vec<u8>>, params: &ScryptParams, output_length: usize) -> Result<KDF, BSVErrors> {
+        match salt {
+            Some(s) => KDF::scrypt_impl(password, &s, params, output_length),
+            None => KDF::scrypt_random_salt_impl(password, params, output_length),
+        }
+    }
+}
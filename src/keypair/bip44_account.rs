@@ -0,0 +1,272 @@
+use crate::{BSVErrors, ExtendedPrivateKey, ExtendedPublicKey, P2PKHAddress, BIP44_PURPOSE, BSV_COIN_TYPE, HARDENED_KEY_OFFSET};
+use wasm_bindgen::{prelude::*, throw_str};
+
+/**
+ * The chains under a BIP44 account.
+ * External addresses are given out to receive funds, Internal addresses are used for change.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BIP44Chain {
+    External = 0,
+    Internal = 1,
+}
+
+/**
+ * A BIP44 account (m/44'/coin_type'/account') backed by the account level xpub.
+ * Receive and change addresses can be generated without access to any private keys.
+ */
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct BIP44Account {
+    account_xpub: ExtendedPublicKey,
+    coin_type: u32,
+    account: u32,
+}
+
+impl BIP44Account {
+    pub(crate) fn from_xpriv_impl(master_xpriv: &ExtendedPrivateKey, coin_type: u32, account: u32) -> Result<BIP44Account, BSVErrors> {
+        if master_xpriv.get_depth() != 0 {
+            return Err(BSVErrors::DerivationError(format!(
+                "BIP44 accounts must be derived from a master key, given key has depth {}",
+                master_xpriv.get_depth()
+            )));
+        }
+
+        let account_xpriv = master_xpriv.derive_from_path_impl(&BIP44Account::account_path(coin_type, account)?)?;
+
+        Ok(BIP44Account {
+            account_xpub: ExtendedPublicKey::from_xpriv(&account_xpriv),
+            coin_type,
+            account,
+        })
+    }
+
+    pub(crate) fn from_account_xpub_impl(account_xpub: &ExtendedPublicKey, coin_type: u32, account: u32) -> Result<BIP44Account, BSVErrors> {
+        if account_xpub.get_depth() != 3 {
+            return Err(BSVErrors::DerivationError(format!(
+                "BIP44 account xpubs must be at depth 3 (m/44'/coin_type'/account'), given key has depth {}",
+                account_xpub.get_depth()
+            )));
+        }
+
+        if account >= HARDENED_KEY_OFFSET || account_xpub.get_index() != HARDENED_KEY_OFFSET + account {
+            return Err(BSVErrors::DerivationError(format!(
+                "Account xpub has child index {}, account {} must be at index {}'",
+                account_xpub.get_index(),
+                account,
+                account
+            )));
+        }
+
+        Ok(BIP44Account {
+            account_xpub: account_xpub.clone(),
+            coin_type,
+            account,
+        })
+    }
+
+    fn account_path(coin_type: u32, account: u32) -> Result<String, BSVErrors> {
+        if coin_type >= HARDENED_KEY_OFFSET || account >= HARDENED_KEY_OFFSET {
+            return Err(BSVErrors::DerivationError(format!("Coin type and account must be between 0 and {}", HARDENED_KEY_OFFSET - 1)));
+        }
+
+        Ok(format!("m/{}'/{}'/{}'", BIP44_PURPOSE, coin_type, account))
+    }
+
+    pub(crate) fn get_xpub_impl(&self, chain: BIP44Chain, index: u32) -> Result<ExtendedPublicKey, BSVErrors> {
+        self.account_xpub.derive_impl(chain as u32)?.derive_impl(index)
+    }
+
+    pub(crate) fn get_address_impl(&self, chain: BIP44Chain, index: u32) -> Result<P2PKHAddress, BSVErrors> {
+        P2PKHAddress::from_pubkey_impl(&self.get_xpub_impl(chain, index)?.get_public_key())
+    }
+
+    /**
+     * Walks the given chain from index 0 and asks `is_used` about every address.
+     * Scanning stops once `gap_limit` consecutive unused addresses have been seen.
+     *
+     * Returns the indices of every used address that was found.
+     */
+    pub(crate) fn scan_impl<F>(&self, chain: BIP44Chain, gap_limit: u32, mut is_used: F) -> Result<Vec<u32>, BSVErrors>
+    where
+        F: FnMut(&P2PKHAddress) -> Result<bool, BSVErrors>,
+    {
+        if gap_limit == 0 {
+            return Err(BSVErrors::DerivationError("Gap limit must be greater than 0".into()));
+        }
+
+        let chain_xpub = self.account_xpub.derive_impl(chain as u32)?;
+
+        let mut used_indices = vec![];
+        let mut unused_run = 0;
+        let mut index = 0;
+        while unused_run < gap_limit && index < HARDENED_KEY_OFFSET {
+            let address = P2PKHAddress::from_pubkey_impl(&chain_xpub.derive_impl(index)?.get_public_key())?;
+
+            match is_used(&address)? {
+                true => {
+                    used_indices.push(index);
+                    unused_run = 0;
+                }
+                false => unused_run += 1,
+            }
+
+            index += 1;
+        }
+
+        Ok(used_indices)
+    }
+
+    /**
+     * Returns the first index on the given chain after the last used address found by scanning.
+     */
+    pub(crate) fn next_unused_index_impl<F>(&self, chain: BIP44Chain, gap_limit: u32, is_used: F) -> Result<u32, BSVErrors>
+    where
+        F: FnMut(&P2PKHAddress) -> Result<bool, BSVErrors>,
+    {
+        let used_indices = self.scan_impl(chain, gap_limit, is_used)?;
+
+        Ok(used_indices.last().map_or(0, |x| x + 1))
+    }
+}
+
+#[wasm_bindgen]
+impl BIP44Account {
+    #[wasm_bindgen(js_name = getAccountXPub)]
+    pub fn get_account_xpub(&self) -> ExtendedPublicKey {
+        self.account_xpub.clone()
+    }
+
+    #[wasm_bindgen(js_name = getCoinType)]
+    pub fn get_coin_type(&self) -> u32 {
+        self.coin_type
+    }
+
+    #[wasm_bindgen(js_name = getAccount)]
+    pub fn get_account(&self) -> u32 {
+        self.account
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl BIP44Account {
+    /**
+     * Derives m/44'/coin_type'/account' from a master key.
+     * When coin_type is not specified, the BSV coin type (236) is used.
+     */
+    #[wasm_bindgen(js_name = fromXPriv)]
+    pub fn from_xpriv(master_xpriv: &ExtendedPrivateKey, account: u32, coin_type: Option<u32>) -> Result<BIP44Account, JsValue> {
+        match BIP44Account::from_xpriv_impl(master_xpriv, coin_type.unwrap_or(BSV_COIN_TYPE), account) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromAccountXPub)]
+    pub fn from_account_xpub(account_xpub: &ExtendedPublicKey, account: u32, coin_type: Option<u32>) -> Result<BIP44Account, JsValue> {
+        match BIP44Account::from_account_xpub_impl(account_xpub, coin_type.unwrap_or(BSV_COIN_TYPE), account) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getReceiveAddress)]
+    pub fn get_receive_address(&self, index: u32) -> Result<P2PKHAddress, JsValue> {
+        match self.get_address_impl(BIP44Chain::External, index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getChangeAddress)]
+    pub fn get_change_address(&self, index: u32) -> Result<P2PKHAddress, JsValue> {
+        match self.get_address_impl(BIP44Chain::Internal, index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getXPub)]
+    pub fn get_xpub(&self, chain: BIP44Chain, index: u32) -> Result<ExtendedPublicKey, JsValue> {
+        match self.get_xpub_impl(chain, index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Scans the given chain, calling is_used(address: P2PKHAddress) => boolean for every address.
+     * Stops after gap_limit (default 20) consecutive unused addresses and returns the used indices.
+     */
+    pub fn scan(&self, chain: BIP44Chain, is_used: &js_sys::Function, gap_limit: Option<u32>) -> Result<Vec<u32>, JsValue> {
+        match self.scan_impl(chain, gap_limit.unwrap_or(crate::DEFAULT_GAP_LIMIT), |address| BIP44Account::call_is_used(is_used, address)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = nextUnusedIndex)]
+    pub fn next_unused_index(&self, chain: BIP44Chain, is_used: &js_sys::Function, gap_limit: Option<u32>) -> Result<u32, JsValue> {
+        match self.next_unused_index_impl(chain, gap_limit.unwrap_or(crate::DEFAULT_GAP_LIMIT), |address| BIP44Account::call_is_used(is_used, address)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    fn call_is_used(is_used: &js_sys::Function, address: &P2PKHAddress) -> Result<bool, BSVErrors> {
+        let result = is_used
+            .call1(&JsValue::NULL, &JsValue::from(address.clone()))
+            .map_err(|e| BSVErrors::DerivationError(format!("is_used callback threw: {:?}", e)))?;
+
+        result
+            .as_bool()
+            .ok_or_else(|| BSVErrors::DerivationError(format!("is_used callback must return a boolean, got {:?}", result)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BIP44Account {
+    /**
+     * Derives m/44'/coin_type'/account' from a master key.
+     * When coin_type is not specified, the BSV coin type (236) is used.
+     */
+    pub fn from_xpriv(master_xpriv: &ExtendedPrivateKey, account: u32, coin_type: Option<u32>) -> Result<BIP44Account, BSVErrors> {
+        BIP44Account::from_xpriv_impl(master_xpriv, coin_type.unwrap_or(BSV_COIN_TYPE), account)
+    }
+
+    pub fn from_account_xpub(account_xpub: &ExtendedPublicKey, account: u32, coin_type: Option<u32>) -> Result<BIP44Account, BSVErrors> {
+        BIP44Account::from_account_xpub_impl(account_xpub, coin_type.unwrap_or(BSV_COIN_TYPE), account)
+    }
+
+    pub fn get_receive_address(&self, index: u32) -> Result<P2PKHAddress, BSVErrors> {
+        self.get_address_impl(BIP44Chain::External, index)
+    }
+
+    pub fn get_change_address(&self, index: u32) -> Result<P2PKHAddress, BSVErrors> {
+        self.get_address_impl(BIP44Chain::Internal, index)
+    }
+
+    pub fn get_xpub(&self, chain: BIP44Chain, index: u32) -> Result<ExtendedPublicKey, BSVErrors> {
+        self.get_xpub_impl(chain, index)
+    }
+
+    /**
+     * Scans the given chain, calling is_used for every address.
+     * Stops after gap_limit (default 20) consecutive unused addresses and returns the used indices.
+     */
+    pub fn scan<F>(&self, chain: BIP44Chain, is_used: F, gap_limit: Option<u32>) -> Result<Vec<u32>, BSVErrors>
+    where
+        F: FnMut(&P2PKHAddress) -> Result<bool, BSVErrors>,
+    {
+        self.scan_impl(chain, gap_limit.unwrap_or(crate::DEFAULT_GAP_LIMIT), is_used)
+    }
+
+    pub fn next_unused_index<F>(&self, chain: BIP44Chain, is_used: F, gap_limit: Option<u32>) -> Result<u32, BSVErrors>
+    where
+        F: FnMut(&P2PKHAddress) -> Result<bool, BSVErrors>,
+    {
+        self.next_unused_index_impl(chain, gap_limit.unwrap_or(crate::DEFAULT_GAP_LIMIT), is_used)
+    }
+}
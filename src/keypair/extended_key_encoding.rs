@@ -0,0 +1,133 @@
+use crate::{hash::Hash, BSVErrors, TPRV_VERSION_BYTE, TPUB_VERSION_BYTE, XPRIV_VERSION_BYTE, XPUB_VERSION_BYTE};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+use zeroize::{Zeroize, Zeroizing};
+
+/**
+ * Length of a serialised extended key without the Base58Check checksum.
+ */
+pub(crate) const EXTENDED_KEY_LENGTH: usize = 78;
+
+/**
+ * Versions accepted by from_string when the caller does not specify any.
+ */
+pub(crate) const DEFAULT_PRIVATE_VERSIONS: [u32; 2] = [XPRIV_VERSION_BYTE, TPRV_VERSION_BYTE];
+pub(crate) const DEFAULT_PUBLIC_VERSIONS: [u32; 2] = [XPUB_VERSION_BYTE, TPUB_VERSION_BYTE];
+
+/**
+ * The header fields shared by extended private and public keys.
+ */
+pub(crate) struct ExtendedKeyHeader {
+    pub(crate) version: u32,
+    pub(crate) depth: u8,
+    pub(crate) parent_fingerprint: Vec<u8>,
+    pub(crate) index: u32,
+    pub(crate) chain_code: Vec<u8>,
+    pub(crate) key_data: Vec<u8>,
+}
+
+/**
+ * Private and public version pairs for BIP32 and SLIP-132, used to pick the version of an xpub derived from an xprv.
+ */
+pub(crate) const KNOWN_VERSION_PAIRS: [(u32, u32); 10] = [
+    (XPRIV_VERSION_BYTE, XPUB_VERSION_BYTE),
+    (TPRV_VERSION_BYTE, TPUB_VERSION_BYTE),
+    // yprv / ypub
+    (0x049d7878, 0x049d7cb2),
+    // zprv / zpub
+    (0x04b2430c, 0x04b24746),
+    // Yprv / Ypub
+    (0x0295b005, 0x0295b43f),
+    // Zprv / Zpub
+    (0x02aa7a99, 0x02aa7ed3),
+    // uprv / upub
+    (0x044a4e28, 0x044a5262),
+    // vprv / vpub
+    (0x045f18bc, 0x045f1cf6),
+    // Uprv / Upub
+    (0x024285b5, 0x024289ef),
+    // Vprv / Vpub
+    (0x02575048, 0x02575483),
+];
+
+/**
+ * Returns the public version bytes matching the given private version bytes.
+ * Versions that are not in KNOWN_VERSION_PAIRS are an error, the caller must supply both versions.
+ */
+pub(crate) fn public_version_for(private_version: u32) -> Result<u32, BSVErrors> {
+    KNOWN_VERSION_PAIRS
+        .iter()
+        .find(|(private, _)| *private == private_version)
+        .map(|(_, public)| *public)
+        .ok_or(BSVErrors::InvalidExtendedKeyVersion(private_version))
+}
+
+/**
+ * Base58Check decodes an extended key and validates everything that is common to xprv and xpub strings:
+ * - Length is 78 bytes + 4 byte checksum
+ * - Checksum matches the first 4 bytes of SHA256d
+ * - Version bytes are one of the allowed versions
+ * - A depth of 0 has a zero parent fingerprint and a zero index
+ */
+pub(crate) fn decode_extended_key(key_string: &str, allowed_versions: &[u32]) -> Result<ExtendedKeyHeader, BSVErrors> {
+    // May contain a private key
+    let bytes = Zeroizing::new(bs58::decode(key_string).into_vec()?);
+
+    if bytes.len() != EXTENDED_KEY_LENGTH + 4 {
+        return Err(BSVErrors::InvalidExtendedKeyLength(bytes.len()));
+    }
+
+    let (payload, checksum) = bytes.split_at(EXTENDED_KEY_LENGTH);
+    let expected_checksum = &Hash::sha_256d(payload).to_bytes()[0..4];
+    if expected_checksum != checksum {
+        return Err(BSVErrors::InvalidExtendedKeyChecksum(hex::encode(expected_checksum), hex::encode(checksum)));
+    }
+
+    let mut cursor = Cursor::new(payload);
+
+    let version = cursor.read_u32::<BigEndian>()?;
+    if !allowed_versions.contains(&version) {
+        return Err(BSVErrors::InvalidExtendedKeyVersion(version));
+    }
+
+    let depth = cursor.read_u8()?;
+    let mut parent_fingerprint = vec![0; 4];
+    cursor.read_exact(&mut parent_fingerprint)?;
+    let index = cursor.read_u32::<BigEndian>()?;
+
+    if depth == 0 && parent_fingerprint != [0, 0, 0, 0] {
+        return Err(BSVErrors::InconsistentExtendedKey(format!("Depth is 0 but parent fingerprint is {}", hex::encode(&parent_fingerprint))));
+    }
+
+    if depth == 0 && index != 0 {
+        return Err(BSVErrors::InconsistentExtendedKey(format!("Depth is 0 but index is {}", index)));
+    }
+
+    let mut chain_code = vec![0; 32];
+    cursor.read_exact(&mut chain_code)?;
+
+    let mut key_data = vec![0; 33];
+    cursor.read_exact(&mut key_data)?;
+
+    Ok(ExtendedKeyHeader {
+        version,
+        depth,
+        parent_fingerprint,
+        index,
+        chain_code,
+        key_data,
+    })
+}
+
+/**
+ * Appends the 4 byte SHA256d checksum and Base58 encodes the serialised extended key.
+ * The payload is wiped afterwards as it may contain a private key.
+ */
+pub(crate) fn encode_extended_key(mut payload: Vec<u8>) -> String {
+    let checksum = Hash::sha_256d(&payload).to_bytes();
+    payload.extend_from_slice(&checksum[0..4]);
+
+    let encoded = bs58::encode(&payload).into_string();
+    payload.zeroize();
+    encoded
+}
@@ -1,18 +1,21 @@
-use crate::{BSVErrors, HARDENED_KEY_OFFSET, KDF, XPRIV_VERSION_BYTE};
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use crate::{decode_extended_key, encode_extended_key, public_version_for, BSVErrors, DEFAULT_PRIVATE_VERSIONS, EXTENDED_KEY_LENGTH, HARDENED_KEY_OFFSET, KDF, XPRIV_VERSION_BYTE, XPUB_VERSION_BYTE};
+use byteorder::{BigEndian, WriteBytesExt};
 use getrandom::*;
 use k256::{Scalar, SecretKey};
 use std::borrow::Cow;
-use std::{
-    io::{Cursor, Read, Write},
-    ops::Add,
-    vec,
-};
+use std::{io::Write, ops::Add, vec};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, Zeroizing};
 
 use wasm_bindgen::{prelude::*, throw_str};
 
 use crate::{hash::Hash, PrivateKey, PublicKey};
 
+/**
+ * 0x00 | private key or compressed public key (33 bytes) | index (4 bytes)
+ */
+const CHILD_KEY_DATA_LENGTH: usize = 37;
+
 #[wasm_bindgen]
 pub struct ExtendedPrivateKey {
     private_key: PrivateKey,
@@ -21,8 +24,45 @@ pub struct ExtendedPrivateKey {
     depth: u8,
     index: u32,
     parent_fingerprint: Vec<u8>,
+    version: u32,
+    /**
+     * The version of xpubs derived from this key, so SLIP-132 and custom versions keep their pairing.
+     */
+    public_version: u32,
+}
+
+/**
+ * The private key wipes itself on drop, the chain code is wiped here.
+ */
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
 }
 
+/**
+ * Compares the private key and chain code in constant time.
+ */
+impl ConstantTimeEq for ExtendedPrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let metadata_eq = self.depth == other.depth
+            && self.index == other.index
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.version == other.version
+            && self.public_version == other.public_version;
+
+        self.private_key.ct_eq(&other.private_key) & self.chain_code.ct_eq(&other.chain_code) & Choice::from(metadata_eq as u8)
+    }
+}
+
+impl PartialEq for ExtendedPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for ExtendedPrivateKey {}
+
 impl ExtendedPrivateKey {
     pub fn new(private_key: &PrivateKey, chain_code: &[u8], depth: &u8, index: &u32, parent_fingerprint: Option<&[u8]>) -> Self {
         let fingerprint = parent_fingerprint.unwrap_or(&[0, 0, 0, 0]);
@@ -34,25 +74,32 @@ impl ExtendedPrivateKey {
             depth: *depth,
             index: *index,
             parent_fingerprint: fingerprint.to_vec(),
+            version: XPRIV_VERSION_BYTE,
+            public_version: XPUB_VERSION_BYTE,
         }
     }
 
     pub fn to_string_impl(&self) -> Result<String, BSVErrors> {
-        let mut buffer: Vec<u8> = vec![];
+        self.to_string_with_version_impl(self.version)
+    }
+
+    /**
+     * Serialises this key with the given version bytes, eg. TPRV_VERSION_BYTE for testnet or a SLIP-132 version.
+     */
+    pub fn to_string_with_version_impl(&self, version: u32) -> Result<String, BSVErrors> {
+        // Sized up front so the private key is not left behind by a reallocation
+        let mut buffer: Vec<u8> = Vec::with_capacity(EXTENDED_KEY_LENGTH + 4);
 
         buffer
-            .write_u32::<BigEndian>(XPRIV_VERSION_BYTE)
+            .write_u32::<BigEndian>(version)
             .and_then(|_| buffer.write_u8(self.depth))
             .and_then(|_| buffer.write(&self.parent_fingerprint))
             .and_then(|_| buffer.write_u32::<BigEndian>(self.index))
             .and_then(|_| buffer.write(&self.chain_code))
             .and_then(|_| buffer.write_u8(0))
-            .and_then(|_| buffer.write(&self.private_key.to_bytes()))?;
-
-        let checksum = &Hash::sha_256d(&buffer).to_bytes()[0..4];
-        buffer.write_all(checksum)?;
+            .and_then(|_| buffer.write(&Zeroizing::new(self.private_key.to_bytes())))?;
 
-        Ok(bs58::encode(buffer).into_string())
+        Ok(encode_extended_key(buffer))
     }
 
     pub fn from_mnemonic_and_passphrase_impl(mnemonic: &[u8], passphrase: Option<Vec<u8>>) -> Result<Self, BSVErrors> {
@@ -62,56 +109,72 @@ impl ExtendedPrivateKey {
         };
 
         let seed = KDF::pbkdf2(mnemonic, Some(fixed_phrase.to_vec()), crate::PBKDF2Hashes::SHA512, 2048, 64);
-        let seed_bytes = seed.get_hash().to_bytes();
+        let seed_bytes = Zeroizing::new(seed.get_hash().to_bytes());
         Self::from_seed_impl(&seed_bytes)
     }
 
     pub fn from_string_impl(xprv_string: &str) -> Result<Self, BSVErrors> {
-        let mut cursor = Cursor::new(bs58::decode(xprv_string).into_vec()?);
+        Self::from_string_with_versions_impl(xprv_string, &DEFAULT_PRIVATE_VERSIONS)
+    }
 
-        // Skip the first 4 bytes "xprv"
-        cursor.set_position(4);
+    /**
+     * Parses an extended private key that must use the given version bytes.
+     * The version must be a BIP32 or SLIP-132 version (eg. zprv), use from_string_with_version_pair for other versions.
+     */
+    pub fn from_string_with_version_impl(xprv_string: &str, version: u32) -> Result<Self, BSVErrors> {
+        Self::from_string_with_versions_impl(xprv_string, &[version])
+    }
 
-        let depth = cursor.read_u8()?;
-        let mut parent_fingerprint = vec![0; 4];
-        cursor.read_exact(&mut parent_fingerprint)?;
-        let index = cursor.read_u32::<BigEndian>()?;
+    /**
+     * Parses an extended private key that must use the given private version bytes.
+     * xpubs derived from it are serialised with the given public version bytes.
+     */
+    pub fn from_string_with_version_pair_impl(xprv_string: &str, private_version: u32, public_version: u32) -> Result<Self, BSVErrors> {
+        let mut key = Self::decode_impl(xprv_string, &[private_version])?;
+        key.public_version = public_version;
+        Ok(key)
+    }
 
-        let mut chain_code = vec![0; 32];
-        cursor.read_exact(&mut chain_code)?;
+    fn from_string_with_versions_impl(xprv_string: &str, allowed_versions: &[u32]) -> Result<Self, BSVErrors> {
+        let mut key = Self::decode_impl(xprv_string, allowed_versions)?;
+        key.public_version = public_version_for(key.version)?;
+        Ok(key)
+    }
 
-        // Skip appended 0 byte on private key
-        cursor.set_position(cursor.position() + 1);
+    fn decode_impl(xprv_string: &str, allowed_versions: &[u32]) -> Result<Self, BSVErrors> {
+        let mut header = decode_extended_key(xprv_string, allowed_versions)?;
 
-        let mut private_key_bytes = vec![0; 32];
-        cursor.read_exact(&mut private_key_bytes)?;
-        let private_key = PrivateKey::from_bytes_impl(&private_key_bytes)?;
-        let public_key = PublicKey::from_private_key_impl(&private_key);
+        // Private keys are prefixed with 0x00 to pad them to the same length as a compressed public key
+        if header.key_data[0] != 0 {
+            return Err(BSVErrors::InvalidExtendedKeyPrefix(header.key_data[0]));
+        }
 
-        let mut checksum = vec![0; 4];
-        cursor.read_exact(&mut checksum)?;
+        let private_key = PrivateKey::from_bytes_impl(&header.key_data[1..]);
+        header.key_data.zeroize();
+        let private_key = private_key?;
+        let public_key = PublicKey::from_private_key_impl(&private_key);
 
         Ok(ExtendedPrivateKey {
             private_key,
             public_key,
-            chain_code,
-            depth,
-            index,
-            parent_fingerprint,
+            chain_code: header.chain_code,
+            depth: header.depth,
+            index: header.index,
+            parent_fingerprint: header.parent_fingerprint,
+            version: header.version,
+            public_version: XPUB_VERSION_BYTE,
         })
     }
 
     pub fn from_random_impl() -> Result<Self, BSVErrors> {
-        let mut seed = vec![0; 64];
+        let mut seed = Zeroizing::new(vec![0; 64]);
         getrandom(&mut seed)?;
 
         Self::from_seed_impl(&seed)
     }
 
     pub fn from_seed_impl(seed: &[u8]) -> Result<Self, BSVErrors> {
-        let seed_hmac = Hash::sha_512_hmac(seed, b"Bitcoin seed");
-
-        let seed_bytes = seed_hmac.to_bytes();
+        let seed_bytes = Zeroizing::new(Hash::sha_512_hmac(seed, b"Bitcoin seed").0);
         let mut seed_chunks = seed_bytes.chunks_exact(32_usize);
         let private_key_bytes = match seed_chunks.next() {
             Some(b) => b,
@@ -133,36 +196,31 @@ impl ExtendedPrivateKey {
             depth: 0,
             index: 0,
             parent_fingerprint: [0, 0, 0, 0].to_vec(),
+            version: XPRIV_VERSION_BYTE,
+            public_version: XPUB_VERSION_BYTE,
         })
     }
 
     pub fn derive_impl(&self, index: u32) -> Result<ExtendedPrivateKey, BSVErrors> {
         let is_hardened = index >= HARDENED_KEY_OFFSET;
 
-        let key_data = match is_hardened {
-            true => {
-                let mut bytes: Vec<u8> = vec![0x0];
-                bytes.extend_from_slice(&self.private_key.clone().to_bytes());
-                bytes.extend_from_slice(&index.to_be_bytes());
-                bytes
-            }
-            false => {
-                let mut bytes: Vec<u8> = vec![];
-
-                let pub_key_bytes = &self.public_key.clone().to_bytes_impl()?;
+        let pub_key_bytes = self.public_key.to_bytes_impl()?;
 
-                bytes.extend_from_slice(pub_key_bytes);
-                bytes.extend_from_slice(&index.to_be_bytes());
-                bytes
+        // Sized up front so the private key is not left behind by a reallocation
+        let mut key_data = Zeroizing::new(Vec::with_capacity(CHILD_KEY_DATA_LENGTH));
+        match is_hardened {
+            true => {
+                key_data.push(0x0);
+                key_data.extend_from_slice(&Zeroizing::new(self.private_key.to_bytes()));
             }
-        };
+            false => key_data.extend_from_slice(&pub_key_bytes),
+        }
+        key_data.extend_from_slice(&index.to_be_bytes());
 
-        let pub_key_bytes = &self.public_key.clone().to_bytes_impl()?;
-        let hash = Hash::hash_160(pub_key_bytes);
+        let hash = Hash::hash_160(&pub_key_bytes);
         let fingerprint = &hash.to_bytes()[0..4];
 
-        let hmac = Hash::sha_512_hmac(&key_data, &self.chain_code.clone());
-        let seed_bytes = hmac.to_bytes();
+        let seed_bytes = Zeroizing::new(Hash::sha_512_hmac(&key_data, &self.chain_code).0);
 
         let mut seed_chunks = seed_bytes.chunks_exact(32_usize);
         // let mut seed_chunks = seed_bytes.chunks_exact(32 as usize);
@@ -175,14 +233,22 @@ impl ExtendedPrivateKey {
             None => return Err(BSVErrors::InvalidSeedHmacError("Could not get 32 bytes for chain code".into())),
         };
 
-        let parent_scalar = SecretKey::from_bytes(self.private_key.clone().to_bytes().as_slice())?.to_secret_scalar();
+        let mut parent_scalar = *self.private_key.secret_key.to_secret_scalar();
 
-        let il_scalar = Scalar::from_bytes_reduced(&SecretKey::from_bytes(private_key_bytes)?.to_secret_scalar().to_bytes());
+        let mut il_scalar = Scalar::from_bytes_reduced(&SecretKey::from_bytes(private_key_bytes)?.to_secret_scalar().to_bytes());
 
         // child_private_key = il + parent_key % n
-        let derived_private_key = parent_scalar.add(il_scalar);
+        let mut derived_private_key = parent_scalar.add(il_scalar);
+        let mut derived_private_key_bytes = derived_private_key.to_bytes();
 
-        let child_private_key = PrivateKey::from_bytes_impl(&derived_private_key.to_bytes())?;
+        let child_private_key = PrivateKey::from_bytes_impl(&derived_private_key_bytes);
+
+        parent_scalar.zeroize();
+        il_scalar.zeroize();
+        derived_private_key.zeroize();
+        derived_private_key_bytes[..].zeroize();
+
+        let child_private_key = child_private_key?;
 
         let child_chain_code_bytes = child_chain_code.to_vec();
         let child_pub_key = PublicKey::from_private_key_impl(&child_private_key);
@@ -194,6 +260,8 @@ impl ExtendedPrivateKey {
             depth: self.depth + 1,
             index,
             parent_fingerprint: fingerprint.to_vec(),
+            version: self.version,
+            public_version: self.public_version,
         })
     }
 
@@ -269,6 +337,26 @@ impl ExtendedPrivateKey {
     pub fn get_index(&self) -> u32 {
         self.index
     }
+
+    #[wasm_bindgen(js_name = getVersion)]
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    /**
+     * The version bytes of xpubs derived from this key.
+     */
+    #[wasm_bindgen(js_name = getPublicVersion)]
+    pub fn get_public_version(&self) -> u32 {
+        self.public_version
+    }
+
+    /**
+     * Constant time comparison of two extended private keys.
+     */
+    pub fn equals(&self, other: &ExtendedPrivateKey) -> bool {
+        self == other
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -322,6 +410,30 @@ impl ExtendedPrivateKey {
         }
     }
 
+    #[wasm_bindgen(js_name = fromStringWithVersion)]
+    pub fn from_string_with_version(xprv_string: &str, version: u32) -> Result<ExtendedPrivateKey, JsValue> {
+        match Self::from_string_with_version_impl(xprv_string, version) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromStringWithVersionPair)]
+    pub fn from_string_with_version_pair(xprv_string: &str, private_version: u32, public_version: u32) -> Result<ExtendedPrivateKey, JsValue> {
+        match Self::from_string_with_version_pair_impl(xprv_string, private_version, public_version) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toStringWithVersion)]
+    pub fn to_string_with_version(&self, version: u32) -> Result<String, JsValue> {
+        match Self::to_string_with_version_impl(&self, version) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
     #[wasm_bindgen(js_name = fromMnemonic)]
     pub fn from_mnemonic(mnemonic: &[u8], passphrase: Option<Vec<u8>>) -> Result<ExtendedPrivateKey, JsValue> {
         match Self::from_mnemonic_and_passphrase_impl(mnemonic, passphrase) {
@@ -357,6 +469,18 @@ impl ExtendedPrivateKey {
         Self::to_string_impl(self)
     }
 
+    pub fn from_string_with_version(xprv_string: &str, version: u32) -> Result<ExtendedPrivateKey, BSVErrors> {
+        Self::from_string_with_version_impl(xprv_string, version)
+    }
+
+    pub fn from_string_with_version_pair(xprv_string: &str, private_version: u32, public_version: u32) -> Result<ExtendedPrivateKey, BSVErrors> {
+        Self::from_string_with_version_pair_impl(xprv_string, private_version, public_version)
+    }
+
+    pub fn to_string_with_version(&self, version: u32) -> Result<String, BSVErrors> {
+        Self::to_string_with_version_impl(self, version)
+    }
+
     pub fn from_mnemonic(mnemonic: &[u8], passphrase: Option<Vec<u8>>) -> Result<ExtendedPrivateKey, BSVErrors> {
         Self::from_mnemonic_and_passphrase_impl(mnemonic, passphrase)
     }
@@ -1,21 +1,23 @@
 use elliptic_curve::sec1::ToEncodedPoint;
 use k256::{ProjectivePoint, PublicKey as K256PublicKey, Scalar, SecretKey};
 
-use crate::{HARDENED_KEY_OFFSET, XPUB_VERSION_BYTE};
-use std::io::{Cursor, Read, Write};
+use crate::{decode_extended_key, encode_extended_key, DEFAULT_PUBLIC_VERSIONS, HARDENED_KEY_OFFSET, XPUB_VERSION_BYTE};
+use std::io::Write;
 
 use crate::{hash::Hash, BSVErrors, ExtendedPrivateKey, PublicKey};
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{BigEndian, WriteBytesExt};
 use getrandom::*;
 use wasm_bindgen::{prelude::*, throw_str};
 
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct ExtendedPublicKey {
     public_key: PublicKey,
     chain_code: Vec<u8>,
     depth: u8,
     index: u32,
     parent_fingerprint: Vec<u8>,
+    version: u32,
 }
 
 impl ExtendedPublicKey {
@@ -28,63 +30,63 @@ impl ExtendedPublicKey {
             depth: *depth,
             index: *index,
             parent_fingerprint: fingerprint.to_vec(),
+            version: XPUB_VERSION_BYTE,
         }
     }
 
     pub fn to_string_impl(&self) -> Result<String, BSVErrors> {
-        let mut cursor: Cursor<Vec<u8>> = Cursor::new(Vec::new());
-
-        cursor
-            .write_u32::<BigEndian>(XPUB_VERSION_BYTE)
-            .and_then(|_| cursor.write_u8(self.depth))
-            .and_then(|_| cursor.write(&self.parent_fingerprint))
-            .and_then(|_| cursor.write_u32::<BigEndian>(self.index))
-            .and_then(|_| cursor.write(&self.chain_code))?;
-
-        let pub_key_bytes = self.public_key.to_bytes_impl()?;
-        cursor.write_all(&pub_key_bytes)?;
+        self.to_string_with_version_impl(self.version)
+    }
 
-        let mut serialised = Vec::new();
-        cursor.set_position(0);
-        cursor.read_to_end(&mut serialised)?;
+    /**
+     * Serialises this key with the given version bytes, eg. TPUB_VERSION_BYTE for testnet or a SLIP-132 version.
+     */
+    pub fn to_string_with_version_impl(&self, version: u32) -> Result<String, BSVErrors> {
+        let mut buffer: Vec<u8> = vec![];
 
-        let checksum = &Hash::sha_256d(&serialised).to_bytes()[0..4];
-        cursor.write_all(checksum)?;
+        buffer
+            .write_u32::<BigEndian>(version)
+            .and_then(|_| buffer.write_u8(self.depth))
+            .and_then(|_| buffer.write(&self.parent_fingerprint))
+            .and_then(|_| buffer.write_u32::<BigEndian>(self.index))
+            .and_then(|_| buffer.write(&self.chain_code))?;
 
-        serialised = Vec::new();
-        cursor.set_position(0);
-        cursor.read_to_end(&mut serialised)?;
+        let pub_key_bytes = self.public_key.to_compressed_impl()?.to_bytes_impl()?;
+        buffer.write_all(&pub_key_bytes)?;
 
-        Ok(bs58::encode(serialised).into_string())
+        Ok(encode_extended_key(buffer))
     }
 
     pub fn from_string_impl(xpub_string: &str) -> Result<Self, BSVErrors> {
-        let mut cursor = Cursor::new(bs58::decode(xpub_string).into_vec()?);
-
-        // Skip the first 4 bytes "xprv"
-        cursor.set_position(4);
+        Self::from_string_with_versions_impl(xpub_string, &DEFAULT_PUBLIC_VERSIONS)
+    }
 
-        let depth = cursor.read_u8()?;
-        let mut parent_fingerprint = vec![0; 4];
-        cursor.read_exact(&mut parent_fingerprint)?;
-        let index = cursor.read_u32::<BigEndian>()?;
+    /**
+     * Parses an extended public key that must use the given version bytes.
+     */
+    pub fn from_string_with_version_impl(xpub_string: &str, version: u32) -> Result<Self, BSVErrors> {
+        Self::from_string_with_versions_impl(xpub_string, &[version])
+    }
 
-        let mut chain_code = vec![0; 32];
-        cursor.read_exact(&mut chain_code)?;
+    fn from_string_with_versions_impl(xpub_string: &str, allowed_versions: &[u32]) -> Result<Self, BSVErrors> {
+        let header = decode_extended_key(xpub_string, allowed_versions)?;
 
-        let mut pub_key_bytes = vec![0; 33];
-        cursor.read_exact(&mut pub_key_bytes)?;
-        let public_key = PublicKey::from_bytes_impl(&pub_key_bytes)?;
+        // Public keys are always serialised compressed
+        if header.key_data[0] != 0x02 && header.key_data[0] != 0x03 {
+            return Err(BSVErrors::InvalidExtendedKeyPrefix(header.key_data[0]));
+        }
 
-        let mut checksum = vec![0; 4];
-        cursor.read_exact(&mut checksum)?;
+        // Make sure the point is actually on the curve
+        K256PublicKey::from_sec1_bytes(&header.key_data)?;
+        let public_key = PublicKey::from_bytes_impl(&header.key_data)?;
 
         Ok(ExtendedPublicKey {
             public_key,
-            chain_code,
-            depth,
-            index,
-            parent_fingerprint,
+            chain_code: header.chain_code,
+            depth: header.depth,
+            index: header.index,
+            parent_fingerprint: header.parent_fingerprint,
+            version: header.version,
         })
     }
 
@@ -148,6 +150,7 @@ impl ExtendedPublicKey {
             depth: self.depth + 1,
             index,
             parent_fingerprint: fingerprint.to_vec(),
+            version: self.version,
         })
     }
 
@@ -210,6 +213,7 @@ impl ExtendedPublicKey {
             depth: xpriv.get_depth(),
             index: xpriv.get_index(),
             parent_fingerprint: xpriv.get_parent_fingerprint(),
+            version: xpriv.get_public_version(),
         }
     }
 
@@ -232,6 +236,11 @@ impl ExtendedPublicKey {
     pub fn get_index(&self) -> u32 {
         self.index
     }
+
+    #[wasm_bindgen(js_name = getVersion)]
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -284,6 +293,22 @@ impl ExtendedPublicKey {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    #[wasm_bindgen(js_name = fromStringWithVersion)]
+    pub fn from_string_with_version(xpub_string: &str, version: u32) -> Result<ExtendedPublicKey, JsValue> {
+        match Self::from_string_with_version_impl(xpub_string, version) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toStringWithVersion)]
+    pub fn to_string_with_version(&self, version: u32) -> Result<String, JsValue> {
+        match Self::to_string_with_version_impl(&self, version) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -309,4 +334,12 @@ impl ExtendedPublicKey {
     pub fn to_string(&self) -> Result<String, BSVErrors> {
         Self::to_string_impl(self)
     }
+
+    pub fn from_string_with_version(xpub_string: &str, version: u32) -> Result<ExtendedPublicKey, BSVErrors> {
+        Self::from_string_with_version_impl(xpub_string, version)
+    }
+
+    pub fn to_string_with_version(&self, version: u32) -> Result<String, BSVErrors> {
+        Self::to_string_with_version_impl(self, version)
+    }
 }
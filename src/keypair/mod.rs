@@ -1,13 +1,26 @@
+mod bip44_account;
+mod extended_key_encoding;
 mod extended_private_key;
 mod extended_public_key;
 mod private_key;
 mod public_key;
+mod signer;
+mod type42_derivation;
 
+pub use bip44_account::*;
+pub(crate) use extended_key_encoding::*;
 pub use extended_private_key::*;
 pub use extended_public_key::*;
 pub use private_key::*;
 pub use public_key::*;
+pub use signer::*;
 
 pub const HARDENED_KEY_OFFSET: u32 = 0x80000000;
 pub const XPRIV_VERSION_BYTE: u32 = 0x0488ade4;
 pub const XPUB_VERSION_BYTE: u32 = 0x0488b21e;
+pub const TPRV_VERSION_BYTE: u32 = 0x04358394;
+pub const TPUB_VERSION_BYTE: u32 = 0x043587cf;
+
+pub const BIP44_PURPOSE: u32 = 44;
+pub const BSV_COIN_TYPE: u32 = 236;
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
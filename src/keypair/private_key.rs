@@ -9,8 +9,10 @@ use k256::ecdsa::digest::Digest;
 use k256::ecdsa::recoverable;
 use k256::{EncodedPoint, SecretKey};
 use rand_core::OsRng;
+use subtle::{Choice, ConstantTimeEq};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::throw_str;
+use zeroize::{Zeroize, Zeroizing};
 
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
@@ -19,6 +21,26 @@ pub struct PrivateKey {
     pub(crate) is_pub_key_compressed: bool,
 }
 
+/**
+ * Compares the secret in constant time, the underlying SecretKey wipes itself on drop.
+ */
+impl ConstantTimeEq for PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let bytes = Zeroizing::new(self.to_bytes());
+        let other_bytes = Zeroizing::new(other.to_bytes());
+
+        bytes.ct_eq(&other_bytes) & Choice::from((self.is_pub_key_compressed == other.is_pub_key_compressed) as u8)
+    }
+}
+
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for PrivateKey {}
+
 /**
  * Internal Methods
  */
@@ -32,17 +54,17 @@ impl PrivateKey {
 
     pub(crate) fn to_wif_impl(&self) -> Result<String, BSVErrors> {
         // 1. Get Private Key hex
-        let priv_key_hex = self.to_hex();
+        let priv_key_hex = Zeroizing::new(self.to_hex());
 
         // 2. Add 0x80 in front + 0x01 to end if compressed pub key
 
-        let padded_hex = match self.is_pub_key_compressed {
-            true => format!("80{}01", priv_key_hex),
-            false => format!("80{}", priv_key_hex),
-        };
+        let padded_hex = Zeroizing::new(match self.is_pub_key_compressed {
+            true => format!("80{}01", *priv_key_hex),
+            false => format!("80{}", *priv_key_hex),
+        });
 
         // 3. SHA256d
-        let bytes = hex::decode(padded_hex.clone())?;
+        let bytes = Zeroizing::new(hex::decode(&*padded_hex)?);
 
         let shad_hex = Hash::sha_256d(&bytes).to_bytes();
 
@@ -50,15 +72,20 @@ impl PrivateKey {
         let checksum = shad_hex.to_vec()[0..4].to_hex();
 
         // 5. Add checksum to end of padded private key
-        let extended_key = format!("{}{}", padded_hex, checksum);
+        let extended_key = Zeroizing::new(format!("{}{}", *padded_hex, checksum));
 
         // 6 Base58 Result
-        let extended_key_bytes = hex::decode(extended_key)?;
+        let extended_key_bytes = Zeroizing::new(hex::decode(&*extended_key)?);
 
-        Ok(bs58::encode(extended_key_bytes).into_string())
+        Ok(bs58::encode(&*extended_key_bytes).into_string())
     }
 
     pub(crate) fn from_bytes_impl(bytes: &[u8]) -> Result<PrivateKey, BSVErrors> {
+        // SecretKey only checks the upper bound, a zero key would panic when getting the public key
+        if bytes.iter().all(|x| *x == 0) {
+            return Err(BSVErrors::CurveError(elliptic_curve::Error));
+        }
+
         let secret_key = SecretKey::from_bytes(bytes)?;
 
         Ok(PrivateKey {
@@ -68,15 +95,15 @@ impl PrivateKey {
     }
 
     pub(crate) fn from_hex_impl(hex_str: &str) -> Result<PrivateKey, BSVErrors> {
-        let bytes = hex::decode(hex_str)?;
+        let bytes = Zeroizing::new(hex::decode(hex_str)?);
 
         Self::from_bytes_impl(&bytes)
     }
 
     pub(crate) fn from_wif_impl(wif_string: &str) -> Result<PrivateKey, BSVErrors> {
         // 1. Decode from Base58
-        let wif_bytes = bs58::decode(wif_string).into_vec()?;
-        let wif_without_checksum = wif_bytes[0..wif_bytes.len() - 4].to_vec();
+        let wif_bytes = Zeroizing::new(bs58::decode(wif_string).into_vec()?);
+        let wif_without_checksum = Zeroizing::new(wif_bytes[0..wif_bytes.len() - 4].to_vec());
 
         // 2. Check the Checksum
         let checksum = wif_bytes[wif_bytes.len() - 4..].to_hex();
@@ -101,10 +128,10 @@ impl PrivateKey {
 
         let is_compressed_pub_key = is_compressed(&wif_without_checksum);
         // 3. Check if compressed public key, return private key string
-        let private_key_hex = match is_compressed_pub_key {
+        let private_key_hex = Zeroizing::new(match is_compressed_pub_key {
             true => wif_without_checksum[1..wif_without_checksum.len() - 1].to_hex(),
             false => wif_without_checksum[1..].to_hex(),
-        };
+        });
 
         Ok(PrivateKey::from_hex_impl(&private_key_hex)?.compress_public_key(is_compressed_pub_key))
     }
@@ -138,13 +165,23 @@ impl PrivateKey {
 impl PrivateKey {
     #[wasm_bindgen(js_name = toBytes)]
     pub fn to_bytes(&self) -> Vec<u8> {
-        self.secret_key.to_bytes().to_vec()
+        let mut secret_key_bytes = self.secret_key.to_bytes();
+        let bytes = secret_key_bytes.to_vec();
+        secret_key_bytes[..].zeroize();
+        bytes
     }
 
     #[wasm_bindgen(js_name = toHex)]
     pub fn to_hex(&self) -> String {
-        let secret_key_bytes = self.to_bytes();
-        hex::encode(secret_key_bytes)
+        let secret_key_bytes = Zeroizing::new(self.to_bytes());
+        hex::encode(&*secret_key_bytes)
+    }
+
+    /**
+     * Constant time comparison of two private keys.
+     */
+    pub fn equals(&self, other: &PrivateKey) -> bool {
+        self == other
     }
 
     #[wasm_bindgen(js_name = fromRandom)]
@@ -0,0 +1,195 @@
+#[cfg(target_arch = "wasm32")]
+use crate::get_hash_digest;
+use crate::{BSVErrors, PrivateKey, PublicKey, Signature, SigningHash, ECDSA};
+#[cfg(target_arch = "wasm32")]
+use digest::FixedOutput;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{prelude::*, throw_str, JsValue};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+/**
+ * Anything that can sign for a public key, so signing does not require the private key in memory,
+ * eg. keys held by a browser extension or an HSM.
+ */
+pub trait Signer {
+    fn get_public_key(&self) -> Result<PublicKey, BSVErrors>;
+
+    /**
+     * Signs the hash_algo digest of the preimage. Signers that only sign digests should sign get_hash_digest(hash_algo, preimage).
+     * reverse_k asks deterministic (RFC6979) signers to derive the nonce from the byte-reversed digest, as transaction signing does; others can ignore it.
+     * The recovery id must match the public key, S may be high.
+     */
+    fn sign(&self, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, BSVErrors>;
+}
+
+impl Signer for PrivateKey {
+    fn get_public_key(&self) -> Result<PublicKey, BSVErrors> {
+        self.get_public_key_impl()
+    }
+
+    fn sign(&self, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, BSVErrors> {
+        ECDSA::sign_with_deterministic_k_impl(self, preimage, hash_algo, reverse_k)
+    }
+}
+
+impl ECDSA {
+    /**
+     * Signs with any Signer, normalising the result to low S.
+     */
+    pub(crate) fn sign_with_signer_impl(signer: &dyn Signer, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, BSVErrors> {
+        signer.sign(preimage, hash_algo, reverse_k)?.normalise_s_impl()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ECDSA {
+    /**
+     * Signs with any Signer, eg. an external or hardware-backed key. The signature is normalised to low S.
+     */
+    pub fn sign_with_signer(signer: &dyn Signer, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, BSVErrors> {
+        ECDSA::sign_with_signer_impl(signer, preimage, hash_algo, reverse_k)
+    }
+}
+
+/**
+ * Signer backed by a JS callback, eg. a browser extension: sign(digest: Uint8Array) => Uint8Array.
+ * The callback receives the 32 byte digest and returns a DER signature or a 65 byte compact signature.
+ *
+ * The *WithSigner functions call it synchronously. Callbacks returning a Promise, eg. hardware wallets,
+ * need the *WithSignerAsync functions, which return a Promise of the result.
+ */
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct JsSigner {
+    public_key: PublicKey,
+    sign_fn: js_sys::Function,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl JsSigner {
+    fn call_sign_fn(&self, digest: &[u8]) -> Result<JsValue, BSVErrors> {
+        self.sign_fn
+            .call1(&JsValue::NULL, &js_sys::Uint8Array::from(digest))
+            .map_err(|e| BSVErrors::SignerError(format!("sign callback threw: {:?}", e)))
+    }
+
+    fn to_signature(&self, result: JsValue, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        let signature_bytes = match result.dyn_into::<js_sys::Uint8Array>() {
+            Ok(v) => v.to_vec(),
+            Err(e) => return Err(BSVErrors::SignerError(format!("sign callback must return a Uint8Array, got {:?}", e))),
+        };
+
+        let signature = match signature_bytes.len() {
+            65 => Signature::from_compact_impl(&signature_bytes)?,
+            _ => Signature::from_der_impl(&signature_bytes, false)?,
+        };
+
+        self.with_recovery_id(signature, preimage, hash_algo)
+    }
+
+    /**
+     * Like Signer::sign, but awaits the callback's result if it returns a Promise.
+     */
+    pub(crate) async fn sign_async(&self, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        let digest = get_hash_digest(hash_algo, preimage).finalize_fixed();
+        let result = self.call_sign_fn(&digest)?;
+
+        // Resolving a plain value gives the value itself, so synchronous callbacks work too
+        let result = JsFuture::from(js_sys::Promise::resolve(&result))
+            .await
+            .map_err(|e| BSVErrors::SignerError(format!("sign callback rejected: {:?}", e)))?;
+
+        self.to_signature(result, preimage, hash_algo)
+    }
+
+    /**
+     * DER signatures carry no recovery id, so find the one that recovers this signer's public key.
+     */
+    fn with_recovery_id(&self, signature: Signature, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        let expected = self.public_key.to_compressed_impl()?;
+
+        for recovery_i in 0..2 {
+            let candidate = Signature { recovery_i, ..signature.clone() };
+            if let Ok(v) = candidate.get_public_key(preimage, hash_algo) {
+                if v.to_compressed_impl()? == expected {
+                    return Ok(candidate);
+                }
+            }
+        }
+
+        Err(BSVErrors::SignerError("Signature from the sign callback is not valid for the signer's public key".into()))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Signer for JsSigner {
+    fn get_public_key(&self) -> Result<PublicKey, BSVErrors> {
+        Ok(self.public_key.clone())
+    }
+
+    fn sign(&self, preimage: &[u8], hash_algo: SigningHash, _reverse_k: bool) -> Result<Signature, BSVErrors> {
+        let digest = get_hash_digest(hash_algo, preimage).finalize_fixed();
+        let result = self.call_sign_fn(&digest)?;
+
+        self.to_signature(result, preimage, hash_algo)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl JsSigner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(public_key: &PublicKey, sign_fn: js_sys::Function) -> JsSigner {
+        JsSigner {
+            public_key: public_key.clone(),
+            sign_fn,
+        }
+    }
+
+    #[wasm_bindgen(js_name = getPublicKey)]
+    pub fn get_public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl ECDSA {
+    /**
+     * Signs with a JS signing callback that may return a Promise, normalising the result to low S.
+     */
+    pub(crate) async fn sign_with_js_signer_async_impl(signer: &JsSigner, preimage: &[u8], hash_algo: SigningHash) -> Result<Signature, BSVErrors> {
+        signer.sign_async(preimage, hash_algo).await?.normalise_s_impl()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl ECDSA {
+    /**
+     * Signs with the JS signing callback. The signature is normalised to low S.
+     */
+    #[wasm_bindgen(js_name = signWithSigner)]
+    pub fn sign_with_signer(signer: &JsSigner, preimage: &[u8], hash_algo: SigningHash, reverse_k: bool) -> Result<Signature, JsValue> {
+        match ECDSA::sign_with_signer_impl(signer, preimage, hash_algo, reverse_k) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+    /**
+     * Signs with a JS signing callback that may return a Promise. Returns a Promise of the signature, normalised to low S.
+     */
+    #[wasm_bindgen(js_name = signWithSignerAsync)]
+    pub fn sign_with_signer_async(signer: &JsSigner, preimage: &[u8], hash_algo: SigningHash) -> js_sys::Promise {
+        let signer = signer.clone();
+        let preimage = preimage.to_vec();
+
+        future_to_promise(async move {
+            match ECDSA::sign_with_js_signer_async_impl(&signer, &preimage, hash_algo).await {
+                Ok(v) => Ok(v.into()),
+                Err(e) => Err(JsValue::from_str(&e.to_string())),
+            }
+        })
+    }
+}
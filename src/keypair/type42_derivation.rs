@@ -0,0 +1,96 @@
+use crate::{BSVErrors, Hash, PrivateKey, PublicKey};
+use elliptic_curve::sec1::ToEncodedPoint;
+use k256::{FieldBytes, ProjectivePoint, PublicKey as K256PublicKey, Scalar};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::{Zeroize, Zeroizing};
+
+/**
+ * BRC-42 (Type-42) key derivation. Both parties compute HMAC-SHA256(ECDH point, invoice number) and offset their keys by it:
+ * the sender derives the recipient's child public key and the recipient derives the matching child private key,
+ * without either revealing anything to observers of the root keys.
+ */
+fn invoice_scalar(private_key: &PrivateKey, public_key: &PublicKey, invoice_number: &str) -> Result<Scalar, BSVErrors> {
+    let pub_key_point = K256PublicKey::from_sec1_bytes(&public_key.to_bytes_impl()?)?.to_projective();
+
+    let mut private_scalar = *private_key.secret_key.to_secret_scalar();
+    let shared_point = pub_key_point * private_scalar;
+    private_scalar.zeroize();
+
+    let shared_secret = Zeroizing::new(K256PublicKey::from_affine(shared_point.to_affine())?.to_encoded_point(true).as_bytes().to_vec());
+    let hmac = Zeroizing::new(Hash::sha_256_hmac(invoice_number.as_bytes(), &shared_secret).0);
+
+    Ok(Scalar::from_bytes_reduced(FieldBytes::from_slice(&hmac)))
+}
+
+impl PrivateKey {
+    pub(crate) fn derive_child_impl(&self, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<PrivateKey, BSVErrors> {
+        let mut child_scalar = *self.secret_key.to_secret_scalar() + invoice_scalar(self, counterparty_pub_key, invoice_number)?;
+        let child_bytes = Zeroizing::new(child_scalar.to_bytes().to_vec());
+        child_scalar.zeroize();
+
+        PrivateKey::from_bytes_impl(&child_bytes).map_err(|_| BSVErrors::DerivationError(format!("Invoice number {} derives an invalid private key", invoice_number)))
+    }
+}
+
+impl PublicKey {
+    pub(crate) fn derive_child_impl(&self, counterparty_priv_key: &PrivateKey, invoice_number: &str) -> Result<PublicKey, BSVErrors> {
+        let point = K256PublicKey::from_sec1_bytes(&self.to_bytes_impl()?)?.to_projective();
+        let child_point = point + ProjectivePoint::generator() * invoice_scalar(counterparty_priv_key, self, invoice_number)?;
+
+        let child_key = K256PublicKey::from_affine(child_point.to_affine()).map_err(|_| BSVErrors::DerivationError(format!("Invoice number {} derives an invalid public key", invoice_number)))?;
+        PublicKey::from_bytes_impl(child_key.to_encoded_point(self.is_compressed()).as_bytes())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl PrivateKey {
+    /**
+     * BRC-42: derives this key's child private key for the invoice number, shared with the counterparty's public key.
+     * Matches the counterparty's PublicKey.deriveChild of our public key.
+     */
+    #[wasm_bindgen(js_name = deriveChild)]
+    pub fn derive_child(&self, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<PrivateKey, JsValue> {
+        match self.derive_child_impl(counterparty_pub_key, invoice_number) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl PublicKey {
+    /**
+     * BRC-42: derives the child public key of this (counterparty's) key for the invoice number, using our private key.
+     */
+    #[wasm_bindgen(js_name = deriveChild)]
+    pub fn derive_child(&self, counterparty_priv_key: &PrivateKey, invoice_number: &str) -> Result<PublicKey, JsValue> {
+        match self.derive_child_impl(counterparty_priv_key, invoice_number) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PrivateKey {
+    /**
+     * BRC-42: derives this key's child private key for the invoice number, shared with the counterparty's public key.
+     * Matches the counterparty's PublicKey::derive_child of our public key.
+     */
+    pub fn derive_child(&self, counterparty_pub_key: &PublicKey, invoice_number: &str) -> Result<PrivateKey, BSVErrors> {
+        self.derive_child_impl(counterparty_pub_key, invoice_number)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PublicKey {
+    /**
+     * BRC-42: derives the child public key of this (counterparty's) key for the invoice number, using our private key.
+     */
+    pub fn derive_child(&self, counterparty_priv_key: &PrivateKey, invoice_number: &str) -> Result<PublicKey, BSVErrors> {
+        self.derive_child_impl(counterparty_priv_key, invoice_number)
+    }
+}
@@ -53,3 +53,15 @@ pub use bsm::*;
 
 pub mod ecies;
 pub use ecies::*;
+
+pub mod brc2;
+pub use brc2::*;
+
+pub mod messages;
+pub use messages::*;
+
+pub mod vault;
+pub use vault::*;
+
+pub mod shamir;
+pub use shamir::*;
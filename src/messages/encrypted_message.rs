@@ -0,0 +1,81 @@
+use crate::messages::{check_recipient, read_message_header, MESSAGE_KEY_ID_LENGTH, PUB_KEY_LENGTH};
+use crate::{BSVErrors, PrivateKey, PublicKey, BRC2};
+use rand_core::{OsRng, RngCore};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+const ENCRYPTED_MESSAGE_VERSION: [u8; 4] = [0x42, 0x42, 0x10, 0x33];
+
+/**
+ * BRC-78 encrypted messages: version | sender public key | recipient public key | key ID (32 bytes) | BRC-2 ciphertext,
+ * encrypted for the invoice number "2-message encryption-<base64 key ID>".
+ */
+#[wasm_bindgen]
+pub struct EncryptedMessage {}
+
+impl EncryptedMessage {
+    fn invoice_number(key_id: &[u8]) -> String {
+        format!("2-message encryption-{}", base64::encode(key_id))
+    }
+
+    pub(crate) fn encrypt_impl(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey) -> Result<Vec<u8>, BSVErrors> {
+        let mut key_id = [0; MESSAGE_KEY_ID_LENGTH];
+        OsRng.fill_bytes(&mut key_id);
+
+        let mut buffer = ENCRYPTED_MESSAGE_VERSION.to_vec();
+        buffer.extend_from_slice(&sender_priv_key.get_public_key_impl()?.to_compressed_impl()?.to_bytes_impl()?);
+        buffer.extend_from_slice(&recipient_pub_key.to_compressed_impl()?.to_bytes_impl()?);
+        buffer.extend_from_slice(&key_id);
+        buffer.extend_from_slice(&BRC2::encrypt_impl(message, sender_priv_key, recipient_pub_key, &EncryptedMessage::invoice_number(&key_id))?);
+
+        Ok(buffer)
+    }
+
+    pub(crate) fn decrypt_impl(message: &[u8], recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        let (sender_pub_key, rest) = read_message_header(message, &ENCRYPTED_MESSAGE_VERSION)?;
+        if rest.len() < PUB_KEY_LENGTH + MESSAGE_KEY_ID_LENGTH {
+            return Err(BSVErrors::BRCError(format!("Message is too short: {} bytes", message.len())));
+        }
+
+        let (recipient_pub_key, rest) = rest.split_at(PUB_KEY_LENGTH);
+        check_recipient(recipient_pub_key, recipient_priv_key)?;
+
+        let (key_id, ciphertext) = rest.split_at(MESSAGE_KEY_ID_LENGTH);
+        BRC2::decrypt_impl(ciphertext, recipient_priv_key, &sender_pub_key, &EncryptedMessage::invoice_number(key_id))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl EncryptedMessage {
+    pub fn encrypt(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey) -> Result<Vec<u8>, JsValue> {
+        match EncryptedMessage::encrypt_impl(message, sender_priv_key, recipient_pub_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Errors if the message is for another recipient or was modified.
+     */
+    pub fn decrypt(message: &[u8], recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, JsValue> {
+        match EncryptedMessage::decrypt_impl(message, recipient_priv_key) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl EncryptedMessage {
+    pub fn encrypt(message: &[u8], sender_priv_key: &PrivateKey, recipient_pub_key: &PublicKey) -> Result<Vec<u8>, BSVErrors> {
+        EncryptedMessage::encrypt_impl(message, sender_priv_key, recipient_pub_key)
+    }
+
+    /**
+     * Errors if the message is for another recipient or was modified.
+     */
+    pub fn decrypt(message: &[u8], recipient_priv_key: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        EncryptedMessage::decrypt_impl(message, recipient_priv_key)
+    }
+}
@@ -0,0 +1,47 @@
+pub mod encrypted_message;
+pub use encrypted_message::*;
+
+pub mod signed_message;
+pub use signed_message::*;
+
+use crate::{BSVErrors, PrivateKey, PublicKey};
+
+pub const MESSAGE_KEY_ID_LENGTH: usize = 32;
+const PUB_KEY_LENGTH: usize = 33;
+
+/**
+ * Reads the version | sender public key prefix shared by BRC-77 and BRC-78 messages.
+ */
+fn read_message_header<'a>(message: &'a [u8], version: &[u8]) -> Result<(PublicKey, &'a [u8]), BSVErrors> {
+    if message.len() < version.len() + PUB_KEY_LENGTH {
+        return Err(BSVErrors::BRCError(format!("Message is too short: {} bytes", message.len())));
+    }
+
+    let (message_version, rest) = message.split_at(version.len());
+    if message_version != version {
+        return Err(BSVErrors::BRCError(format!(
+            "Message version mismatch: expected {}, received {}",
+            hex::encode(version),
+            hex::encode(message_version)
+        )));
+    }
+
+    let (sender_pub_key, rest) = rest.split_at(PUB_KEY_LENGTH);
+    Ok((PublicKey::from_bytes_impl(sender_pub_key)?, rest))
+}
+
+/**
+ * Checks the recipient public key in a message belongs to the private key being used.
+ */
+fn check_recipient(recipient_pub_key: &[u8], recipient_priv_key: &PrivateKey) -> Result<(), BSVErrors> {
+    let expected = recipient_priv_key.get_public_key_impl()?.to_compressed_impl()?.to_bytes_impl()?;
+    if recipient_pub_key != expected.as_slice() {
+        return Err(BSVErrors::BRCError(format!(
+            "The recipient public key is {} but the private key you are using corresponds to {}",
+            hex::encode(recipient_pub_key),
+            hex::encode(expected)
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::messages::{check_recipient, read_message_header, MESSAGE_KEY_ID_LENGTH, PUB_KEY_LENGTH};
+use crate::{BSVErrors, PrivateKey, PublicKey, Signature, SigningHash, ECDSA};
+use rand_core::{OsRng, RngCore};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+
+const SIGNED_MESSAGE_VERSION: [u8; 4] = [0x42, 0x42, 0x33, 0x01];
+
+/**
+ * Marks a message anyone can verify, in place of the verifier's public key.
+ */
+const ANYONE_VERIFIER: u8 = 0;
+
+/**
+ * BRC-77 signed messages: version | signer public key | verifier public key, or 0 for anyone | key ID (32 bytes) | DER signature.
+ * The signature is made over the SHA256 of the message with the signer's BRC-42 child key for "2-message signing-<base64 key ID>",
+ * so only the verifier can check it. Messages for anyone use the private key 1 as the verifier.
+ */
+#[wasm_bindgen]
+pub struct SignedMessage {}
+
+impl SignedMessage {
+    fn invoice_number(key_id: &[u8]) -> String {
+        format!("2-message signing-{}", base64::encode(key_id))
+    }
+
+    fn anyone_private_key() -> Result<PrivateKey, BSVErrors> {
+        let mut one = [0; 32];
+        one[31] = 1;
+        PrivateKey::from_bytes_impl(&one)
+    }
+
+    pub(crate) fn sign_impl(message: &[u8], signer: &PrivateKey, verifier: Option<&PublicKey>) -> Result<Vec<u8>, BSVErrors> {
+        let mut key_id = [0; MESSAGE_KEY_ID_LENGTH];
+        OsRng.fill_bytes(&mut key_id);
+
+        let verifier_pub_key = match verifier {
+            Some(v) => v.clone(),
+            None => SignedMessage::anyone_private_key()?.get_public_key_impl()?,
+        };
+        let signing_key = signer.derive_child_impl(&verifier_pub_key, &SignedMessage::invoice_number(&key_id))?;
+        let signature = ECDSA::sign_with_deterministic_k_impl(&signing_key, message, SigningHash::Sha256, false)?;
+
+        let mut buffer = SIGNED_MESSAGE_VERSION.to_vec();
+        buffer.extend_from_slice(&signer.get_public_key_impl()?.to_compressed_impl()?.to_bytes_impl()?);
+        match verifier {
+            Some(v) => buffer.extend_from_slice(&v.to_compressed_impl()?.to_bytes_impl()?),
+            None => buffer.push(ANYONE_VERIFIER),
+        }
+        buffer.extend_from_slice(&key_id);
+        buffer.extend_from_slice(&signature.to_der_bytes());
+
+        Ok(buffer)
+    }
+
+    pub(crate) fn verify_impl(message: &[u8], signed_message: &[u8], recipient: Option<&PrivateKey>) -> Result<bool, BSVErrors> {
+        let (signer_pub_key, rest) = read_message_header(signed_message, &SIGNED_MESSAGE_VERSION)?;
+
+        let (recipient, rest) = match rest.first() {
+            Some(&ANYONE_VERIFIER) => (SignedMessage::anyone_private_key()?, &rest[1..]),
+            Some(_) if rest.len() >= PUB_KEY_LENGTH => {
+                let (verifier_pub_key, rest) = rest.split_at(PUB_KEY_LENGTH);
+                let recipient = recipient.ok_or_else(|| {
+                    BSVErrors::BRCError(format!(
+                        "This signature can only be verified with knowledge of a specific private key. The associated public key is: {}",
+                        hex::encode(verifier_pub_key)
+                    ))
+                })?;
+                check_recipient(verifier_pub_key, recipient)?;
+                (recipient.clone(), rest)
+            }
+            _ => return Err(BSVErrors::BRCError(format!("Message is too short: {} bytes", signed_message.len()))),
+        };
+
+        if rest.len() < MESSAGE_KEY_ID_LENGTH {
+            return Err(BSVErrors::BRCError(format!("Message is too short: {} bytes", signed_message.len())));
+        }
+        let (key_id, signature) = rest.split_at(MESSAGE_KEY_ID_LENGTH);
+
+        let signature = Signature::from_der_impl(signature, false)?;
+        let signing_pub_key = signer_pub_key.derive_child_impl(&recipient, &SignedMessage::invoice_number(key_id))?;
+        ECDSA::verify_digest_impl(message, &signing_pub_key, &signature, SigningHash::Sha256)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl SignedMessage {
+    /**
+     * Signs a message only the verifier can check.
+     */
+    pub fn sign(message: &[u8], signer: &PrivateKey, verifier: &PublicKey) -> Result<Vec<u8>, JsValue> {
+        match SignedMessage::sign_impl(message, signer, Some(verifier)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs a message anyone can check.
+     */
+    #[wasm_bindgen(js_name = signForAnyone)]
+    pub fn sign_for_anyone(message: &[u8], signer: &PrivateKey) -> Result<Vec<u8>, JsValue> {
+        match SignedMessage::sign_impl(message, signer, None) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Verifies a message signed for the recipient, or for anyone.
+     */
+    pub fn verify(message: &[u8], signed_message: &[u8], recipient: &PrivateKey) -> Result<bool, JsValue> {
+        match SignedMessage::verify_impl(message, signed_message, Some(recipient)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Verifies a message signed for anyone, errors if it was signed for a specific verifier.
+     */
+    #[wasm_bindgen(js_name = verifyForAnyone)]
+    pub fn verify_for_anyone(message: &[u8], signed_message: &[u8]) -> Result<bool, JsValue> {
+        match SignedMessage::verify_impl(message, signed_message, None) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SignedMessage {
+    /**
+     * Signs a message only the verifier can check.
+     */
+    pub fn sign(message: &[u8], signer: &PrivateKey, verifier: &PublicKey) -> Result<Vec<u8>, BSVErrors> {
+        SignedMessage::sign_impl(message, signer, Some(verifier))
+    }
+
+    /**
+     * Signs a message anyone can check.
+     */
+    pub fn sign_for_anyone(message: &[u8], signer: &PrivateKey) -> Result<Vec<u8>, BSVErrors> {
+        SignedMessage::sign_impl(message, signer, None)
+    }
+
+    /**
+     * Verifies a message signed for the recipient, or for anyone.
+     */
+    pub fn verify(message: &[u8], signed_message: &[u8], recipient: &PrivateKey) -> Result<bool, BSVErrors> {
+        SignedMessage::verify_impl(message, signed_message, Some(recipient))
+    }
+
+    /**
+     * Verifies a message signed for anyone, errors if it was signed for a specific verifier.
+     */
+    pub fn verify_for_anyone(message: &[u8], signed_message: &[u8]) -> Result<bool, BSVErrors> {
+        SignedMessage::verify_impl(message, signed_message, None)
+    }
+}
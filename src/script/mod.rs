@@ -189,6 +189,63 @@ impl Script {
     }
 }
 
+/**
+ * Script Code Methods
+ */
+impl Script {
+    /**
+     * Byte offset of every opcode in the script, push data is skipped rather than interpreted.
+     * A push running past the end of the script consumes the remainder, as it does in the interpreter.
+     */
+    fn opcode_offsets(&self) -> Vec<usize> {
+        let script = &self.0;
+        let mut offsets = vec![];
+        let mut i = 0;
+
+        while i < script.len() {
+            offsets.push(i);
+
+            let (length_bytes, data_length) = match FromPrimitive::from_u8(script[i]) {
+                Some(OpCodes::OP_PUSHDATA1) => (1, script.get(i + 1).map(|x| *x as usize)),
+                Some(OpCodes::OP_PUSHDATA2) => (2, script.get(i + 1..i + 3).map(|x| u16::from_le_bytes([x[0], x[1]]) as usize)),
+                Some(OpCodes::OP_PUSHDATA4) => (4, script.get(i + 1..i + 5).map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]) as usize)),
+                _ => match script[i] {
+                    size @ 0x01..=0x4b => (0, Some(size as usize)),
+                    _ => (0, Some(0)),
+                },
+            };
+
+            i = match data_length {
+                Some(v) => i.saturating_add(1 + length_bytes).saturating_add(v),
+                None => script.len(),
+            };
+        }
+
+        offsets
+    }
+
+    /**
+     * Byte offset of every OP_CODESEPARATOR opcode, ignoring 0xab bytes inside push data.
+     */
+    fn codeseparator_offsets(&self) -> Vec<usize> {
+        let codeseparator = OpCodes::OP_CODESEPARATOR.to_u8().unwrap();
+        self.opcode_offsets().into_iter().filter(|i| self.0[*i] == codeseparator).collect()
+    }
+
+    pub(crate) fn get_subscript_impl(&self, codeseparator_index: Option<usize>) -> Result<Script, BSVErrors> {
+        let index = match codeseparator_index {
+            Some(v) => v,
+            None => return Ok(self.clone()),
+        };
+
+        let offsets = self.codeseparator_offsets();
+        match offsets.get(index) {
+            Some(offset) => Ok(Script(self.0[offset + 1..].to_vec())),
+            None => Err(BSVErrors::OutOfBounds(format!("Cannot get OP_CODESEPARATOR at index {}, script contains {}", index, offsets.len()))),
+        }
+    }
+}
+
 /**
  * Shared Functions
  */
@@ -214,8 +271,12 @@ impl Script {
         hex::encode(self.to_bytes())
     }
 
+    /**
+     * Removes every OP_CODESEPARATOR opcode, push data containing 0xab is left untouched.
+     */
     pub fn remove_codeseparators(&mut self) {
-        self.0 = self.0.clone().into_iter().filter(|x| *x != OpCodes::OP_CODESEPARATOR.to_u8().unwrap()).collect();
+        let offsets = self.codeseparator_offsets();
+        self.0 = self.0.iter().enumerate().filter(|(i, _)| offsets.binary_search(i).is_err()).map(|(_, x)| *x).collect();
     }
 }
 
@@ -239,6 +300,14 @@ impl Script {
     pub fn from_asm_string(asm_string: &str) -> Result<Script, BSVErrors> {
         Script::from_asm_string_impl(asm_string)
     }
+
+    /**
+     * The part of the script after the OP_CODESEPARATOR at codeseparator_index (counting from 0), or the whole script if None.
+     * This is the scriptCode signed by a checksig executed after that OP_CODESEPARATOR.
+     */
+    pub fn get_subscript(&self, codeseparator_index: Option<usize>) -> Result<Script, BSVErrors> {
+        Script::get_subscript_impl(self, codeseparator_index)
+    }
 }
 
 /**
@@ -278,4 +347,16 @@ impl Script {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    /**
+     * The part of the script after the OP_CODESEPARATOR at codeseparator_index (counting from 0), or the whole script if None.
+     * This is the scriptCode signed by a checksig executed after that OP_CODESEPARATOR.
+     */
+    #[wasm_bindgen(js_name = getSubscript)]
+    pub fn get_subscript(&self, codeseparator_index: Option<usize>) -> Result<Script, JsValue> {
+        match Script::get_subscript_impl(self, codeseparator_index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
@@ -0,0 +1,244 @@
+use crate::{BSVErrors, PrivateKey};
+use getrandom::getrandom;
+use subtle::ConstantTimeEq;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+pub mod secret_share;
+pub use secret_share::*;
+
+/**
+ * Shamir Secret Sharing over GF(256).
+ * Each byte of the secret is split independently using a random polynomial of degree threshold - 1.
+ */
+#[wasm_bindgen]
+pub struct Shamir {}
+
+/**
+ * GF(256) arithmetic using the AES reduction polynomial x^8 + x^4 + x^3 + x + 1.
+ * Multiplication does not use lookup tables so it does not leak secret bytes through cache timing.
+ */
+fn gf256_mul(a: u8, b: u8) -> u8 {
+    let mut a = a;
+    let mut b = b;
+    let mut product = 0u8;
+
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+
+    product
+}
+
+/**
+ * a^254 == a^-1 in GF(256)
+ */
+fn gf256_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf256_mul(result, base);
+        }
+        base = gf256_mul(base, base);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+impl Shamir {
+    /**
+     * Splits the secret into `shares` shares, any `threshold` of which can recombine the secret.
+     */
+    pub(crate) fn split_impl(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<SecretShare>, BSVErrors> {
+        if secret.is_empty() {
+            return Err(BSVErrors::ShamirError("Cannot split an empty secret".into()));
+        }
+
+        // With a threshold of 1 every polynomial is constant and each share holds the secret in plaintext
+        if threshold < 2 || threshold > shares {
+            return Err(BSVErrors::ShamirError(format!(
+                "Threshold must be between 2 and the number of shares ({}), given {}",
+                shares, threshold
+            )));
+        }
+
+        let mut id = vec![0; SHARE_ID_LENGTH];
+        getrandom(&mut id)?;
+
+        // coefficients[i] holds the non-constant coefficients of the polynomial for secret[i]
+        let mut coefficients = Zeroizing::new(vec![0; secret.len() * (threshold as usize - 1)]);
+        getrandom(&mut coefficients)?;
+
+        let split_shares = (1..=shares)
+            .map(|x| {
+                let data = secret
+                    .iter()
+                    .enumerate()
+                    .map(|(i, secret_byte)| {
+                        let polynomial = &coefficients[i * (threshold as usize - 1)..(i + 1) * (threshold as usize - 1)];
+                        // Horner's method, highest degree coefficient first
+                        let y = polynomial.iter().rev().fold(0u8, |acc, coefficient| gf256_mul(acc, x) ^ coefficient);
+                        gf256_mul(y, x) ^ secret_byte
+                    })
+                    .collect();
+
+                SecretShare {
+                    threshold,
+                    index: x,
+                    id: id.clone(),
+                    data,
+                }
+            })
+            .collect();
+
+        Ok(split_shares)
+    }
+
+    /**
+     * Recombines the secret from at least `threshold` shares of the same split.
+     */
+    pub(crate) fn combine_impl(shares: &[SecretShare]) -> Result<Vec<u8>, BSVErrors> {
+        let first = shares.first().ok_or_else(|| BSVErrors::ShamirError("No shares were provided".into()))?;
+
+        for share in shares {
+            if share.id != first.id || share.threshold != first.threshold || share.data.len() != first.data.len() {
+                return Err(BSVErrors::ShamirError("Shares do not belong to the same secret".into()));
+            }
+
+            if share.index == 0 {
+                return Err(BSVErrors::ShamirError("Share index 0 is invalid".into()));
+            }
+        }
+
+        let mut unique_shares: Vec<&SecretShare> = vec![];
+        for share in shares {
+            match unique_shares.iter().find(|x| x.index == share.index) {
+                Some(existing) if !bool::from(existing.data.ct_eq(&share.data)) => {
+                    return Err(BSVErrors::ShamirError(format!("Conflicting shares provided for index {}", share.index)));
+                }
+                Some(_) => continue,
+                None => unique_shares.push(share),
+            }
+        }
+
+        if unique_shares.len() < first.threshold as usize {
+            return Err(BSVErrors::ShamirError(format!(
+                "At least {} unique shares are required, given {}",
+                first.threshold,
+                unique_shares.len()
+            )));
+        }
+
+        let points = &unique_shares[0..first.threshold as usize];
+
+        // Lagrange basis polynomials evaluated at x = 0
+        let basis: Vec<u8> = points
+            .iter()
+            .map(|share_i| {
+                points
+                    .iter()
+                    .filter(|share_j| share_j.index != share_i.index)
+                    .fold(1u8, |acc, share_j| gf256_mul(acc, gf256_mul(share_j.index, gf256_inv(share_j.index ^ share_i.index))))
+            })
+            .collect();
+
+        let secret = (0..first.data.len())
+            .map(|byte_index| points.iter().zip(basis.iter()).fold(0u8, |acc, (share, l)| acc ^ gf256_mul(share.data[byte_index], *l)))
+            .collect();
+
+        Ok(secret)
+    }
+
+    pub(crate) fn split_private_key_impl(private_key: &PrivateKey, threshold: u8, shares: u8) -> Result<Vec<SecretShare>, BSVErrors> {
+        Shamir::split_impl(&Zeroizing::new(private_key.to_bytes()), threshold, shares)
+    }
+
+    pub(crate) fn combine_private_key_impl(shares: &[SecretShare]) -> Result<PrivateKey, BSVErrors> {
+        let bytes = Zeroizing::new(Shamir::combine_impl(shares)?);
+        PrivateKey::from_bytes_impl(&bytes)
+    }
+
+    fn split_to_hex(shares: Vec<SecretShare>) -> Box<[JsValue]> {
+        shares.iter().map(|x| JsValue::from_str(&x.to_hex())).collect::<Vec<JsValue>>().into_boxed_slice()
+    }
+
+    fn shares_from_hex(shares: &[JsValue]) -> Result<Vec<SecretShare>, BSVErrors> {
+        shares
+            .iter()
+            .map(|x| match x.as_string() {
+                Some(hex) => SecretShare::from_hex_impl(&hex),
+                None => Err(BSVErrors::ShamirError("Shares must be given as hex strings".into())),
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl Shamir {
+    /**
+     * Splits the secret (eg. a BIP32 seed) into hex encoded shares.
+     */
+    pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Box<[JsValue]>, JsValue> {
+        match Shamir::split_impl(secret, threshold, shares) {
+            Ok(v) => Ok(Shamir::split_to_hex(v)),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Recombines the secret from an array of hex encoded shares.
+     */
+    pub fn combine(shares: Box<[JsValue]>) -> Result<Vec<u8>, JsValue> {
+        match Shamir::shares_from_hex(&shares).and_then(|x| Shamir::combine_impl(&x)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = splitPrivateKey)]
+    pub fn split_private_key(private_key: &PrivateKey, threshold: u8, shares: u8) -> Result<Box<[JsValue]>, JsValue> {
+        match Shamir::split_private_key_impl(private_key, threshold, shares) {
+            Ok(v) => Ok(Shamir::split_to_hex(v)),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = combinePrivateKey)]
+    pub fn combine_private_key(shares: Box<[JsValue]>) -> Result<PrivateKey, JsValue> {
+        match Shamir::shares_from_hex(&shares).and_then(|x| Shamir::combine_private_key_impl(&x)) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Shamir {
+    /**
+     * Splits the secret (eg. a BIP32 seed) into shares.
+     */
+    pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<SecretShare>, BSVErrors> {
+        Shamir::split_impl(secret, threshold, shares)
+    }
+
+    pub fn combine(shares: &[SecretShare]) -> Result<Vec<u8>, BSVErrors> {
+        Shamir::combine_impl(shares)
+    }
+
+    pub fn split_private_key(private_key: &PrivateKey, threshold: u8, shares: u8) -> Result<Vec<SecretShare>, BSVErrors> {
+        Shamir::split_private_key_impl(private_key, threshold, shares)
+    }
+
+    pub fn combine_private_key(shares: &[SecretShare]) -> Result<PrivateKey, BSVErrors> {
+        Shamir::combine_private_key_impl(shares)
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{BSVErrors, Hash};
+use subtle::{Choice, ConstantTimeEq};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroize;
+
+/**
+ * Length of the random identifier shared by every share of a single split.
+ */
+pub(crate) const SHARE_ID_LENGTH: usize = 4;
+
+/**
+ * threshold (1) + index (1) + id (4) + checksum (4)
+ */
+const SHARE_OVERHEAD_LENGTH: usize = 2 + SHARE_ID_LENGTH + 4;
+
+/**
+ * A single share of a secret.
+ * Serialised as: threshold (1 byte) | index (1 byte) | id (4 bytes) | data | checksum (first 4 bytes of SHA256d)
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SecretShare {
+    pub(crate) threshold: u8,
+    pub(crate) index: u8,
+    pub(crate) id: Vec<u8>,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Drop for SecretShare {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl ConstantTimeEq for SecretShare {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let metadata_eq = self.threshold == other.threshold && self.index == other.index && self.id == other.id;
+
+        self.data.ct_eq(&other.data) & Choice::from(metadata_eq as u8)
+    }
+}
+
+impl PartialEq for SecretShare {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SecretShare {}
+
+impl SecretShare {
+    pub(crate) fn from_bytes_impl(bytes: &[u8]) -> Result<SecretShare, BSVErrors> {
+        if bytes.len() <= SHARE_OVERHEAD_LENGTH {
+            return Err(BSVErrors::ShamirError(format!("Share is too short: {} bytes", bytes.len())));
+        }
+
+        let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+        let expected_checksum = &Hash::sha_256d(payload).to_bytes()[0..4];
+        if expected_checksum != checksum {
+            return Err(BSVErrors::ShamirError(format!(
+                "Invalid share checksum: expected {}, got {}",
+                hex::encode(expected_checksum),
+                hex::encode(checksum)
+            )));
+        }
+
+        let threshold = payload[0];
+        let index = payload[1];
+
+        if threshold < 2 {
+            return Err(BSVErrors::ShamirError("Share threshold must be at least 2".into()));
+        }
+
+        if index == 0 {
+            return Err(BSVErrors::ShamirError("Share index 0 is invalid".into()));
+        }
+
+        Ok(SecretShare {
+            threshold,
+            index,
+            id: payload[2..2 + SHARE_ID_LENGTH].to_vec(),
+            data: payload[2 + SHARE_ID_LENGTH..].to_vec(),
+        })
+    }
+
+    pub(crate) fn from_hex_impl(hex_str: &str) -> Result<SecretShare, BSVErrors> {
+        let bytes = hex::decode(hex_str)?;
+        SecretShare::from_bytes_impl(&bytes)
+    }
+}
+
+#[wasm_bindgen]
+impl SecretShare {
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.threshold, self.index];
+        bytes.extend_from_slice(&self.id);
+        bytes.extend_from_slice(&self.data);
+
+        let checksum = Hash::sha_256d(&bytes).to_bytes();
+        bytes.extend_from_slice(&checksum[0..4]);
+        bytes
+    }
+
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /**
+     * Number of shares required to recombine the secret.
+     */
+    #[wasm_bindgen(js_name = getThreshold)]
+    pub fn get_threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /**
+     * The x coordinate of this share, between 1 and 255.
+     */
+    #[wasm_bindgen(js_name = getIndex)]
+    pub fn get_index(&self) -> u8 {
+        self.index
+    }
+
+    /**
+     * Random identifier shared by all shares from the same split.
+     */
+    #[wasm_bindgen(js_name = getId)]
+    pub fn get_id(&self) -> Vec<u8> {
+        self.id.clone()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl SecretShare {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<SecretShare, JsValue> {
+        match SecretShare::from_bytes_impl(bytes) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex_str: &str) -> Result<SecretShare, JsValue> {
+        match SecretShare::from_hex_impl(hex_str) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SecretShare {
+    pub fn from_bytes(bytes: &[u8]) -> Result<SecretShare, BSVErrors> {
+        SecretShare::from_bytes_impl(bytes)
+    }
+
+    pub fn from_hex(hex_str: &str) -> Result<SecretShare, BSVErrors> {
+        SecretShare::from_hex_impl(hex_str)
+    }
+}
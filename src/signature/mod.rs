@@ -5,10 +5,14 @@ use elliptic_curve::sec1::*;
 use k256::{
     ecdsa::Signature as SecpSignature,
     ecdsa::{recoverable, signature::Verifier, VerifyingKey},
-    EncodedPoint, FieldBytes, Scalar,
+    EncodedPoint, FieldBytes,
 };
 use wasm_bindgen::{convert::OptionIntoWasmAbi, prelude::*, throw_str};
 
+const COMPACT_SIGNATURE_LENGTH: usize = 65;
+const COMPACT_HEADER_BASE: u8 = 27;
+const COMPACT_COMPRESSED_FLAG: u8 = 4;
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Signature {
@@ -64,20 +68,114 @@ impl Signature {
         Ok(pub_key)
     }
 
-    pub(crate) fn from_compact_impl(compact_bytes: &[u8]) -> Result<Signature, BSVErrors> {
+    /**
+     * Parses a 65 byte compact signature (header | r | s).
+     * Header is 27 + recovery id, plus 4 if the signing key's public key is compressed.
+     *
+     * Returns the signature and whether the public key is compressed.
+     */
+    pub(crate) fn from_compact_with_compression_impl(compact_bytes: &[u8]) -> Result<(Signature, bool), BSVErrors> {
+        if compact_bytes.len() != COMPACT_SIGNATURE_LENGTH {
+            return Err(BSVErrors::InvalidCompactSignature(format!("Expected {} bytes, got {}", COMPACT_SIGNATURE_LENGTH, compact_bytes.len())));
+        }
+
         // 27-30: P2PKH uncompressed
         // 31-34: P2PKH compressed
-        let i = match compact_bytes[0] - 27 {
-            x if x > 4 => x - 4,
-            x => x,
-        };
+        let header = compact_bytes[0];
+        if !(COMPACT_HEADER_BASE..COMPACT_HEADER_BASE + 8).contains(&header) {
+            return Err(BSVErrors::InvalidCompactSignature(format!("Header byte {} must be between 27 and 34", header)));
+        }
 
-        let r = Scalar::from_bytes_reduced(FieldBytes::from_slice(&compact_bytes[1..33]));
-        let s = Scalar::from_bytes_reduced(FieldBytes::from_slice(&compact_bytes[33..65]));
+        let recovery_i = (header - COMPACT_HEADER_BASE) & 3;
+        let is_compressed = header >= COMPACT_HEADER_BASE + COMPACT_COMPRESSED_FLAG;
 
+        let r = FieldBytes::clone_from_slice(&compact_bytes[1..33]);
+        let s = FieldBytes::clone_from_slice(&compact_bytes[33..65]);
         let sig = SecpSignature::from_scalars(r, s)?;
 
-        Ok(Signature { sig, recovery_i: i })
+        Ok((Signature { sig, recovery_i }, is_compressed))
+    }
+
+    pub(crate) fn from_compact_impl(compact_bytes: &[u8]) -> Result<Signature, BSVErrors> {
+        Ok(Signature::from_compact_with_compression_impl(compact_bytes)?.0)
+    }
+
+    pub(crate) fn from_compact_base64_impl(compact_base64: &str) -> Result<Signature, BSVErrors> {
+        let bytes = base64::decode(compact_base64)?;
+        Signature::from_compact_impl(&bytes)
+    }
+
+    /**
+     * Checks the DER encoding against the strict rules from BIP66 (without the trailing sighash byte).
+     * Format: 0x30 [total-length] 0x02 [R-length] [R] 0x02 [S-length] [S]
+     */
+    pub(crate) fn check_strict_der(der_bytes: &[u8]) -> Result<(), BSVErrors> {
+        let rule = |msg: &str| Err(BSVErrors::NonStrictDERSignature(msg.into()));
+        let len = der_bytes.len();
+
+        if len < 8 {
+            return rule("signature is too short");
+        }
+        if len > 72 {
+            return rule("signature is too long");
+        }
+        if der_bytes[0] != 0x30 {
+            return rule("signature is not a compound structure");
+        }
+        if der_bytes[1] as usize != len - 2 {
+            return rule("total length does not match the signature length");
+        }
+
+        let len_r = der_bytes[3] as usize;
+        if 5 + len_r >= len {
+            return rule("R length exceeds the signature length");
+        }
+
+        let len_s = der_bytes[5 + len_r] as usize;
+        if len_r + len_s + 6 != len {
+            return rule("R and S lengths do not match the signature length");
+        }
+
+        if der_bytes[2] != 0x02 {
+            return rule("R is not an integer");
+        }
+        if len_r == 0 {
+            return rule("R has zero length");
+        }
+        if der_bytes[4] & 0x80 != 0 {
+            return rule("R is negative");
+        }
+        if len_r > 1 && der_bytes[4] == 0x00 && der_bytes[5] & 0x80 == 0 {
+            return rule("R has excess zero padding");
+        }
+
+        if der_bytes[len_r + 4] != 0x02 {
+            return rule("S is not an integer");
+        }
+        if len_s == 0 {
+            return rule("S has zero length");
+        }
+        if der_bytes[len_r + 6] & 0x80 != 0 {
+            return rule("S is negative");
+        }
+        if len_s > 1 && der_bytes[len_r + 6] == 0x00 && der_bytes[len_r + 7] & 0x80 == 0 {
+            return rule("S has excess zero padding");
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Replaces a high S with n - S. Negating S negates R for public key recovery, so the recovery id parity flips too.
+     */
+    pub(crate) fn normalise_s_impl(&self) -> Result<Signature, BSVErrors> {
+        let mut sig = self.sig;
+        let was_high = sig.normalize_s()?;
+
+        Ok(Signature {
+            sig,
+            recovery_i: self.recovery_i ^ was_high as u8,
+        })
     }
 }
 
@@ -97,10 +195,25 @@ impl Signature {
         bytes.as_bytes().to_vec()
     }
 
+    /**
+     * 65 byte compact signature as used by Bitcoin Signed Messages: header | r | s.
+     * The header encodes the recovery id and that the signer's public key is compressed.
+     */
     #[wasm_bindgen(js_name = toCompactBytes)]
     pub fn to_compact_bytes(&self) -> Vec<u8> {
-        // Need to handle compression?
-        let mut compact_buf = vec![self.recovery_i + 27 + 4];
+        self.to_compact_bytes_with_compression(true)
+    }
+
+    /**
+     * 65 byte compact signature with the header flagging whether the signer's public key is compressed.
+     */
+    #[wasm_bindgen(js_name = toCompactBytesWithCompression)]
+    pub fn to_compact_bytes_with_compression(&self, compressed: bool) -> Vec<u8> {
+        let compressed_flag = match compressed {
+            true => COMPACT_COMPRESSED_FLAG,
+            false => 0,
+        };
+        let mut compact_buf = vec![COMPACT_HEADER_BASE + self.recovery_i + compressed_flag];
 
         let r_bytes = &*self.sig.r().to_bytes();
         compact_buf.extend_from_slice(r_bytes);
@@ -111,6 +224,30 @@ impl Signature {
         compact_buf
     }
 
+    /**
+     * Base64 of the compact signature, the format produced by Electrum, bsv.js and other wallets for signed messages.
+     */
+    #[wasm_bindgen(js_name = toCompactBase64)]
+    pub fn to_compact_base64(&self) -> String {
+        base64::encode(&self.to_compact_bytes())
+    }
+
+    /**
+     * Base64 of the compact signature, for a signer whose public key may be uncompressed.
+     */
+    #[wasm_bindgen(js_name = toCompactBase64WithCompression)]
+    pub fn to_compact_base64_with_compression(&self, compressed: bool) -> String {
+        base64::encode(&self.to_compact_bytes_with_compression(compressed))
+    }
+
+    /**
+     * S is at most half the curve order (BIP62), required for standard BSV transactions.
+     */
+    #[wasm_bindgen(js_name = isLowS)]
+    pub fn is_low_s(&self) -> bool {
+        !bool::from(self.sig.s().is_high())
+    }
+
     #[wasm_bindgen(js_name = verifyMessage)]
     pub fn verify_message(&self, message: &[u8], pub_key: &PublicKey) -> bool {
         ECDSA::verify_digest_impl(message, pub_key, self, SigningHash::Sha256).is_ok()
@@ -144,6 +281,25 @@ impl Signature {
         }
     }
 
+    #[wasm_bindgen(js_name = fromCompactBase64)]
+    pub fn from_compact_base64(compact_base64: &str) -> Result<Signature, JsValue> {
+        match Signature::from_compact_base64_impl(compact_base64) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns the low S form of this signature, unchanged if S is already low.
+     */
+    #[wasm_bindgen(js_name = normaliseS)]
+    pub fn normalise_s(&self) -> Result<Signature, JsValue> {
+        match self.normalise_s_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
     #[wasm_bindgen(js_name = recoverPublicKey)]
     pub fn recover_public_key(&self, message: &[u8], hash_algo: SigningHash) -> Result<PublicKey, JsValue> {
         match Signature::get_public_key(&self, &message, hash_algo) {
@@ -172,6 +328,17 @@ impl Signature {
         Signature::from_compact_impl(compact_bytes)
     }
 
+    pub fn from_compact_base64(compact_base64: &str) -> Result<Signature, BSVErrors> {
+        Signature::from_compact_base64_impl(compact_base64)
+    }
+
+    /**
+     * Returns the low S form of this signature, unchanged if S is already low.
+     */
+    pub fn normalise_s(&self) -> Result<Signature, BSVErrors> {
+        self.normalise_s_impl()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn recover_public_key(&self, message: &[u8], hash_algo: SigningHash) -> Result<PublicKey, BSVErrors> {
         Signature::get_public_key(self, message, hash_algo)
@@ -11,12 +11,18 @@ use thiserror::*;
 use wasm_bindgen::{prelude::*, throw_str, JsValue};
 
 mod match_criteria;
+mod push_tx;
 mod sighash;
+mod sighash_flags;
+mod sighash_preimage;
 mod txin;
 mod txout;
 
 pub use match_criteria::*;
+pub use push_tx::*;
 pub use sighash::*;
+pub use sighash_flags::*;
+pub use sighash_preimage::*;
 pub use txin::*;
 pub use txout::*;
 
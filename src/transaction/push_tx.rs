@@ -0,0 +1,133 @@
+use crate::BSVErrors;
+use crate::ECDSA;
+
+use crate::{transaction::*, PrivateKey, Script, SigningHash};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::throw_str;
+
+/**
+ * Maximum number of transaction tweaks before giving up on a low S signature. Each attempt succeeds with ~50% probability.
+ */
+const MAX_PUSH_TX_ATTEMPTS: u32 = 256;
+
+/**
+ * The transaction field tweaked between OP_PUSH_TX signing attempts.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PushTxMalleation {
+    /**
+     * Increment nLocktime. Only enforced by the network when an input has nSequence below 0xffffffff.
+     */
+    NLocktime,
+    /**
+     * Decrement the nSequence of the input being signed.
+     */
+    NSequence,
+}
+
+impl Transaction {
+    /**
+     * OP_PUSH_TX: signs the sighash preimage with a well-known private key and nonce so the locking script can recompute the signature
+     * from the preimage and check it with OP_CHECKSIG, proving the preimage belongs to the spending transaction.
+     *
+     * The script cannot normalise S, so instead the transaction is tweaked (see PushTxMalleation) and re-signed until S is low.
+     * A low S keeps both R (for k = 1) and S within 32 bytes, so the DER signature has a fixed, short length.
+     * The tweaked nLocktime/nSequence are left on the transaction.
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sign_push_tx_impl(
+        &mut self,
+        priv_key: &PrivateKey,
+        k: &PrivateKey,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        malleation: PushTxMalleation,
+    ) -> Result<SighashSignature, BSVErrors> {
+        if n_tx_in >= self.inputs.len() {
+            return Err(BSVErrors::OutOfBounds(format!("Could not get TxIn at index {}", n_tx_in)));
+        }
+
+        for _ in 0..MAX_PUSH_TX_ATTEMPTS {
+            let buffer = self.sighash_preimage_impl(n_tx_in, sighash, unsigned_script, value)?;
+            let signature = ECDSA::sign_with_k_unnormalised_impl(priv_key, k, &buffer, SigningHash::Sha256d)?;
+
+            if signature.is_low_s() {
+                return Ok(SighashSignature {
+                    signature,
+                    sighash_type: sighash,
+                    sighash_buffer: buffer,
+                });
+            }
+
+            match malleation {
+                PushTxMalleation::NLocktime => {
+                    self.n_locktime = self
+                        .n_locktime
+                        .checked_add(1)
+                        .ok_or_else(|| BSVErrors::PushTxSigning("nLocktime cannot be incremented any further".into()))?;
+                }
+                PushTxMalleation::NSequence => {
+                    let input = &mut self.inputs[n_tx_in];
+                    input.sequence = input
+                        .sequence
+                        .checked_sub(1)
+                        .ok_or_else(|| BSVErrors::PushTxSigning("nSequence cannot be decremented any further".into()))?;
+                    // hashSequence commits to every nSequence
+                    self.hash_cache = HashCache::new();
+                }
+            }
+        }
+
+        Err(BSVErrors::PushTxSigning(format!("No low S signature found after {} attempts", MAX_PUSH_TX_ATTEMPTS)))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl Transaction {
+    /**
+     * Produces an OP_PUSH_TX signature with the given well-known private key and nonce k,
+     * tweaking nLocktime or the input's nSequence until the signature has a low S.
+     */
+    #[wasm_bindgen(js_name = signPushTx)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_push_tx(
+        &mut self,
+        priv_key: &PrivateKey,
+        k: &PrivateKey,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        malleation: PushTxMalleation,
+    ) -> Result<SighashSignature, JsValue> {
+        match Transaction::sign_push_tx_impl(self, priv_key, k, sighash, n_tx_in, unsigned_script, value, malleation) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Transaction {
+    /**
+     * Produces an OP_PUSH_TX signature with the given well-known private key and nonce k,
+     * tweaking nLocktime or the input's nSequence until the signature has a low S.
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_push_tx(
+        &mut self,
+        priv_key: &PrivateKey,
+        k: &PrivateKey,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        malleation: PushTxMalleation,
+    ) -> Result<SighashSignature, BSVErrors> {
+        Transaction::sign_push_tx_impl(self, priv_key, k, sighash, n_tx_in, unsigned_script, value, malleation)
+    }
+}
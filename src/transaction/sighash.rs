@@ -3,12 +3,16 @@ use crate::ECDSA;
 use std::convert::TryFrom;
 use std::io::{Cursor, Write};
 
-use crate::{transaction::*, Hash, PrivateKey, PublicKey, Script, Signature, VarInt};
+#[cfg(target_arch = "wasm32")]
+use crate::JsSigner;
+use crate::{transaction::*, Hash, PrivateKey, PublicKey, Script, Signature, Signer, VarInt};
 use byteorder::{LittleEndian, WriteBytesExt};
 use num_traits::{FromPrimitive, ToPrimitive};
 use strum_macros::EnumString;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::throw_str;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_futures::future_to_promise;
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, FromPrimitive, ToPrimitive, EnumString)]
@@ -103,13 +107,40 @@ impl HashCache {
     }
 }
 
+/**
+ * uint256(1) as serialised by the original client, which signs it as-is when SIGHASH_SINGLE has no matching output.
+ */
+const SIGHASH_SINGLE_BUG_DIGEST: [u8; 32] = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
 impl Transaction {
     /**
      * Calculates the SIGHASH buffer and then signs it
      */
     pub(crate) fn sign_impl(&mut self, priv_key: &PrivateKey, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<SighashSignature, BSVErrors> {
-        let buffer = self.sighash_preimage_impl(n_tx_in, sighash, unsigned_script, value)?;
-        let signature = ECDSA::sign_with_deterministic_k_impl(priv_key, &buffer, crate::SigningHash::Sha256d, true)?;
+        self.sign_with_codeseparator_impl(priv_key, sighash, n_tx_in, unsigned_script, value, None)
+    }
+
+    /**
+     * Calculates the SIGHASH buffer and signs it with any Signer, eg. an external or hardware-backed key
+     */
+    pub(crate) fn sign_with_signer_impl(&mut self, signer: &dyn Signer, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<SighashSignature, BSVErrors> {
+        self.sign_with_codeseparator_impl(signer, sighash, n_tx_in, unsigned_script, value, None)
+    }
+
+    /**
+     * Calculates the SIGHASH buffer for a checksig executed after the given OP_CODESEPARATOR and then signs it
+     */
+    pub(crate) fn sign_with_codeseparator_impl(
+        &mut self,
+        signer: &dyn Signer,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        codeseparator_index: Option<usize>,
+    ) -> Result<SighashSignature, BSVErrors> {
+        let buffer = self.sighash_preimage_with_codeseparator_impl(n_tx_in, sighash.into(), unsigned_script, value, codeseparator_index)?;
+        let signature = ECDSA::sign_with_signer_impl(signer, &buffer, crate::SigningHash::Sha256d, true)?;
 
         Ok(SighashSignature {
             signature,
@@ -122,17 +153,80 @@ impl Transaction {
      * Calculates the SIGHASH Buffer to be signed
      */
     pub(crate) fn sighash_preimage_impl(&mut self, n_tx_in: usize, sighash: SigHash, unsigned_script: &Script, value: u64) -> Result<Vec<u8>, BSVErrors> {
-        // If uses any of the FORK_ID sighash variants
-        // Gross, fix this. Maybe a nice method on SigHash enum to check if contains another SigHash type
-        match sighash {
-            SigHash::Input | SigHash::InputOutput | SigHash::InputOutputs | SigHash::Inputs | SigHash::InputsOutput | SigHash::InputsOutputs => {
-                self.sighash_bip143(n_tx_in, sighash, unsigned_script, value)
-            }
-            _ => self.sighash_legacy(n_tx_in, sighash, unsigned_script),
+        self.sighash_preimage_with_codeseparator_impl(n_tx_in, sighash.into(), unsigned_script, value, None)
+    }
+
+    /**
+     * Calculates the SIGHASH Buffer for a checksig executed after the OP_CODESEPARATOR at codeseparator_index (counting from 0).
+     * The scriptCode is the part of unsigned_script following that OP_CODESEPARATOR, or all of it if None.
+     */
+    pub(crate) fn sighash_preimage_with_codeseparator_impl(
+        &mut self,
+        n_tx_in: usize,
+        sighash: SighashFlags,
+        unsigned_script: &Script,
+        value: u64,
+        codeseparator_index: Option<usize>,
+    ) -> Result<Vec<u8>, BSVErrors> {
+        self.sighash_preimage_for_hash_type(n_tx_in, sighash.to_u8() as u32, unsigned_script, value, codeseparator_index, false, true)
+    }
+
+    /**
+     * Calculates the SIGHASH Buffer for a 32 bit nHashType. Only the low byte selects the flags but all 4 bytes are signed.
+     * With single_without_output, FORKID SIGHASH_SINGLE without a matching output signs zero hashOutputs like the node instead of erroring.
+     * Without forkid_enabled the FORKID bit is ignored and the original algorithm is used, like a node before the fork.
+     */
+    #[allow(clippy::too_many_arguments)]
+    fn sighash_preimage_for_hash_type(
+        &mut self,
+        n_tx_in: usize,
+        hash_type: u32,
+        unsigned_script: &Script,
+        value: u64,
+        codeseparator_index: Option<usize>,
+        single_without_output: bool,
+        forkid_enabled: bool,
+    ) -> Result<Vec<u8>, BSVErrors> {
+        let script_code = unsigned_script.get_subscript_impl(codeseparator_index)?;
+
+        match forkid_enabled && SighashFlags(hash_type as u8).has_forkid() {
+            true => self.sighash_bip143(n_tx_in, hash_type, &script_code, value, single_without_output),
+            false => self.sighash_legacy(n_tx_in, hash_type, &script_code),
+        }
+    }
+
+    /**
+     * Calculates the digest that is signed, SHA256d of the SIGHASH buffer, in the byte order it is signed in.
+     * Test vectors and block explorers usually display it reversed.
+     *
+     * When legacy_single_bug is set, SIGHASH_SINGLE signing an input without a matching output gets the consensus digest
+     * instead of an error: 1 for legacy (non FORKID) like the original client, or zero hashOutputs for FORKID.
+     * Only needed to validate historical transactions, anyone can reuse a legacy signature of 1 to spend the input.
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sighash_digest_impl(
+        &mut self,
+        n_tx_in: usize,
+        hash_type: u32,
+        unsigned_script: &Script,
+        value: u64,
+        legacy_single_bug: bool,
+        codeseparator_index: Option<usize>,
+        forkid_enabled: bool,
+    ) -> Result<Vec<u8>, BSVErrors> {
+        let sighash = SighashFlags(hash_type as u8);
+        let is_legacy_single = !(forkid_enabled && sighash.has_forkid()) && sighash.is_single();
+
+        if legacy_single_bug && is_legacy_single && n_tx_in < self.inputs.len() && n_tx_in >= self.outputs.len() {
+            return Ok(SIGHASH_SINGLE_BUG_DIGEST.to_vec());
         }
+
+        let buffer = self.sighash_preimage_for_hash_type(n_tx_in, hash_type, unsigned_script, value, codeseparator_index, legacy_single_bug, forkid_enabled)?;
+        Ok(Hash::sha_256d(&buffer).to_bytes())
     }
 
-    pub(crate) fn sighash_legacy(&mut self, n_tx_in: usize, sighash: SigHash, unsigned_script: &Script) -> Result<Vec<u8>, BSVErrors> {
+    pub(crate) fn sighash_legacy(&mut self, n_tx_in: usize, hash_type: u32, unsigned_script: &Script) -> Result<Vec<u8>, BSVErrors> {
+        let sighash = SighashFlags(hash_type as u8);
         let mut tx = self.clone();
         let mut script = unsigned_script.clone();
         script.remove_codeseparators();
@@ -144,66 +238,52 @@ impl Transaction {
         prev_txin.set_script(&script);
         tx.set_input(n_tx_in, &prev_txin);
 
-        match sighash {
-            SigHash::SINGLE | SigHash::Legacy_InputOutput => {
-                // Not supporting the SIGHASH_SINGLE bug. Sue me craig.
-                // // This if statement is needed because of Consensus SIGHASH_SINGLE bug
-                // // https://bitcoinfiles.org/t/9a3a165cc7881bb2e37567dec5eaab64568a889e83e6b850b42f347e1d96a555
-                // if n_tx_in >= tx.outputs.len() {
-                //   return Ok(hex::decode("0000000000000000000000000000000000000000000000000000000000000001").map_err(|e| anyhow!(e))?)
-                // }
-
-                let txout = tx.get_output(n_tx_in).ok_or_else(|| BSVErrors::OutOfBounds(format!("Could not get TxOut at index {}", n_tx_in)))?;
-                tx.outputs = vec![txout];
-
-                for i in 0..tx.outputs.len() {
-                    if i < n_tx_in {
-                        tx.set_output(i, &TxOut::new(0xffffffffffffffff, &Script::default()));
-                    }
-                }
-
-                for i in 0..tx.inputs.len() {
-                    if i == n_tx_in {
-                        continue;
-                    }
+        if sighash.is_single() {
+            // There is no preimage for the SIGHASH_SINGLE bug, see Transaction::sighash_digest
+            let txout = tx.get_output(n_tx_in).ok_or_else(|| {
+                BSVErrors::OutOfBounds(format!(
+                    "Could not get TxOut at index {}, use sighash_digest with legacy_single_bug to get the consensus SIGHASH_SINGLE bug digest",
+                    n_tx_in
+                ))
+            })?;
+
+            // Outputs before the signed one are blanked (value -1, empty script), later ones are dropped
+            tx.outputs = vec![TxOut::new(0xffffffffffffffff, &Script::default()); n_tx_in];
+            tx.outputs.push(txout);
+        } else if sighash.is_none() {
+            tx.outputs.clear();
+        }
 
-                    tx.inputs[i].set_sequence(0x00000000);
+        // Other inputs' nSequence is not signed when the outputs they could be paired with are not
+        if sighash.is_single() || sighash.is_none() {
+            for i in 0..tx.inputs.len() {
+                if i == n_tx_in {
+                    continue;
                 }
-            }
-
-            SigHash::NONE | SigHash::Legacy_Input => {
-                tx.outputs.clear();
 
-                for i in 0..tx.inputs.len() {
-                    if i == n_tx_in {
-                        continue;
-                    }
-
-                    tx.inputs[i].set_sequence(0x00000000);
-                }
+                tx.inputs[i].set_sequence(0x00000000);
             }
-            _ => {}
         }
 
-        if sighash.ge(&SigHash::ANYONECANPAY) {
+        if sighash.is_anyonecanpay() {
             let input = tx.inputs[n_tx_in].clone();
             tx.inputs = vec![];
             tx.add_input(&input);
         }
 
         let mut buffer = tx.to_bytes_impl()?;
-        let sighash_i32 = sighash.to_i32().ok_or_else(|| BSVErrors::FromSighash(format!("Cannot convert SigHash {:?} into i32", sighash)))?;
-        buffer.write_i32::<LittleEndian>(sighash_i32)?;
+        buffer.write_u32::<LittleEndian>(hash_type)?;
 
         Ok(buffer)
     }
 
-    pub(crate) fn sighash_bip143(&mut self, n_tx_in: usize, sighash: SigHash, unsigned_script: &Script, value: u64) -> Result<Vec<u8>, BSVErrors> {
+    pub(crate) fn sighash_bip143(&mut self, n_tx_in: usize, hash_type: u32, unsigned_script: &Script, value: u64, single_without_output: bool) -> Result<Vec<u8>, BSVErrors> {
+        let sighash = SighashFlags(hash_type as u8);
         let mut buffer: Vec<u8> = vec![];
 
         let input = self.get_input(n_tx_in).ok_or_else(|| BSVErrors::OutOfBounds(format!("Could not get TxIn at index {}", n_tx_in)))?;
 
-        let hashed_outputs = self.hash_outputs(sighash, n_tx_in)?;
+        let hashed_outputs = self.hash_outputs(sighash, n_tx_in, single_without_output)?;
 
         buffer.write_u32::<LittleEndian>(self.version)?;
         buffer.write_all(&self.hash_inputs(sighash))?;
@@ -216,62 +296,74 @@ impl Transaction {
         buffer.write_all(&hashed_outputs)?;
         buffer.write_u32::<LittleEndian>(self.n_locktime)?;
 
-        let sighash_u32 = sighash.to_u32().ok_or_else(|| BSVErrors::FromSighash(format!("Cannot convert SigHash {:?} into u32", sighash)))?;
-        buffer.write_u32::<LittleEndian>(sighash_u32)?;
+        buffer.write_u32::<LittleEndian>(hash_type)?;
 
         Ok(buffer)
     }
 
     /**
-     * Checks the hash cache to see if there already are hashed sequence, otherwise calculates the hash and adds it to the cache
+     * (hashSequence) Checks the hash cache to see if there already are hashed sequence, otherwise calculates the hash and adds it to the cache
+     *
+     * Logic:
+     * - If SigHash is not ANYONECANPAY, SINGLE or NONE, SHA256d all input nSequences
+     * - Else 32 bytes of zeroes
      */
-    fn hash_sequence(&mut self, sighash: SigHash) -> Vec<u8> {
+    fn hash_sequence(&mut self, sighash: SighashFlags) -> Vec<u8> {
+        if sighash.is_anyonecanpay() || sighash.is_single() || sighash.is_none() {
+            return [0; 32].to_vec();
+        }
+
         if let Some(x) = &self.hash_cache.hash_sequence {
             return x.to_bytes();
         }
 
-        match sighash {
-            SigHash::ALL | SigHash::InputsOutputs => {
-                let input_sequences: Vec<u8> = self.inputs.iter().flat_map(|x| x.get_sequence_as_bytes()).collect();
-                let hash = Hash::sha_256d(&input_sequences);
-                self.hash_cache.hash_sequence = Some(hash.clone());
-                hash.to_bytes()
-            }
-            _ => [0; 32].to_vec(),
-        }
+        let input_sequences: Vec<u8> = self.inputs.iter().flat_map(|x| x.get_sequence().to_le_bytes()).collect();
+        let hash = Hash::sha_256d(&input_sequences);
+        self.hash_cache.hash_sequence = Some(hash.clone());
+        hash.to_bytes()
     }
 
     /**
-     * Checks the hash cache to see if there already are hashed outputs, otherwise calculates the hash and adds it to the cache
+     * (hashOutputs) Checks the hash cache to see if there already are hashed outputs, otherwise calculates the hash and adds it to the cache
+     *
+     * Logic:
+     * - If SigHash is SINGLE, SHA256d the output at the same index as the input (not cached)
+     * - If SigHash is SINGLE without a matching output, an error, or 32 bytes of zeroes if single_without_output
+     * - If SigHash is NONE, 32 bytes of zeroes
+     * - Else SHA256d all outputs
      */
-    fn hash_outputs(&mut self, sighash: SigHash, n_tx_in: usize) -> Result<Vec<u8>, BSVErrors> {
-        if let Some(x) = &self.hash_cache.hash_outputs {
-            return Ok(x.to_bytes());
-        }
-
-        match sighash {
+    fn hash_outputs(&mut self, sighash: SighashFlags, n_tx_in: usize, single_without_output: bool) -> Result<Vec<u8>, BSVErrors> {
+        if sighash.is_single() {
             // Only sign the output at the same index as the given txin
-            SigHash::SINGLE | SigHash::InputOutput | SigHash::Legacy_InputOutput | SigHash::InputsOutput => {
-                if n_tx_in > self.get_noutputs() as usize {
-                    return Err(BSVErrors::OutOfBounds("Cannot sign with SIGHASH_SINGLE given input index greater than number of outputs".into()));
+            if n_tx_in >= self.get_noutputs() {
+                if single_without_output {
+                    return Ok([0; 32].to_vec());
                 }
 
-                let output = self.get_output(n_tx_in).ok_or_else(|| BSVErrors::OutOfBounds(format!("Could not find output at index {}", n_tx_in)))?;
-                let output_bytes = output.to_bytes_impl()?;
-                Ok(Hash::sha_256d(&output_bytes).to_bytes())
-            }
-            // Sign all outputs
-            SigHash::ALL | SigHash::InputOutputs | SigHash::Legacy_InputOutputs | SigHash::InputsOutputs => {
-                let mut txout_bytes = Vec::new();
-                for output in &self.outputs {
-                    txout_bytes.write_all(&output.to_bytes_impl()?)?;
-                }
-                let hash = Hash::sha_256d(&txout_bytes);
-                self.hash_cache.hash_outputs = Some(hash.clone());
-                Ok(hash.to_bytes())
+                return Err(BSVErrors::OutOfBounds("Cannot sign with SIGHASH_SINGLE given input index greater than number of outputs".into()));
             }
-            _ => Ok([0; 32].to_vec()),
+
+            let output = self.get_output(n_tx_in).ok_or_else(|| BSVErrors::OutOfBounds(format!("Could not find output at index {}", n_tx_in)))?;
+            let output_bytes = output.to_bytes_impl()?;
+            return Ok(Hash::sha_256d(&output_bytes).to_bytes());
+        }
+
+        if sighash.is_none() {
+            return Ok([0; 32].to_vec());
+        }
+
+        if let Some(x) = &self.hash_cache.hash_outputs {
+            return Ok(x.to_bytes());
+        }
+
+        // Sign all outputs
+        let mut txout_bytes = Vec::new();
+        for output in &self.outputs {
+            txout_bytes.write_all(&output.to_bytes_impl()?)?;
         }
+        let hash = Hash::sha_256d(&txout_bytes);
+        self.hash_cache.hash_outputs = Some(hash.clone());
+        Ok(hash.to_bytes())
     }
 
     /**
@@ -281,30 +373,34 @@ impl Transaction {
      * Logic:
      * - If SigHash does not contain ANYONECANPAY, SHA256d all input outpoints
      * - Else 32 bytes of zeroes
+     *
+     * Takes a SigHash or any SighashFlags.
      */
-    pub fn hash_inputs(&mut self, sighash: SigHash) -> Vec<u8> {
+    pub fn hash_inputs(&mut self, sighash: impl Into<SighashFlags>) -> Vec<u8> {
+        if sighash.into().is_anyonecanpay() {
+            return [0; 32].to_vec();
+        }
+
         if let Some(x) = &self.hash_cache.hash_inputs {
             return x.to_bytes();
         }
 
-        match sighash {
-            SigHash::ANYONECANPAY | SigHash::Input | SigHash::InputOutput | SigHash::Legacy_Input | SigHash::Legacy_InputOutput | SigHash::InputOutputs => [0; 32].to_vec(),
-            _ => {
-                let input_bytes: Vec<u8> = self.inputs.iter().flat_map(|txin| txin.get_outpoint_bytes(Some(true))).collect();
+        let input_bytes: Vec<u8> = self.inputs.iter().flat_map(|txin| txin.get_outpoint_bytes(Some(true))).collect();
 
-                let hash = Hash::sha_256d(&input_bytes);
-                self.hash_cache.hash_inputs = Some(hash.clone());
+        let hash = Hash::sha_256d(&input_bytes);
+        self.hash_cache.hash_inputs = Some(hash.clone());
 
-                hash.to_bytes()
-            }
-        }
+        hash.to_bytes()
     }
 }
 
 #[wasm_bindgen]
 impl Transaction {
+    /**
+     * High S signatures are non-standard, so they do not verify.
+     */
     pub fn verify(&self, pub_key: &PublicKey, sig: &SighashSignature) -> bool {
-        ECDSA::verify_digest_impl(&sig.sighash_buffer, pub_key, &sig.signature, crate::SigningHash::Sha256d).unwrap_or(false)
+        ECDSA::verify_digest_strict_impl(&sig.sighash_buffer, pub_key, &sig.signature, crate::SigningHash::Sha256d).unwrap_or(false)
     }
 }
 
@@ -314,9 +410,79 @@ impl Transaction {
         Transaction::sign_impl(self, priv_key, sighash, n_tx_in, unsigned_script, value)
     }
 
+    /**
+     * Signs with any Signer, eg. an external or hardware-backed key.
+     */
+    pub fn sign_with_signer(&mut self, signer: &dyn Signer, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<SighashSignature, BSVErrors> {
+        Transaction::sign_with_signer_impl(self, signer, sighash, n_tx_in, unsigned_script, value)
+    }
+
     pub fn sighash_preimage(&mut self, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<Vec<u8>, BSVErrors> {
         Transaction::sighash_preimage_impl(self, n_tx_in, sighash, unsigned_script, value)
     }
+
+    /**
+     * The digest that is signed, SHA256d of the SIGHASH buffer, for a checksig executed after the OP_CODESEPARATOR at codeseparator_index.
+     * Set legacy_single_bug to get the consensus digest for SIGHASH_SINGLE without a matching output instead of an error.
+     */
+    pub fn sighash_digest(
+        &mut self,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        legacy_single_bug: bool,
+        codeseparator_index: Option<usize>,
+    ) -> Result<Vec<u8>, BSVErrors> {
+        Transaction::sighash_digest_impl(self, n_tx_in, sighash as u32, unsigned_script, value, legacy_single_bug, codeseparator_index, true)
+    }
+
+    /**
+     * The digest that is signed for any 32 bit nHashType, as found in consensus test vectors.
+     * Clear forkid_enabled to get the original (pre fork) digest even when nHashType has the FORKID bit.
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn sighash_digest_with_hash_type(
+        &mut self,
+        hash_type: u32,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        legacy_single_bug: bool,
+        codeseparator_index: Option<usize>,
+        forkid_enabled: bool,
+    ) -> Result<Vec<u8>, BSVErrors> {
+        Transaction::sighash_digest_impl(self, n_tx_in, hash_type, unsigned_script, value, legacy_single_bug, codeseparator_index, forkid_enabled)
+    }
+
+    /**
+     * Signs for a checksig executed after the OP_CODESEPARATOR at codeseparator_index (counting from 0) in unsigned_script.
+     */
+    pub fn sign_with_codeseparator(
+        &mut self,
+        priv_key: &PrivateKey,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        codeseparator_index: Option<usize>,
+    ) -> Result<SighashSignature, BSVErrors> {
+        Transaction::sign_with_codeseparator_impl(self, priv_key, sighash, n_tx_in, unsigned_script, value, codeseparator_index)
+    }
+
+    /**
+     * The SIGHASH buffer for a checksig executed after the OP_CODESEPARATOR at codeseparator_index (counting from 0) in unsigned_script.
+     */
+    pub fn sighash_preimage_with_codeseparator(&mut self, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64, codeseparator_index: Option<usize>) -> Result<Vec<u8>, BSVErrors> {
+        Transaction::sighash_preimage_with_codeseparator_impl(self, n_tx_in, sighash.into(), unsigned_script, value, codeseparator_index)
+    }
+
+    /**
+     * The SIGHASH buffer for any sighash byte, including undefined types found in historical transactions.
+     */
+    pub fn sighash_preimage_with_flags(&mut self, sighash: &SighashFlags, n_tx_in: usize, unsigned_script: &Script, value: u64, codeseparator_index: Option<usize>) -> Result<Vec<u8>, BSVErrors> {
+        Transaction::sighash_preimage_with_codeseparator_impl(self, n_tx_in, *sighash, unsigned_script, value, codeseparator_index)
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -330,6 +496,44 @@ impl Transaction {
         }
     }
 
+    /**
+     * Signs with a JS signing callback, eg. a browser extension.
+     */
+    #[wasm_bindgen(js_name = signWithSigner)]
+    pub fn sign_with_signer(&mut self, signer: &JsSigner, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<SighashSignature, JsValue> {
+        match Transaction::sign_with_signer_impl(self, signer, sighash, n_tx_in, unsigned_script, value) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs with a JS signing callback that may return a Promise, eg. a hardware wallet.
+     * Returns a Promise of the SighashSignature.
+     */
+    #[wasm_bindgen(js_name = signWithSignerAsync)]
+    pub fn sign_with_signer_async(&mut self, signer: &JsSigner, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<js_sys::Promise, JsValue> {
+        let buffer = match Transaction::sighash_preimage_impl(self, n_tx_in, sighash, unsigned_script, value) {
+            Ok(v) => v,
+            Err(e) => throw_str(&e.to_string()),
+        };
+        let signer = signer.clone();
+
+        Ok(future_to_promise(async move {
+            let signature = ECDSA::sign_with_js_signer_async_impl(&signer, &buffer, crate::SigningHash::Sha256d).await;
+
+            match signature {
+                Ok(signature) => Ok(SighashSignature {
+                    signature,
+                    sighash_type: sighash,
+                    sighash_buffer: buffer,
+                }
+                .into()),
+                Err(e) => Err(JsValue::from_str(&e.to_string())),
+            }
+        }))
+    }
+
     #[wasm_bindgen(js_name = sighashPreimage)]
     pub fn sighash_preimage(&mut self, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64) -> Result<Vec<u8>, JsValue> {
         match Transaction::sighash_preimage_impl(self, n_tx_in, sighash, unsigned_script, value) {
@@ -337,6 +541,81 @@ impl Transaction {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    /**
+     * The digest that is signed, SHA256d of the SIGHASH buffer, for a checksig executed after the OP_CODESEPARATOR at codeseparator_index.
+     * Set legacy_single_bug to get the consensus digest for SIGHASH_SINGLE without a matching output instead of an error.
+     */
+    #[wasm_bindgen(js_name = sighashDigest)]
+    pub fn sighash_digest(&mut self, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64, legacy_single_bug: bool, codeseparator_index: Option<usize>) -> Result<Vec<u8>, JsValue> {
+        match Transaction::sighash_digest_impl(self, n_tx_in, sighash as u32, unsigned_script, value, legacy_single_bug, codeseparator_index, true) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * The digest that is signed for any 32 bit nHashType, as found in consensus test vectors.
+     * Clear forkid_enabled to get the original (pre fork) digest even when nHashType has the FORKID bit.
+     */
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = sighashDigestWithHashType)]
+    pub fn sighash_digest_with_hash_type(
+        &mut self,
+        hash_type: u32,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        legacy_single_bug: bool,
+        codeseparator_index: Option<usize>,
+        forkid_enabled: bool,
+    ) -> Result<Vec<u8>, JsValue> {
+        match Transaction::sighash_digest_impl(self, n_tx_in, hash_type, unsigned_script, value, legacy_single_bug, codeseparator_index, forkid_enabled) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Signs for a checksig executed after the OP_CODESEPARATOR at codeseparator_index (counting from 0) in unsigned_script.
+     */
+    #[wasm_bindgen(js_name = signWithCodeSeparator)]
+    pub fn sign_with_codeseparator(
+        &mut self,
+        priv_key: &PrivateKey,
+        sighash: SigHash,
+        n_tx_in: usize,
+        unsigned_script: &Script,
+        value: u64,
+        codeseparator_index: Option<usize>,
+    ) -> Result<SighashSignature, JsValue> {
+        match Transaction::sign_with_codeseparator_impl(self, priv_key, sighash, n_tx_in, unsigned_script, value, codeseparator_index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * The SIGHASH buffer for a checksig executed after the OP_CODESEPARATOR at codeseparator_index (counting from 0) in unsigned_script.
+     */
+    #[wasm_bindgen(js_name = sighashPreimageWithCodeSeparator)]
+    pub fn sighash_preimage_with_codeseparator(&mut self, sighash: SigHash, n_tx_in: usize, unsigned_script: &Script, value: u64, codeseparator_index: Option<usize>) -> Result<Vec<u8>, JsValue> {
+        match Transaction::sighash_preimage_with_codeseparator_impl(self, n_tx_in, sighash.into(), unsigned_script, value, codeseparator_index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * The SIGHASH buffer for any sighash byte, including undefined types found in historical transactions.
+     */
+    #[wasm_bindgen(js_name = sighashPreimageWithFlags)]
+    pub fn sighash_preimage_with_flags(&mut self, sighash: &SighashFlags, n_tx_in: usize, unsigned_script: &Script, value: u64, codeseparator_index: Option<usize>) -> Result<Vec<u8>, JsValue> {
+        match Transaction::sighash_preimage_with_codeseparator_impl(self, n_tx_in, *sighash, unsigned_script, value, codeseparator_index) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -361,6 +640,43 @@ impl SighashSignature {
         sig_bytes.push(sighash_u8);
         Ok(sig_bytes)
     }
+
+    /**
+     * Parses a checksig formatted signature (DER signature followed by the sighash byte), eg. as pushed in a script_sig.
+     * Applies the same checks as the node in order: strict DER (BIP66), low S, then a defined sighash type.
+     */
+    pub(crate) fn from_bytes_impl(bytes: &[u8], sighash_buffer: &[u8]) -> Result<SighashSignature, BSVErrors> {
+        let (sighash_byte, der_bytes) = bytes.split_last().ok_or_else(|| BSVErrors::NonStrictDERSignature("signature is empty".into()))?;
+
+        Signature::check_strict_der(der_bytes)?;
+        let signature = Signature::from_der_impl(der_bytes, false)?;
+
+        if !signature.is_low_s() {
+            return Err(BSVErrors::HighSSignature);
+        }
+
+        Ok(SighashSignature {
+            signature,
+            sighash_type: SighashSignature::defined_sighash(*sighash_byte)?,
+            sighash_buffer: sighash_buffer.to_vec(),
+        })
+    }
+
+    pub(crate) fn from_hex_impl(hex: &str, sighash_buffer: &[u8]) -> Result<SighashSignature, BSVErrors> {
+        SighashSignature::from_bytes_impl(&hex::decode(hex)?, sighash_buffer)
+    }
+
+    /**
+     * Defined sighash types are ALL, NONE or SINGLE, optionally combined with FORKID and/or ANYONECANPAY.
+     */
+    pub(crate) fn defined_sighash(sighash_byte: u8) -> Result<SigHash, BSVErrors> {
+        let flags = SighashFlags::from_u8(sighash_byte);
+        if !flags.is_defined() {
+            return Err(BSVErrors::UndefinedSighashType(sighash_byte));
+        }
+
+        SigHash::try_from(flags)
+    }
 }
 
 #[wasm_bindgen]
@@ -373,6 +689,21 @@ impl SighashSignature {
             sighash_buffer: sighash_buffer.to_vec(),
         }
     }
+
+    #[wasm_bindgen(js_name = getSignature)]
+    pub fn get_signature(&self) -> Signature {
+        self.signature.clone()
+    }
+
+    #[wasm_bindgen(js_name = getSighashType)]
+    pub fn get_sighash_type(&self) -> SigHash {
+        self.sighash_type
+    }
+
+    #[wasm_bindgen(js_name = getSighashBuffer)]
+    pub fn get_sighash_buffer(&self) -> Vec<u8> {
+        self.sighash_buffer.clone()
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -384,6 +715,18 @@ impl SighashSignature {
     pub fn to_bytes(&self) -> Result<Vec<u8>, BSVErrors> {
         self.to_bytes_impl()
     }
+
+    /**
+     * Parses a DER signature with a trailing sighash byte, rejecting non-strict DER, high S and undefined sighash types.
+     * The sighash_buffer is the preimage the signature commits to, it is only used when verifying.
+     */
+    pub fn from_bytes(bytes: &[u8], sighash_buffer: &[u8]) -> Result<SighashSignature, BSVErrors> {
+        SighashSignature::from_bytes_impl(bytes, sighash_buffer)
+    }
+
+    pub fn from_hex(hex: &str, sighash_buffer: &[u8]) -> Result<SighashSignature, BSVErrors> {
+        SighashSignature::from_hex_impl(hex, sighash_buffer)
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -404,4 +747,24 @@ impl SighashSignature {
             Err(e) => throw_str(&e.to_string()),
         }
     }
+
+    /**
+     * Parses a DER signature with a trailing sighash byte, rejecting non-strict DER, high S and undefined sighash types.
+     * The sighash_buffer is the preimage the signature commits to, it is only used when verifying.
+     */
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8], sighash_buffer: &[u8]) -> Result<SighashSignature, JsValue> {
+        match SighashSignature::from_bytes_impl(bytes, sighash_buffer) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex: &str, sighash_buffer: &[u8]) -> Result<SighashSignature, JsValue> {
+        match SighashSignature::from_hex_impl(hex, sighash_buffer) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use crate::BSVErrors;
+use std::convert::TryFrom;
+
+use crate::SigHash;
+use num_traits::FromPrimitive;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::throw_str;
+
+const BASE_TYPE_MASK: u8 = 0x1f;
+
+/**
+ * Any sighash byte, including combinations and undefined values that SigHash cannot represent.
+ * The low 5 bits are the base type (ALL, NONE, SINGLE), 0x40 is FORKID and 0x80 is ANYONECANPAY.
+ *
+ * Like the node, any base type other than NONE or SINGLE is hashed as ALL.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SighashFlags(pub(crate) u8);
+
+impl SighashFlags {
+    pub const ALL: SighashFlags = SighashFlags(SigHash::ALL as u8);
+    pub const NONE: SighashFlags = SighashFlags(SigHash::NONE as u8);
+    pub const SINGLE: SighashFlags = SighashFlags(SigHash::SINGLE as u8);
+    pub const FORKID: SighashFlags = SighashFlags(SigHash::FORKID as u8);
+    pub const ANYONECANPAY: SighashFlags = SighashFlags(SigHash::ANYONECANPAY as u8);
+
+    pub(crate) fn from_checksig_bytes_impl(bytes: &[u8]) -> Result<SighashFlags, BSVErrors> {
+        match bytes.last() {
+            Some(v) => Ok(SighashFlags(*v)),
+            None => Err(BSVErrors::ToSighash("Cannot read the sighash byte of an empty signature".into())),
+        }
+    }
+
+    pub(crate) fn to_sighash_impl(self) -> Result<SigHash, BSVErrors> {
+        SigHash::try_from(self)
+    }
+}
+
+/**
+ * Platform Agnostic Functions
+ * ie. Don't need Result<T, E>
+ */
+#[wasm_bindgen]
+impl SighashFlags {
+    #[wasm_bindgen(constructor)]
+    pub fn new(base_type: u8, anyonecanpay: bool, forkid: bool) -> SighashFlags {
+        let mut flags = base_type & BASE_TYPE_MASK;
+        if anyonecanpay {
+            flags |= SighashFlags::ANYONECANPAY.0;
+        }
+        if forkid {
+            flags |= SighashFlags::FORKID.0;
+        }
+
+        SighashFlags(flags)
+    }
+
+    #[wasm_bindgen(js_name = fromU8)]
+    pub fn from_u8(value: u8) -> SighashFlags {
+        SighashFlags(value)
+    }
+
+    #[wasm_bindgen(js_name = toU8)]
+    pub fn to_u8(&self) -> u8 {
+        self.0
+    }
+
+    /**
+     * The low 5 bits, 1 (ALL), 2 (NONE) or 3 (SINGLE) for defined sighash types.
+     */
+    #[wasm_bindgen(js_name = baseType)]
+    pub fn base_type(&self) -> u8 {
+        self.0 & BASE_TYPE_MASK
+    }
+
+    #[wasm_bindgen(js_name = hasForkId)]
+    pub fn has_forkid(&self) -> bool {
+        self.0 & SighashFlags::FORKID.0 != 0
+    }
+
+    #[wasm_bindgen(js_name = isAnyoneCanPay)]
+    pub fn is_anyonecanpay(&self) -> bool {
+        self.0 & SighashFlags::ANYONECANPAY.0 != 0
+    }
+
+    /**
+     * Signs no outputs.
+     */
+    #[wasm_bindgen(js_name = isNone)]
+    pub fn is_none(&self) -> bool {
+        self.base_type() == SighashFlags::NONE.0
+    }
+
+    /**
+     * Signs only the output at the same index as the input.
+     */
+    #[wasm_bindgen(js_name = isSingle)]
+    pub fn is_single(&self) -> bool {
+        self.base_type() == SighashFlags::SINGLE.0
+    }
+
+    /**
+     * Base type is ALL, NONE or SINGLE and no bits other than FORKID and ANYONECANPAY are set, ie. passes the node's strict encoding check.
+     */
+    #[wasm_bindgen(js_name = isDefined)]
+    pub fn is_defined(&self) -> bool {
+        let unknown_bits = self.0 & !(BASE_TYPE_MASK | SighashFlags::FORKID.0 | SighashFlags::ANYONECANPAY.0);
+        unknown_bits == 0 && (SighashFlags::ALL.0..=SighashFlags::SINGLE.0).contains(&self.base_type())
+    }
+}
+
+impl From<SigHash> for SighashFlags {
+    fn from(sighash: SigHash) -> Self {
+        SighashFlags(sighash as u8)
+    }
+}
+
+impl From<u8> for SighashFlags {
+    fn from(value: u8) -> Self {
+        SighashFlags(value)
+    }
+}
+
+impl TryFrom<SighashFlags> for SigHash {
+    type Error = BSVErrors;
+
+    fn try_from(flags: SighashFlags) -> Result<Self, Self::Error> {
+        FromPrimitive::from_u8(flags.0).ok_or_else(|| BSVErrors::ToSighash(format!("{:#04x} is not a defined SigHash", flags.0)))
+    }
+}
+
+impl std::ops::BitOr for SighashFlags {
+    type Output = SighashFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        SighashFlags(self.0 | rhs.0)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl SighashFlags {
+    /**
+     * Reads the sighash byte from the end of a checksig signature without validating it.
+     */
+    #[wasm_bindgen(js_name = fromChecksigBytes)]
+    pub fn from_checksig_bytes(bytes: &[u8]) -> Result<SighashFlags, JsValue> {
+        match SighashFlags::from_checksig_bytes_impl(bytes) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Only defined sighash types can be converted.
+     */
+    #[wasm_bindgen(js_name = toSigHash)]
+    pub fn to_sighash(&self) -> Result<SigHash, JsValue> {
+        match self.to_sighash_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SighashFlags {
+    /**
+     * Reads the sighash byte from the end of a checksig signature without validating it.
+     */
+    pub fn from_checksig_bytes(bytes: &[u8]) -> Result<SighashFlags, BSVErrors> {
+        SighashFlags::from_checksig_bytes_impl(bytes)
+    }
+
+    /**
+     * Only defined sighash types can be converted.
+     */
+    pub fn to_sighash(&self) -> Result<SigHash, BSVErrors> {
+        self.to_sighash_impl()
+    }
+}
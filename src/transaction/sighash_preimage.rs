@@ -0,0 +1,425 @@
+use crate::BSVErrors;
+use std::convert::TryFrom;
+use std::io::Cursor;
+use std::io::Read;
+
+use crate::{Hash, Script, SigHash, SighashFlags, SighashSignature, VarInt};
+use byteorder::*;
+use wasm_bindgen::{prelude::*, throw_str, JsValue};
+
+/**
+ * The BIP143 (FORKID) sighash preimage as produced by Transaction::sighash_preimage, ie. what OP_PUSH_TX pushes onto the stack.
+ *
+ * Serialised as: version (4) | hashPrevouts (32) | hashSequence (32) | outpoint (36) | scriptCode (VarInt + bytes)
+ * | value (8) | nSequence (4) | hashOutputs (32) | nLocktime (4) | sighash type (4)
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SighashPreimage {
+    pub(crate) version: u32,
+    pub(crate) hash_prevouts: Vec<u8>,
+    pub(crate) hash_sequence: Vec<u8>,
+    pub(crate) prev_tx_id: Vec<u8>,
+    pub(crate) vout: u32,
+    pub(crate) script_code: Script,
+    pub(crate) value: u64,
+    pub(crate) sequence: u32,
+    pub(crate) hash_outputs: Vec<u8>,
+    pub(crate) n_locktime: u32,
+    pub(crate) sighash_type: SigHash,
+}
+
+/**
+ * value (8) + nSequence (4) + hashOutputs (32) + nLocktime (4) + sighash type (4)
+ */
+const PREIMAGE_SUFFIX_LENGTH: u64 = 8 + 4 + 32 + 4 + 4;
+
+impl SighashPreimage {
+    pub(crate) fn from_bytes_impl(bytes: &[u8]) -> Result<SighashPreimage, BSVErrors> {
+        let mut cursor = Cursor::new(bytes.to_vec());
+
+        // Version - 4 bytes
+        let version = match cursor.read_u32::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("version".to_string(), e)),
+        };
+
+        let hash_prevouts = SighashPreimage::read_hash(&mut cursor, "hash_prevouts")?;
+        let hash_sequence = SighashPreimage::read_hash(&mut cursor, "hash_sequence")?;
+
+        // Outpoint - 32 byte reversed txid + 4 byte vout
+        let mut prev_tx_id = SighashPreimage::read_hash(&mut cursor, "prev_tx_id")?;
+        prev_tx_id.reverse();
+
+        let vout = match cursor.read_u32::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("vout".to_string(), e)),
+        };
+
+        // Script Code - VarInt + bytes
+        let script_code_length = match cursor.read_varint() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("script_code_length".to_string(), e)),
+        };
+
+        let remaining = bytes.len() as u64 - cursor.position();
+        if script_code_length > remaining.saturating_sub(PREIMAGE_SUFFIX_LENGTH) {
+            return Err(BSVErrors::InvalidSighashPreimage(format!(
+                "script code length {} is longer than the {} bytes remaining",
+                script_code_length,
+                remaining.saturating_sub(PREIMAGE_SUFFIX_LENGTH)
+            )));
+        }
+
+        let mut script_code = vec![0; script_code_length as usize];
+        if let Err(e) = cursor.read_exact(&mut script_code) {
+            return Err(BSVErrors::DeserialiseSighashPreimage("script_code".to_string(), e));
+        }
+
+        // Value - 8 bytes
+        let value = match cursor.read_u64::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("value".to_string(), e)),
+        };
+
+        // nSequence - 4 bytes
+        let sequence = match cursor.read_u32::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("sequence".to_string(), e)),
+        };
+
+        let hash_outputs = SighashPreimage::read_hash(&mut cursor, "hash_outputs")?;
+
+        // nLocktime - 4 bytes
+        let n_locktime = match cursor.read_u32::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("n_locktime".to_string(), e)),
+        };
+
+        // Sighash Type - 4 bytes
+        let sighash_u32 = match cursor.read_u32::<LittleEndian>() {
+            Ok(v) => v,
+            Err(e) => return Err(BSVErrors::DeserialiseSighashPreimage("sighash_type".to_string(), e)),
+        };
+
+        if cursor.position() != bytes.len() as u64 {
+            return Err(BSVErrors::InvalidSighashPreimage(format!("{} unexpected trailing bytes", bytes.len() as u64 - cursor.position())));
+        }
+
+        Ok(SighashPreimage {
+            version,
+            hash_prevouts,
+            hash_sequence,
+            prev_tx_id,
+            vout,
+            script_code: Script(script_code),
+            value,
+            sequence,
+            hash_outputs,
+            n_locktime,
+            sighash_type: SighashPreimage::bip143_sighash(sighash_u32)?,
+        })
+    }
+
+    pub(crate) fn from_hex_impl(hex_str: &str) -> Result<SighashPreimage, BSVErrors> {
+        SighashPreimage::from_bytes_impl(&hex::decode(hex_str)?)
+    }
+
+    fn read_hash(cursor: &mut Cursor<Vec<u8>>, field: &str) -> Result<Vec<u8>, BSVErrors> {
+        let mut hash = vec![0; 32];
+        if let Err(e) = cursor.read_exact(&mut hash) {
+            return Err(BSVErrors::DeserialiseSighashPreimage(field.to_string(), e));
+        }
+
+        Ok(hash)
+    }
+
+    /**
+     * Only FORKID sighash types use the BIP143 preimage format, legacy types sign a modified transaction instead.
+     */
+    fn bip143_sighash(sighash_u32: u32) -> Result<SigHash, BSVErrors> {
+        let sighash_u8 = u8::try_from(sighash_u32).map_err(|_| BSVErrors::InvalidSighashPreimage(format!("sighash type {:#010x} does not fit in a single byte", sighash_u32)))?;
+        let sighash = SighashSignature::defined_sighash(sighash_u8)?;
+
+        if !SighashFlags::from_u8(sighash_u8).has_forkid() {
+            return Err(BSVErrors::InvalidSighashPreimage(format!("sighash type {:?} does not include FORKID", sighash)));
+        }
+
+        Ok(sighash)
+    }
+
+    fn check_hash_length(field: &str, hash: &[u8]) -> Result<(), BSVErrors> {
+        match hash.len() {
+            32 => Ok(()),
+            v => Err(BSVErrors::InvalidSighashPreimage(format!("{} must be 32 bytes, got {}", field, v))),
+        }
+    }
+
+    pub(crate) fn set_hash_prevouts_impl(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        SighashPreimage::check_hash_length("hash_prevouts", hash)?;
+        self.hash_prevouts = hash.to_vec();
+        Ok(())
+    }
+
+    pub(crate) fn set_hash_sequence_impl(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        SighashPreimage::check_hash_length("hash_sequence", hash)?;
+        self.hash_sequence = hash.to_vec();
+        Ok(())
+    }
+
+    pub(crate) fn set_prev_tx_id_impl(&mut self, prev_tx_id: &[u8]) -> Result<(), BSVErrors> {
+        SighashPreimage::check_hash_length("prev_tx_id", prev_tx_id)?;
+        self.prev_tx_id = prev_tx_id.to_vec();
+        Ok(())
+    }
+
+    pub(crate) fn set_hash_outputs_impl(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        SighashPreimage::check_hash_length("hash_outputs", hash)?;
+        self.hash_outputs = hash.to_vec();
+        Ok(())
+    }
+
+    pub(crate) fn set_sighash_type_impl(&mut self, sighash: SigHash) -> Result<(), BSVErrors> {
+        self.sighash_type = SighashPreimage::bip143_sighash(sighash as u32)?;
+        Ok(())
+    }
+}
+
+/**
+ * Platform Agnostic Functions
+ * ie. Don't need Result<T, E>
+ */
+#[wasm_bindgen]
+impl SighashPreimage {
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let script_code = self.script_code.to_bytes();
+        let mut buffer = Vec::with_capacity(4 + 32 + 32 + 36 + 9 + script_code.len() + PREIMAGE_SUFFIX_LENGTH as usize);
+
+        // Writing into a Vec cannot fail
+        let _ = buffer.write_u32::<LittleEndian>(self.version);
+        buffer.extend_from_slice(&self.hash_prevouts);
+        buffer.extend_from_slice(&self.hash_sequence);
+        buffer.extend_from_slice(&self.get_prev_tx_id(Some(true)));
+        let _ = buffer.write_u32::<LittleEndian>(self.vout);
+        let _ = buffer.write_varint(script_code.len() as u64);
+        buffer.extend_from_slice(&script_code);
+        let _ = buffer.write_u64::<LittleEndian>(self.value);
+        let _ = buffer.write_u32::<LittleEndian>(self.sequence);
+        buffer.extend_from_slice(&self.hash_outputs);
+        let _ = buffer.write_u32::<LittleEndian>(self.n_locktime);
+        let _ = buffer.write_u32::<LittleEndian>(self.sighash_type as u32);
+
+        buffer
+    }
+
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /**
+     * The digest that is actually signed, SHA256d of the preimage.
+     */
+    #[wasm_bindgen(js_name = getSighashHash)]
+    pub fn get_sighash_hash(&self) -> Vec<u8> {
+        Hash::sha_256d(&self.to_bytes()).to_bytes()
+    }
+
+    #[wasm_bindgen(js_name = getVersion)]
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    #[wasm_bindgen(js_name = getHashPrevouts)]
+    pub fn get_hash_prevouts(&self) -> Vec<u8> {
+        self.hash_prevouts.clone()
+    }
+
+    #[wasm_bindgen(js_name = getHashSequence)]
+    pub fn get_hash_sequence(&self) -> Vec<u8> {
+        self.hash_sequence.clone()
+    }
+
+    /**
+     * TxID of the output being spent, big endian (as displayed) unless little_endian is set.
+     */
+    #[wasm_bindgen(js_name = getPrevTxId)]
+    pub fn get_prev_tx_id(&self, little_endian: Option<bool>) -> Vec<u8> {
+        match little_endian {
+            Some(true) => self.prev_tx_id.iter().rev().cloned().collect(),
+            _ => self.prev_tx_id.clone(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getVOut)]
+    pub fn get_vout(&self) -> u32 {
+        self.vout
+    }
+
+    #[wasm_bindgen(js_name = getScriptCode)]
+    pub fn get_script_code(&self) -> Script {
+        self.script_code.clone()
+    }
+
+    /**
+     * Satoshis of the output being spent.
+     */
+    #[wasm_bindgen(js_name = getValue)]
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+
+    #[wasm_bindgen(js_name = getSequence)]
+    pub fn get_sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    #[wasm_bindgen(js_name = getHashOutputs)]
+    pub fn get_hash_outputs(&self) -> Vec<u8> {
+        self.hash_outputs.clone()
+    }
+
+    #[wasm_bindgen(js_name = getNLocktime)]
+    pub fn get_n_locktime(&self) -> u32 {
+        self.n_locktime
+    }
+
+    #[wasm_bindgen(js_name = getSighashType)]
+    pub fn get_sighash_type(&self) -> SigHash {
+        self.sighash_type
+    }
+
+    #[wasm_bindgen(js_name = setVersion)]
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    #[wasm_bindgen(js_name = setVOut)]
+    pub fn set_vout(&mut self, vout: u32) {
+        self.vout = vout;
+    }
+
+    #[wasm_bindgen(js_name = setScriptCode)]
+    pub fn set_script_code(&mut self, script_code: &Script) {
+        self.script_code = script_code.clone();
+    }
+
+    #[wasm_bindgen(js_name = setValue)]
+    pub fn set_value(&mut self, value: u64) {
+        self.value = value;
+    }
+
+    #[wasm_bindgen(js_name = setSequence)]
+    pub fn set_sequence(&mut self, sequence: u32) {
+        self.sequence = sequence;
+    }
+
+    #[wasm_bindgen(js_name = setNLocktime)]
+    pub fn set_n_locktime(&mut self, n_locktime: u32) {
+        self.n_locktime = n_locktime;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl SighashPreimage {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<SighashPreimage, JsValue> {
+        match SighashPreimage::from_bytes_impl(bytes) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex_str: &str) -> Result<SighashPreimage, JsValue> {
+        match SighashPreimage::from_hex_impl(hex_str) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = setHashPrevouts)]
+    pub fn set_hash_prevouts(&mut self, hash: &[u8]) -> Result<(), JsValue> {
+        match self.set_hash_prevouts_impl(hash) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = setHashSequence)]
+    pub fn set_hash_sequence(&mut self, hash: &[u8]) -> Result<(), JsValue> {
+        match self.set_hash_sequence_impl(hash) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Expects the TxID big endian (as displayed).
+     */
+    #[wasm_bindgen(js_name = setPrevTxId)]
+    pub fn set_prev_tx_id(&mut self, prev_tx_id: &[u8]) -> Result<(), JsValue> {
+        match self.set_prev_tx_id_impl(prev_tx_id) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = setHashOutputs)]
+    pub fn set_hash_outputs(&mut self, hash: &[u8]) -> Result<(), JsValue> {
+        match self.set_hash_outputs_impl(hash) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Must be one of the FORKID sighash types.
+     */
+    #[wasm_bindgen(js_name = setSighashType)]
+    pub fn set_sighash_type(&mut self, sighash: SigHash) -> Result<(), JsValue> {
+        match self.set_sighash_type_impl(sighash) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SighashPreimage {
+    pub fn from_bytes(bytes: &[u8]) -> Result<SighashPreimage, BSVErrors> {
+        SighashPreimage::from_bytes_impl(bytes)
+    }
+
+    pub fn from_hex(hex_str: &str) -> Result<SighashPreimage, BSVErrors> {
+        SighashPreimage::from_hex_impl(hex_str)
+    }
+
+    pub fn set_hash_prevouts(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        self.set_hash_prevouts_impl(hash)
+    }
+
+    pub fn set_hash_sequence(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        self.set_hash_sequence_impl(hash)
+    }
+
+    /**
+     * Expects the TxID big endian (as displayed).
+     */
+    pub fn set_prev_tx_id(&mut self, prev_tx_id: &[u8]) -> Result<(), BSVErrors> {
+        self.set_prev_tx_id_impl(prev_tx_id)
+    }
+
+    pub fn set_hash_outputs(&mut self, hash: &[u8]) -> Result<(), BSVErrors> {
+        self.set_hash_outputs_impl(hash)
+    }
+
+    /**
+     * Must be one of the FORKID sighash types.
+     */
+    pub fn set_sighash_type(&mut self, sighash: SigHash) -> Result<(), BSVErrors> {
+        self.set_sighash_type_impl(sighash)
+    }
+}
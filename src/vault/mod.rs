@@ -0,0 +1,516 @@
+use crate::utils::{from_hex, to_hex};
+use crate::{AEADAlgorithms, Argon2Params, BSVErrors, ExtendedPrivateKey, PrivateKey, ScryptParams, AEAD_NONCE_LENGTH, AEAD_TAG_LENGTH, AES, KDF};
+use rand_core::{OsRng, RngCore};
+use serde::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{throw_str, JsValue};
+use zeroize::Zeroizing;
+
+pub const VAULT_VERSION: u32 = 1;
+
+const VAULT_MAGIC_BYTES: &[u8] = b"VLT1";
+const VAULT_KEY_LENGTH: usize = 32;
+const VAULT_SALT_LENGTH: usize = 16;
+
+/**
+ * The most expensive KDF parameters a vault may carry. Vault JSON is untrusted input, so anything costlier is rejected
+ * before the KDF runs: at most 1 GiB of memory and a few seconds of work on a desktop.
+ */
+pub const VAULT_MAX_SCRYPT_LOG_N: u8 = 20;
+pub const VAULT_MAX_SCRYPT_R_TIMES_P: u32 = 64;
+pub const VAULT_MAX_MEMORY_BYTES: u64 = 1 << 30;
+pub const VAULT_MAX_ARGON2_ITERATIONS: u32 = 10;
+pub const VAULT_MAX_ARGON2_PARALLELISM: u32 = 16;
+
+/**
+ * What the vault's plaintext is, so it can be decrypted straight back into the right type.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VaultSecretType {
+    #[serde(rename = "wif")]
+    PrivateKey,
+    #[serde(rename = "xprv")]
+    ExtendedPrivateKey,
+    #[serde(rename = "mnemonic")]
+    Mnemonic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "algorithm")]
+enum VaultKDFParams {
+    #[serde(rename = "scrypt")]
+    Scrypt(ScryptParams),
+    #[serde(rename = "argon2id")]
+    Argon2id(Argon2Params),
+}
+
+/**
+ * The password KDF a vault is locked with, and its cost parameters.
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VaultKDF(VaultKDFParams);
+
+/**
+ * Argon2id with RFC 9106's recommended parameters.
+ */
+impl Default for VaultKDF {
+    fn default() -> Self {
+        VaultKDF(VaultKDFParams::Argon2id(Argon2Params::default()))
+    }
+}
+
+#[wasm_bindgen]
+impl VaultKDF {
+    pub fn scrypt(params: &ScryptParams) -> VaultKDF {
+        VaultKDF(VaultKDFParams::Scrypt(*params))
+    }
+
+    pub fn argon2id(params: &Argon2Params) -> VaultKDF {
+        VaultKDF(VaultKDFParams::Argon2id(*params))
+    }
+
+    /**
+     * "scrypt" or "argon2id"
+     */
+    #[wasm_bindgen(js_name = getAlgorithm)]
+    pub fn get_algorithm(&self) -> String {
+        match self.0 {
+            VaultKDFParams::Scrypt(_) => "scrypt".into(),
+            VaultKDFParams::Argon2id(_) => "argon2id".into(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getScryptParams)]
+    pub fn get_scrypt_params(&self) -> Option<ScryptParams> {
+        match self.0 {
+            VaultKDFParams::Scrypt(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    #[wasm_bindgen(js_name = getArgon2Params)]
+    pub fn get_argon2_params(&self) -> Option<Argon2Params> {
+        match self.0 {
+            VaultKDFParams::Argon2id(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl VaultKDF {
+    /**
+     * Errors if the parameters exceed the VAULT_MAX_* limits.
+     */
+    fn check_limits(&self) -> Result<(), BSVErrors> {
+        match self.0 {
+            VaultKDFParams::Scrypt(params) => {
+                let r_times_p = params.get_r() as u64 * params.get_p() as u64;
+                if params.get_log_n() > VAULT_MAX_SCRYPT_LOG_N || r_times_p > VAULT_MAX_SCRYPT_R_TIMES_P as u64 {
+                    return Err(BSVErrors::VaultError(format!(
+                        "Vault scrypt parameters exceed log_n <= {} and r * p <= {}, got log_n = {} r = {} p = {}",
+                        VAULT_MAX_SCRYPT_LOG_N,
+                        VAULT_MAX_SCRYPT_R_TIMES_P,
+                        params.get_log_n(),
+                        params.get_r(),
+                        params.get_p()
+                    )));
+                }
+
+                let memory = 128 * params.get_r() as u64 * (1 << params.get_log_n());
+                if memory > VAULT_MAX_MEMORY_BYTES {
+                    return Err(BSVErrors::VaultError(format!(
+                        "Vault scrypt parameters need {} bytes of memory, more than {}",
+                        memory, VAULT_MAX_MEMORY_BYTES
+                    )));
+                }
+            }
+            VaultKDFParams::Argon2id(params) => {
+                if params.get_memory_kib() as u64 * 1024 > VAULT_MAX_MEMORY_BYTES || params.get_iterations() > VAULT_MAX_ARGON2_ITERATIONS || params.get_parallelism() > VAULT_MAX_ARGON2_PARALLELISM {
+                    return Err(BSVErrors::VaultError(format!(
+                        "Vault Argon2id parameters exceed memory <= {} KiB, iterations <= {} and parallelism <= {}, got {} KiB, {} and {}",
+                        VAULT_MAX_MEMORY_BYTES / 1024,
+                        VAULT_MAX_ARGON2_ITERATIONS,
+                        VAULT_MAX_ARGON2_PARALLELISM,
+                        params.get_memory_kib(),
+                        params.get_iterations(),
+                        params.get_parallelism()
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn derive_key(&self, password: &[u8], salt: &[u8]) -> Result<Zeroizing<Vec<u8>>, BSVErrors> {
+        let kdf = match &self.0 {
+            VaultKDFParams::Scrypt(params) => KDF::scrypt_impl(password, salt, params, VAULT_KEY_LENGTH)?,
+            VaultKDFParams::Argon2id(params) => KDF::argon2id_impl(password, salt, params, VAULT_KEY_LENGTH)?,
+        };
+
+        Ok(Zeroizing::new(kdf.get_hash().to_bytes()))
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        match self.0 {
+            VaultKDFParams::Scrypt(params) => [&[0, params.get_log_n()], &params.get_r().to_le_bytes()[..], &params.get_p().to_le_bytes()].concat(),
+            VaultKDFParams::Argon2id(params) => [
+                &[1][..],
+                &params.get_memory_kib().to_le_bytes(),
+                &params.get_iterations().to_le_bytes(),
+                &params.get_parallelism().to_le_bytes(),
+            ]
+            .concat(),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum VaultCipher {
+    #[serde(rename = "aes-256-gcm")]
+    AES256_GCM,
+}
+
+/**
+ * The private and public version bytes of a vaulted xprv, so SLIP-132 (eg. zprv) and custom versions parse again on decryption.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct VaultExtendedKeyVersions {
+    private: u32,
+    public: u32,
+}
+
+/**
+ * A password protected container for a wallet secret (WIF, xprv or mnemonic), meant to be persisted as JSON.
+ * It records everything needed to open it again: the format version, the secret type, the KDF and its parameters, the salt,
+ * the cipher and its nonce, the ciphertext and the MAC. Every field but the ciphertext and MAC is authenticated as associated data.
+ * xprv vaults also record the key's private and public version bytes.
+ * KDF parameters above the VAULT_MAX_* limits are rejected when a vault is created or parsed.
+ *
+ * {"version":1,"secret_type":"wif","kdf":{"algorithm":"argon2id","memory_kib":65536,"iterations":3,"parallelism":4},
+ *  "salt":"..","cipher":"aes-256-gcm","nonce":"..","ciphertext":"..","mac":".."}
+ */
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vault {
+    version: u32,
+    secret_type: VaultSecretType,
+    kdf: VaultKDF,
+    #[serde(serialize_with = "to_hex", deserialize_with = "from_hex")]
+    salt: Vec<u8>,
+    cipher: VaultCipher,
+    #[serde(serialize_with = "to_hex", deserialize_with = "from_hex")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_hex", deserialize_with = "from_hex")]
+    ciphertext: Vec<u8>,
+    #[serde(serialize_with = "to_hex", deserialize_with = "from_hex")]
+    mac: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extended_key_versions: Option<VaultExtendedKeyVersions>,
+}
+
+impl Vault {
+    /**
+     * Locks the secret with a fresh random salt and nonce.
+     */
+    fn encrypt_impl(secret: &[u8], secret_type: VaultSecretType, extended_key_versions: Option<VaultExtendedKeyVersions>, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        kdf.check_limits()?;
+
+        let mut salt = vec![0; VAULT_SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = vec![0; AEAD_NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut vault = Vault {
+            version: VAULT_VERSION,
+            secret_type,
+            kdf: *kdf,
+            salt,
+            cipher: VaultCipher::AES256_GCM,
+            nonce,
+            ciphertext: vec![],
+            mac: vec![],
+            extended_key_versions,
+        };
+
+        let key = kdf.derive_key(password, &vault.salt)?;
+        let mut ciphertext = AES::encrypt_aead_impl(&key, &vault.nonce, secret, &vault.header_bytes(), AEADAlgorithms::AES256_GCM)?;
+        vault.mac = ciphertext.split_off(ciphertext.len() - AEAD_TAG_LENGTH);
+        vault.ciphertext = ciphertext;
+
+        Ok(vault)
+    }
+
+    /**
+     * Errors with InvalidAuthenticationTag if the password is wrong or any field was modified.
+     */
+    pub(crate) fn decrypt_impl(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>, BSVErrors> {
+        let key = self.kdf.derive_key(password, &self.salt)?;
+        let ciphertext = [&self.ciphertext[..], &self.mac].concat();
+        let algo = match self.cipher {
+            VaultCipher::AES256_GCM => AEADAlgorithms::AES256_GCM,
+        };
+
+        Ok(Zeroizing::new(AES::decrypt_aead_impl(&key, &self.nonce, &ciphertext, &self.header_bytes(), algo)?))
+    }
+
+    /**
+     * Re-encrypts the secret under a new KDF (eg. stronger parameters), with a fresh salt and nonce.
+     */
+    pub(crate) fn upgrade_impl(&self, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        let secret = self.decrypt_impl(password)?;
+        Vault::encrypt_impl(&secret, self.secret_type, self.extended_key_versions, password, kdf)
+    }
+
+    pub(crate) fn from_private_key_impl(private_key: &PrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        let wif = Zeroizing::new(private_key.to_wif_impl()?);
+        Vault::encrypt_impl(wif.as_bytes(), VaultSecretType::PrivateKey, None, password, kdf)
+    }
+
+    pub(crate) fn from_extended_private_key_impl(xprv: &ExtendedPrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        let xprv_string = Zeroizing::new(xprv.to_string_impl()?);
+        let versions = VaultExtendedKeyVersions {
+            private: xprv.get_version(),
+            public: xprv.get_public_version(),
+        };
+        Vault::encrypt_impl(xprv_string.as_bytes(), VaultSecretType::ExtendedPrivateKey, Some(versions), password, kdf)
+    }
+
+    pub(crate) fn from_mnemonic_impl(mnemonic: &str, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        Vault::encrypt_impl(mnemonic.as_bytes(), VaultSecretType::Mnemonic, None, password, kdf)
+    }
+
+    pub(crate) fn decrypt_private_key_impl(&self, password: &[u8]) -> Result<PrivateKey, BSVErrors> {
+        PrivateKey::from_wif_impl(&self.decrypt_string(password, VaultSecretType::PrivateKey)?)
+    }
+
+    pub(crate) fn decrypt_extended_private_key_impl(&self, password: &[u8]) -> Result<ExtendedPrivateKey, BSVErrors> {
+        let xprv_string = self.decrypt_string(password, VaultSecretType::ExtendedPrivateKey)?;
+        let versions = self.extended_key_versions.ok_or_else(|| BSVErrors::VaultError("xprv vault does not record its version bytes".into()))?;
+        ExtendedPrivateKey::from_string_with_version_pair_impl(&xprv_string, versions.private, versions.public)
+    }
+
+    pub(crate) fn decrypt_mnemonic_impl(&self, password: &[u8]) -> Result<String, BSVErrors> {
+        Ok(self.decrypt_string(password, VaultSecretType::Mnemonic)?.to_string())
+    }
+
+    fn decrypt_string(&self, password: &[u8], expected_type: VaultSecretType) -> Result<Zeroizing<String>, BSVErrors> {
+        if self.secret_type != expected_type {
+            return Err(BSVErrors::VaultError(format!("Vault holds a {:?}, not a {:?}", self.secret_type, expected_type)));
+        }
+
+        let secret = self.decrypt_impl(password)?;
+        match std::str::from_utf8(&secret) {
+            Ok(v) => Ok(Zeroizing::new(v.to_string())),
+            Err(_) => Err(BSVErrors::VaultError("Decrypted secret is not valid UTF-8".into())),
+        }
+    }
+
+    pub(crate) fn to_json_string_impl(&self) -> Result<String, BSVErrors> {
+        let json = serde_json::to_string(self)?;
+        Ok(json)
+    }
+
+    pub(crate) fn from_json_string_impl(json: &str) -> Result<Vault, BSVErrors> {
+        let vault: Vault = serde_json::from_str(json)?;
+
+        if vault.version != VAULT_VERSION {
+            return Err(BSVErrors::VaultError(format!("Unsupported vault version {}, expected {}", vault.version, VAULT_VERSION)));
+        }
+
+        if vault.nonce.len() != AEAD_NONCE_LENGTH || vault.mac.len() != AEAD_TAG_LENGTH {
+            return Err(BSVErrors::VaultError(format!(
+                "Vault nonce must be {} bytes and MAC {} bytes, got {} and {}",
+                AEAD_NONCE_LENGTH,
+                AEAD_TAG_LENGTH,
+                vault.nonce.len(),
+                vault.mac.len()
+            )));
+        }
+
+        if vault.extended_key_versions.is_some() != (vault.secret_type == VaultSecretType::ExtendedPrivateKey) {
+            return Err(BSVErrors::VaultError("Only xprv vaults record extended key version bytes, and they must".into()));
+        }
+
+        vault.kdf.check_limits()?;
+
+        Ok(vault)
+    }
+
+    /**
+     * The associated data: "VLT1" | version | secret type | KDF id and parameters | salt length | salt | cipher id | xprv version | xpub version
+     * The version bytes are only present for xprv vaults.
+     */
+    fn header_bytes(&self) -> Vec<u8> {
+        let mut header = VAULT_MAGIC_BYTES.to_vec();
+        header.extend_from_slice(&self.version.to_le_bytes());
+        header.push(self.secret_type as u8);
+        header.extend_from_slice(&self.kdf.to_bytes());
+        header.extend_from_slice(&(self.salt.len() as u32).to_le_bytes());
+        header.extend_from_slice(&self.salt);
+        header.push(self.cipher as u8);
+        if let Some(versions) = self.extended_key_versions {
+            header.extend_from_slice(&versions.private.to_le_bytes());
+            header.extend_from_slice(&versions.public.to_le_bytes());
+        }
+        header
+    }
+}
+
+#[wasm_bindgen]
+impl Vault {
+    #[wasm_bindgen(js_name = getVersion)]
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    #[wasm_bindgen(js_name = getSecretType)]
+    pub fn get_secret_type(&self) -> VaultSecretType {
+        self.secret_type
+    }
+
+    /**
+     * Compare against the current recommendation to decide whether to upgrade.
+     */
+    #[wasm_bindgen(js_name = getKDF)]
+    pub fn get_kdf(&self) -> VaultKDF {
+        self.kdf
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl Vault {
+    #[wasm_bindgen(js_name = fromPrivateKey)]
+    pub fn from_private_key(private_key: &PrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, JsValue> {
+        match Vault::from_private_key_impl(private_key, password, kdf) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromExtendedPrivateKey)]
+    pub fn from_extended_private_key(xprv: &ExtendedPrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, JsValue> {
+        match Vault::from_extended_private_key_impl(xprv, password, kdf) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromMnemonic)]
+    pub fn from_mnemonic(mnemonic: &str, password: &[u8], kdf: &VaultKDF) -> Result<Vault, JsValue> {
+        match Vault::from_mnemonic_impl(mnemonic, password, kdf) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = decryptPrivateKey)]
+    pub fn decrypt_private_key(&self, password: &[u8]) -> Result<PrivateKey, JsValue> {
+        match self.decrypt_private_key_impl(password) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = decryptExtendedPrivateKey)]
+    pub fn decrypt_extended_private_key(&self, password: &[u8]) -> Result<ExtendedPrivateKey, JsValue> {
+        match self.decrypt_extended_private_key_impl(password) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = decryptMnemonic)]
+    pub fn decrypt_mnemonic(&self, password: &[u8]) -> Result<String, JsValue> {
+        match self.decrypt_mnemonic_impl(password) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    /**
+     * Returns a new vault holding the same secret under the given KDF, the password stays the same.
+     */
+    pub fn upgrade(&self, password: &[u8], kdf: &VaultKDF) -> Result<Vault, JsValue> {
+        match self.upgrade_impl(password, kdf) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_json_string(&self) -> Result<String, JsValue> {
+        match self.to_json_string_impl() {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        match JsValue::from_serde(&self) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromJSONString)]
+    pub fn from_json_string(json: &str) -> Result<Vault, JsValue> {
+        match Vault::from_json_string_impl(json) {
+            Ok(v) => Ok(v),
+            Err(e) => throw_str(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Vault {
+    pub fn from_private_key(private_key: &PrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        Vault::from_private_key_impl(private_key, password, kdf)
+    }
+
+    pub fn from_extended_private_key(xprv: &ExtendedPrivateKey, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        Vault::from_extended_private_key_impl(xprv, password, kdf)
+    }
+
+    pub fn from_mnemonic(mnemonic: &str, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        Vault::from_mnemonic_impl(mnemonic, password, kdf)
+    }
+
+    pub fn decrypt_private_key(&self, password: &[u8]) -> Result<PrivateKey, BSVErrors> {
+        self.decrypt_private_key_impl(password)
+    }
+
+    pub fn decrypt_extended_private_key(&self, password: &[u8]) -> Result<ExtendedPrivateKey, BSVErrors> {
+        self.decrypt_extended_private_key_impl(password)
+    }
+
+    pub fn decrypt_mnemonic(&self, password: &[u8]) -> Result<String, BSVErrors> {
+        self.decrypt_mnemonic_impl(password)
+    }
+
+    /**
+     * Returns a new vault holding the same secret under the given KDF, the password stays the same.
+     */
+    pub fn upgrade(&self, password: &[u8], kdf: &VaultKDF) -> Result<Vault, BSVErrors> {
+        self.upgrade_impl(password, kdf)
+    }
+
+    pub fn to_json_string(&self) -> Result<String, BSVErrors> {
+        self.to_json_string_impl()
+    }
+
+    pub fn to_json(&self) -> Result<serde_json::Value, BSVErrors> {
+        let json = serde_json::to_value(self)?;
+        Ok(json)
+    }
+
+    pub fn from_json_string(json: &str) -> Result<Vault, BSVErrors> {
+        Vault::from_json_string_impl(json)
+    }
+}
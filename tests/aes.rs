@@ -3,7 +3,11 @@
 mod aes_tests {
     use std::io::Read;
 
-    use bsv_wasm::{encryption::AESAlgorithms, hash::Hash, AES};
+    use bsv_wasm::{
+        encryption::{AEADAlgorithms, AESAlgorithms, AESDecryptor, AESEncryptor, AESStreamAlgorithms},
+        hash::Hash,
+        BSVErrors, AES,
+    };
     use rand_core::{OsRng, RngCore};
     use wasm_bindgen_test::*;
     wasm_bindgen_test::wasm_bindgen_test_configure!();
@@ -71,4 +75,175 @@ mod aes_tests {
         let decrypted = AES::decrypt(key, iv, &encrypted, AESAlgorithms::AES256_CTR).unwrap();
         assert_eq!(decrypted, message)
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn aes_gcm_spec_vectors() {
+        // Test cases 1, 2, 4 and 14 from the GCM specification (McGrew & Viega)
+        let zero_nonce = [0; 12];
+
+        let encrypted = AES::encrypt_aead(&[0; 16], &zero_nonce, &[], &[], AEADAlgorithms::AES128_GCM).unwrap();
+        assert_eq!(hex::encode(&encrypted), "58e2fccefa7e3061367f1d57a4e7455a");
+
+        let encrypted = AES::encrypt_aead(&[0; 16], &zero_nonce, &[0; 16], &[], AEADAlgorithms::AES128_GCM).unwrap();
+        assert_eq!(hex::encode(&encrypted), "0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf");
+
+        let encrypted = AES::encrypt_aead(&[0; 32], &zero_nonce, &[0; 16], &[], AEADAlgorithms::AES256_GCM).unwrap();
+        assert_eq!(hex::encode(&encrypted), "cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0265b98b5d48ab919");
+
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308").unwrap();
+        let nonce = hex::decode("cafebabefacedbaddecaf888").unwrap();
+        let plaintext = hex::decode("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39").unwrap();
+        let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+
+        let encrypted = AES::encrypt_aead(&key, &nonce, &plaintext, &aad, AEADAlgorithms::AES128_GCM).unwrap();
+        assert_eq!(
+            hex::encode(&encrypted),
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e0915bc94fbc3221a5db94fae95ae7121a47"
+        );
+        assert_eq!(AES::decrypt_aead(&key, &nonce, &encrypted, &aad, AEADAlgorithms::AES128_GCM).unwrap(), plaintext);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn aes_gcm_spec_vectors_with_other_nonce_lengths() {
+        // GCM spec test cases 5 (8 byte IV) and 6 (60 byte IV)
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308").unwrap();
+        let plaintext = hex::decode("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39").unwrap();
+        let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+
+        for (nonce_hex, ciphertext_prefix, tag) in [
+            ("cafebabefacedbad", "61353b4c2806934a777ff51fa22a4755", "3612d2e79e3b0785561be14aaca2fccb"),
+            (
+                "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+                "8ce24998625615b603a033aca13fb894",
+                "619cc5aefffe0bfa462af43c1699d050",
+            ),
+        ] {
+            let nonce = hex::decode(nonce_hex).unwrap();
+
+            // One-shot GCM takes 12, 16 or 32 byte nonces, the stream API any length
+            assert!(AES::encrypt_aead(&key, &nonce, &plaintext, &aad, AEADAlgorithms::AES128_GCM).is_err());
+
+            let mut encryptor = AESEncryptor::new(&key, &nonce, &aad, AESStreamAlgorithms::AES128_GCM).unwrap();
+            let encrypted = [encryptor.update(&plaintext[..20]).unwrap(), encryptor.update(&plaintext[20..]).unwrap()].concat();
+            assert_eq!(hex::encode(&encrypted[..16]), ciphertext_prefix);
+            assert_eq!(hex::encode(encryptor.finalise().unwrap()), tag);
+
+            let mut decryptor = AESDecryptor::new(&key, &nonce, &aad, AESStreamAlgorithms::AES128_GCM).unwrap();
+            assert_eq!(decryptor.update(&encrypted).unwrap(), plaintext);
+            decryptor.finalise(&hex::decode(tag).unwrap()).unwrap();
+        }
+
+        for nonce in [vec![9; 16], vec![9; 32]].iter() {
+            let mut encryptor = AESEncryptor::new(&key, nonce, &aad, AESStreamAlgorithms::AES128_GCM).unwrap();
+            let streamed = [encryptor.update(&plaintext).unwrap(), encryptor.finalise().unwrap()].concat();
+            assert_eq!(AES::encrypt_aead(&key, nonce, &plaintext, &aad, AEADAlgorithms::AES128_GCM).unwrap(), streamed);
+        }
+
+        assert!(AES::encrypt_aead(&[0; 32], &[0; 16], &plaintext, &[], AEADAlgorithms::ChaCha20_Poly1305).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn chacha20_poly1305_rfc8439_vector() {
+        // RFC 8439 section 2.8.2
+        let key = hex::decode("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f").unwrap();
+        let nonce = hex::decode("070000004041424344454647").unwrap();
+        let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+        let encrypted = AES::encrypt_aead(&key, &nonce, plaintext, &aad, AEADAlgorithms::ChaCha20_Poly1305).unwrap();
+        assert_eq!(
+            hex::encode(&encrypted),
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691"
+        );
+        assert_eq!(AES::decrypt_aead(&key, &nonce, &encrypted, &aad, AEADAlgorithms::ChaCha20_Poly1305).unwrap(), plaintext);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn aead_rejects_tampering() {
+        for (algo, key_length) in [(AEADAlgorithms::AES128_GCM, 16), (AEADAlgorithms::AES256_GCM, 32), (AEADAlgorithms::ChaCha20_Poly1305, 32)] {
+            let mut key = vec![0; key_length];
+            OsRng.fill_bytes(&mut key);
+            let nonce = [7; 12];
+            let message = b"Wallet backup, do not share";
+
+            let encrypted = AES::encrypt_aead(&key, &nonce, message, b"v1", algo).unwrap();
+            assert_eq!(encrypted.len(), message.len() + 16);
+            assert_eq!(AES::decrypt_aead(&key, &nonce, &encrypted, b"v1", algo).unwrap(), message);
+
+            let mut modified = encrypted.clone();
+            modified[0] ^= 1;
+            assert!(matches!(AES::decrypt_aead(&key, &nonce, &modified, b"v1", algo), Err(BSVErrors::InvalidAuthenticationTag)));
+
+            let mut modified_tag = encrypted.clone();
+            *modified_tag.last_mut().unwrap() ^= 1;
+            assert!(matches!(AES::decrypt_aead(&key, &nonce, &modified_tag, b"v1", algo), Err(BSVErrors::InvalidAuthenticationTag)));
+
+            assert!(matches!(AES::decrypt_aead(&key, &nonce, &encrypted, b"v2", algo), Err(BSVErrors::InvalidAuthenticationTag)));
+            assert!(matches!(AES::decrypt_aead(&key, &[8; 12], &encrypted, b"v1", algo), Err(BSVErrors::InvalidAuthenticationTag)));
+            assert!(matches!(AES::decrypt_aead(&key, &nonce, &encrypted[..15], b"v1", algo), Err(BSVErrors::InvalidAuthenticationTag)));
+
+            assert!(AES::encrypt_aead(&key, &[], message, &[], algo).is_err());
+            assert!(AES::encrypt_aead(&key[..8], &nonce, message, &[], algo).is_err());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn stream_matches_one_shot_encryption() {
+        let mut message = vec![0; 1000];
+        OsRng.fill_bytes(&mut message);
+        let key_128 = [3; 16];
+        let key_256 = [4; 32];
+        let iv = [5; 16];
+        let nonce = [6; 12];
+
+        for chunk_size in [1, 7, 16, 33, 1000] {
+            let mut encryptor = AESEncryptor::new(&key_128, &iv, &[], AESStreamAlgorithms::AES128_CTR).unwrap();
+            let ciphertext: Vec<u8> = message.chunks(chunk_size).flat_map(|x| encryptor.update(x).unwrap()).collect();
+            assert!(encryptor.finalise().unwrap().is_empty());
+            assert_eq!(ciphertext, AES::encrypt(&key_128, &iv, &message, AESAlgorithms::AES128_CTR).unwrap());
+
+            let mut encryptor = AESEncryptor::new(&key_256, &nonce, b"header", AESStreamAlgorithms::AES256_GCM).unwrap();
+            let mut ciphertext: Vec<u8> = message.chunks(chunk_size).flat_map(|x| encryptor.update(x).unwrap()).collect();
+            ciphertext.extend_from_slice(&encryptor.finalise().unwrap());
+            assert_eq!(ciphertext, AES::encrypt_aead(&key_256, &nonce, &message, b"header", AEADAlgorithms::AES256_GCM).unwrap());
+
+            let (body, tag) = ciphertext.split_at(message.len());
+            let mut decryptor = AESDecryptor::new(&key_256, &nonce, b"header", AESStreamAlgorithms::AES256_GCM).unwrap();
+            let plaintext: Vec<u8> = body.chunks(chunk_size).flat_map(|x| decryptor.update(x).unwrap()).collect();
+            decryptor.finalise(tag).unwrap();
+            assert_eq!(plaintext, message);
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn stream_decryptor_checks_tag() {
+        let key = [1; 16];
+        let nonce = [2; 12];
+        let mut encryptor = AESEncryptor::new(&key, &nonce, &[], AESStreamAlgorithms::AES128_GCM).unwrap();
+        let mut ciphertext = encryptor.update(b"Large file contents").unwrap();
+        let tag = encryptor.finalise().unwrap();
+        assert!(matches!(encryptor.update(b"more"), Err(BSVErrors::AESStreamError(_))));
+        assert!(encryptor.finalise().is_err());
+
+        ciphertext[0] ^= 1;
+        let mut decryptor = AESDecryptor::new(&key, &nonce, &[], AESStreamAlgorithms::AES128_GCM).unwrap();
+        decryptor.update(&ciphertext).unwrap();
+        assert!(matches!(decryptor.finalise(&tag), Err(BSVErrors::InvalidAuthenticationTag)));
+
+        ciphertext[0] ^= 1;
+        let mut decryptor = AESDecryptor::new(&key, &nonce, &[], AESStreamAlgorithms::AES128_GCM).unwrap();
+        decryptor.update(&ciphertext).unwrap();
+        assert!(matches!(decryptor.finalise(&tag[..8]), Err(BSVErrors::InvalidAuthenticationTag)));
+
+        assert!(AESEncryptor::new(&key, &[0; 16], b"aad", AESStreamAlgorithms::AES128_CTR).is_err());
+        assert!(AESEncryptor::new(&key, &[0; 8], &[], AESStreamAlgorithms::AES128_CTR).is_err());
+        assert!(AESEncryptor::new(&key[..8], &nonce, &[], AESStreamAlgorithms::AES128_GCM).is_err());
+        assert!(AESEncryptor::new(&key, &[], &[], AESStreamAlgorithms::AES128_GCM).is_err());
+    }
 }
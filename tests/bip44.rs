@@ -0,0 +1,93 @@
+#[cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
+#[cfg(test)]
+mod bip44_tests {
+    use bsv_wasm::{keypair::*, P2PKHAddress};
+    extern crate wasm_bindgen_test;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    const MASTER_XPRIV: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    fn address_at(path: &str) -> String {
+        let xpriv = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        xpriv.derive_from_path(path).unwrap().get_public_key().to_p2pkh_address().unwrap().to_address_string().unwrap()
+    }
+
+    #[test]
+    fn account_addresses_match_full_path_derivation() {
+        let master = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        let account = BIP44Account::from_xpriv(&master, 0, None).unwrap();
+
+        assert_eq!(account.get_coin_type(), BSV_COIN_TYPE);
+        assert_eq!(account.get_receive_address(0).unwrap().to_address_string().unwrap(), address_at("m/44'/236'/0'/0/0"));
+        assert_eq!(account.get_receive_address(7).unwrap().to_address_string().unwrap(), address_at("m/44'/236'/0'/0/7"));
+        assert_eq!(account.get_change_address(3).unwrap().to_address_string().unwrap(), address_at("m/44'/236'/0'/1/3"));
+    }
+
+    #[test]
+    fn custom_coin_type_and_account() {
+        let master = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        let account = BIP44Account::from_xpriv(&master, 2, Some(0)).unwrap();
+
+        assert_eq!(account.get_receive_address(1).unwrap().to_address_string().unwrap(), address_at("m/44'/0'/2'/0/1"));
+    }
+
+    #[test]
+    fn watch_only_account_from_xpub() {
+        let master = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        let account = BIP44Account::from_xpriv(&master, 0, None).unwrap();
+
+        let xpub = ExtendedPublicKey::from_string(&account.get_account_xpub().to_string().unwrap()).unwrap();
+        let watch_only = BIP44Account::from_account_xpub(&xpub, 0, None).unwrap();
+
+        assert_eq!(watch_only.get_change_address(9).unwrap(), account.get_change_address(9).unwrap());
+
+        // Non account level keys are rejected
+        assert!(BIP44Account::from_account_xpub(&ExtendedPublicKey::from_xpriv(&master), 0, None).is_err());
+    }
+
+    #[test]
+    fn watch_only_account_must_match_xpub_account_index() {
+        let master = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        let account_5 = BIP44Account::from_xpriv(&master, 5, None).unwrap();
+        let xpub = account_5.get_account_xpub();
+
+        assert!(BIP44Account::from_account_xpub(&xpub, 0, None).is_err());
+        assert!(BIP44Account::from_account_xpub(&xpub, 5, None).is_ok());
+
+        // The account index is taken from the xpub, which is always hardened
+        let unhardened = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap().derive_from_path("m/44'/236'/5").unwrap();
+        assert!(BIP44Account::from_account_xpub(&ExtendedPublicKey::from_xpriv(&unhardened), 5, None).is_err());
+    }
+
+    #[test]
+    fn scan_respects_gap_limit() {
+        let master = ExtendedPrivateKey::from_string(MASTER_XPRIV).unwrap();
+        let account = BIP44Account::from_xpriv(&master, 0, None).unwrap();
+
+        let used: Vec<P2PKHAddress> = [0, 1, 4, 9].iter().map(|i| account.get_receive_address(*i).unwrap()).collect();
+        // Index 30 is beyond a gap of 20 after index 9 so must not be found
+        let unreachable = account.get_receive_address(30).unwrap();
+
+        let mut checked = 0;
+        let found = account
+            .scan(
+                BIP44Chain::External,
+                |address| {
+                    checked += 1;
+                    Ok(used.contains(address) || *address == unreachable)
+                },
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(found, vec![0, 1, 4, 9]);
+        assert_eq!(checked, 10 + DEFAULT_GAP_LIMIT);
+
+        let next = account.next_unused_index(BIP44Chain::External, |address| Ok(used.contains(address)), Some(5)).unwrap();
+        assert_eq!(next, 10);
+
+        let change = account.scan(BIP44Chain::Internal, |address| Ok(used.contains(address)), Some(5)).unwrap();
+        assert!(change.is_empty());
+    }
+}
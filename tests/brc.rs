@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod brc_tests {
+    use bsv_wasm::*;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc42_derivation_vectors() {
+        // From the BRC-42 private and public key derivation test vectors
+        let sender_pub_key = PublicKey::from_hex("033f9160df035156f1c48e75eae99914fa1a1546bec19781e8eddb900200bff9d1").unwrap();
+        let recipient_priv_key = PrivateKey::from_hex("6a1751169c111b4667a6539ee1be6b7cd9f6e9c8fe011a5f2fe31e03a15e0ede").unwrap();
+        assert_eq!(
+            recipient_priv_key.derive_child(&sender_pub_key, "f3WCaUmnN9U=").unwrap().to_hex(),
+            "761656715bbfa172f8f9f58f5af95d9d0dfd69014cfdcacc9a245a10ff8893ef"
+        );
+
+        let sender_priv_key = PrivateKey::from_hex("583755110a8c059de5cd81b8a04e1be884c46083ade3f779c1e022f6f89da94c").unwrap();
+        let recipient_pub_key = PublicKey::from_hex("02c0c1e1a1f7d247827d1bcf399f0ef2deef7695c322fd91a01a91378f101b6ffc").unwrap();
+        assert_eq!(
+            recipient_pub_key.derive_child(&sender_priv_key, "IBioA4D/OaE=").unwrap().to_hex().unwrap(),
+            "03c1bf5baadee39721ae8c9882b3cf324f0bf3b9eb3fc1b8af8089ca7a7c2e669f"
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc42_child_keys_match() {
+        let alice = PrivateKey::from_random();
+        let bob = PrivateKey::from_random();
+
+        let bob_child_priv_key = bob.derive_child(&alice.get_public_key().unwrap(), "2-3241645161d8-1").unwrap();
+        let bob_child_pub_key = bob.get_public_key().unwrap().derive_child(&alice, "2-3241645161d8-1").unwrap();
+        assert_eq!(bob_child_priv_key.get_public_key().unwrap(), bob_child_pub_key);
+
+        let other_child_pub_key = bob.get_public_key().unwrap().derive_child(&alice, "2-3241645161d8-2").unwrap();
+        assert_ne!(other_child_pub_key, bob_child_pub_key);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc2_encrypt_decrypt() {
+        let alice = PrivateKey::from_random();
+        let bob = PrivateKey::from_random();
+        let invoice_number = "2-tests brc2-1";
+
+        let alice_key = BRC2::derive_symmetric_key(&alice, &bob.get_public_key().unwrap(), invoice_number).unwrap();
+        let bob_key = BRC2::derive_symmetric_key(&bob, &alice.get_public_key().unwrap(), invoice_number).unwrap();
+        assert_eq!(alice_key.len(), 32);
+        assert_eq!(alice_key, bob_key);
+
+        let ciphertext = BRC2::encrypt(b"BRC-2 data", &alice, &bob.get_public_key().unwrap(), invoice_number).unwrap();
+        assert_eq!(ciphertext.len(), 32 + 10 + 16);
+        assert_eq!(BRC2::decrypt(&ciphertext, &bob, &alice.get_public_key().unwrap(), invoice_number).unwrap(), b"BRC-2 data");
+
+        // Also decryptable with the symmetric key as AES-256-GCM with the 32 byte IV
+        assert_eq!(
+            AES::decrypt_aead(&bob_key, &ciphertext[..32], &ciphertext[32..], &[], AEADAlgorithms::AES256_GCM).unwrap(),
+            b"BRC-2 data"
+        );
+
+        assert!(BRC2::decrypt(&ciphertext, &bob, &alice.get_public_key().unwrap(), "2-tests brc2-2").is_err());
+        assert!(BRC2::decrypt(&ciphertext, &PrivateKey::from_random(), &alice.get_public_key().unwrap(), invoice_number).is_err());
+        assert!(BRC2::decrypt(&ciphertext[..31], &bob, &alice.get_public_key().unwrap(), invoice_number).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc78_encrypted_message() {
+        let sender = PrivateKey::from_random();
+        let recipient = PrivateKey::from_random();
+        let message = [1, 2, 4, 8, 16, 32];
+
+        let encrypted = EncryptedMessage::encrypt(&message, &sender, &recipient.get_public_key().unwrap()).unwrap();
+        assert_eq!(&encrypted[..4], &[0x42, 0x42, 0x10, 0x33]);
+        assert_eq!(&encrypted[4..37], sender.get_public_key().unwrap().to_bytes().unwrap().as_slice());
+        assert_eq!(&encrypted[37..70], recipient.get_public_key().unwrap().to_bytes().unwrap().as_slice());
+
+        assert_eq!(EncryptedMessage::decrypt(&encrypted, &recipient).unwrap(), message);
+        assert!(matches!(EncryptedMessage::decrypt(&encrypted, &sender), Err(BSVErrors::BRCError(_))));
+
+        let mut bad_version = encrypted.clone();
+        bad_version[0] = 0x01;
+        assert!(matches!(EncryptedMessage::decrypt(&bad_version, &recipient), Err(BSVErrors::BRCError(_))));
+
+        let mut modified = encrypted.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(matches!(EncryptedMessage::decrypt(&modified, &recipient), Err(BSVErrors::InvalidAuthenticationTag)));
+
+        assert!(EncryptedMessage::decrypt(&encrypted[..80], &recipient).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc77_signed_message_for_verifier() {
+        let signer = PrivateKey::from_random();
+        let verifier = PrivateKey::from_random();
+        let message = b"Signed for one verifier";
+
+        let signed = SignedMessage::sign(message, &signer, &verifier.get_public_key().unwrap()).unwrap();
+        assert_eq!(&signed[..4], &[0x42, 0x42, 0x33, 0x01]);
+        assert_eq!(&signed[37..70], verifier.get_public_key().unwrap().to_bytes().unwrap().as_slice());
+
+        assert!(SignedMessage::verify(message, &signed, &verifier).unwrap());
+        assert!(SignedMessage::verify(b"Another message", &signed, &verifier).is_err());
+        assert!(matches!(SignedMessage::verify(message, &signed, &signer), Err(BSVErrors::BRCError(_))));
+        assert!(matches!(SignedMessage::verify_for_anyone(message, &signed), Err(BSVErrors::BRCError(_))));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc77_signed_message_for_anyone() {
+        let signer = PrivateKey::from_random();
+        let message = b"Signed for anyone";
+
+        let signed = SignedMessage::sign_for_anyone(message, &signer).unwrap();
+        assert_eq!(signed[37], 0);
+
+        assert!(SignedMessage::verify_for_anyone(message, &signed).unwrap());
+        assert!(SignedMessage::verify(message, &signed, &PrivateKey::from_random()).unwrap());
+        assert!(SignedMessage::verify_for_anyone(b"Another message", &signed).is_err());
+
+        assert!(SignedMessage::verify_for_anyone(message, &signed[..40]).is_err());
+        assert!(SignedMessage::verify_for_anyone(message, &signed[..20]).is_err());
+    }
+
+    // Produced offline by an independent implementation of the BRC-42, BRC-2, BRC-77 and BRC-78 specs,
+    // with the private keys 15 and 21 and fixed IVs, key IDs and signing nonce
+    #[test]
+    #[wasm_bindgen_test]
+    fn brc_fixed_vectors() {
+        let sender = PrivateKey::from_hex("000000000000000000000000000000000000000000000000000000000000000f").unwrap();
+        let recipient = PrivateKey::from_hex("0000000000000000000000000000000000000000000000000000000000000015").unwrap();
+        let message = [1, 2, 4, 8, 16, 32];
+
+        let brc2 = hex::decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1fa12c153c14699024ef57b5df47218efe3fdb8778fd16").unwrap();
+        assert_eq!(BRC2::decrypt(&brc2, &recipient, &sender.get_public_key().unwrap(), "2-brc2 test vector-1").unwrap(), message);
+        assert!(BRC2::decrypt(&brc2, &recipient, &sender.get_public_key().unwrap(), "2-brc2 test vector-2").is_err());
+
+        let brc78 = hex::decode("4242103302d7924d4f7d43ea965a465ae3095ff41131e5946f3c85f79e44adbcf8e27e080e02352bbf4a4cdd12564f93fa332ce333301d9ad40271f8107181340aef25be59d5202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f666d4cc103ad1209cfb3ebcc4451a33c02996c9792ee").unwrap();
+        assert_eq!(EncryptedMessage::decrypt(&brc78, &recipient).unwrap(), message);
+
+        let brc77 = hex::decode("4242330102d7924d4f7d43ea965a465ae3095ff41131e5946f3c85f79e44adbcf8e27e080e02352bbf4a4cdd12564f93fa332ce333301d9ad40271f8107181340aef25be59d5606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f3044022031a61d8714d575ce8221c8c5857bf18eba90ab63d5167541387fe8aaa53e22e90220630aad2f8c3b6dd406d1287cbe1bab5f7904fef7262107d4db3042eb6fcbd48c").unwrap();
+        assert!(SignedMessage::verify(&message, &brc77, &recipient).unwrap());
+        assert!(SignedMessage::verify(&[1, 2, 4, 8, 16, 64], &brc77, &recipient).is_err());
+
+        let brc77_for_anyone = hex::decode("4242330102d7924d4f7d43ea965a465ae3095ff41131e5946f3c85f79e44adbcf8e27e080e00606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f3044022031a61d8714d575ce8221c8c5857bf18eba90ab63d5167541387fe8aaa53e22e9022028dd5c6d9569ff3c17578bb5c11c0917f0393f9e471bfc1e12602e14cf219706").unwrap();
+        assert!(SignedMessage::verify_for_anyone(&message, &brc77_for_anyone).unwrap());
+    }
+}
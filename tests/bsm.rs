@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod bitcoin_signed_message_tests {
 
-    use bsv_wasm::{PrivateKey, BSM};
+    use bsv_wasm::{P2PKHAddress, PrivateKey, PublicKey, Signature, BSM};
 
     use wasm_bindgen_test::*;
     wasm_bindgen_test::wasm_bindgen_test_configure!();
@@ -22,4 +22,96 @@ mod bitcoin_signed_message_tests {
 
         assert!(verified, "Message is not verified")
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sign_and_verify_base64_message() {
+        let priv_key = PrivateKey::from_wif("L17y3TE8AgM6fiWFP4HsbaLnvuBJsQcFKYRoJoZULpTzeTCr2nEC").unwrap();
+        let address = priv_key.get_public_key().unwrap().to_p2pkh_address().unwrap();
+        let message = b"Hello Bitcoin!";
+
+        let signature = BSM::sign_message_base64(&priv_key, message).unwrap();
+        let compact_bytes = base64::decode(&signature).unwrap();
+        assert_eq!(compact_bytes.len(), 65);
+        assert!((31..=34).contains(&compact_bytes[0]), "Compressed key uses header 31-34");
+
+        assert!(BSM::verify_message_base64(message, &signature, &address).unwrap());
+        assert!(!BSM::is_valid_message_base64(b"Hello Bitcoin?", &signature, &address));
+
+        // Same signature as the Signature based API
+        let der_signature = BSM::sign_message(&priv_key, message).unwrap();
+        assert_eq!(der_signature.to_compact_base64(), signature);
+        assert_eq!(Signature::from_compact_base64(&signature).unwrap().to_der_bytes(), der_signature.to_der_bytes());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sign_and_verify_base64_message_uncompressed() {
+        let priv_key = PrivateKey::from_wif("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ").unwrap();
+        let uncompressed_address = priv_key.get_public_key().unwrap().to_p2pkh_address().unwrap();
+        let compressed_address = priv_key.get_public_key().unwrap().to_compressed().unwrap().to_p2pkh_address().unwrap();
+        let message = b"Hello Bitcoin!";
+
+        let signature = BSM::sign_message_base64(&priv_key, message).unwrap();
+        assert!((27..=30).contains(&base64::decode(&signature).unwrap()[0]), "Uncompressed key uses header 27-30");
+
+        assert!(BSM::verify_message_base64(message, &signature, &uncompressed_address).unwrap());
+        assert!(!BSM::is_valid_message_base64(message, &signature, &compressed_address));
+    }
+
+    // (key, address, message, signature) from Electrum's test_msg_signing (compressed and uncompressed WIF) and rust-bitcoin's
+    // message signature tests, whose key and public key are given in base64 there, both signing with RFC 6979 nonces
+    #[test]
+    #[wasm_bindgen_test]
+    fn external_base64_message_vectors() {
+        let rust_bitcoin_key = PrivateKey::from_hex("52e3860ec7cb3ebe0720c2905f48a98c97916a3d60782ab7c8f505d8238fe4c1").unwrap();
+        let rust_bitcoin_address = PublicKey::from_hex("0351537cc127b4fa40b72dea904a34ab60dcd45132708d746b78e6c04172f90afa")
+            .unwrap()
+            .to_p2pkh_address()
+            .unwrap();
+
+        let vectors = [
+            (
+                PrivateKey::from_wif("L1TnU2zbNaAqMoVh65Cyvmcjzbrj41Gs9iTLcWbpJCMynXuap6UN").unwrap(),
+                P2PKHAddress::from_string("15hETetDmcXm1mM4sEf7U2KXC9hDHFMSzz").unwrap(),
+                "Chancellor on brink of second bailout for banks",
+                "H/9jMOnj4MFbH3d7t4yCQ9i7DgZU/VZ278w3+ySv2F4yIsdqjsc5ng3kmN8OZAThgyfCZOQxZCWza9V5XzlVY0Y=",
+            ),
+            (
+                PrivateKey::from_wif("5Hxn5C4SQuiV6e62A1MtZmbSeQyrLFhu5uYks62pU5VBUygK2KD").unwrap(),
+                P2PKHAddress::from_string("1GPHVTY8UD9my6jyP4tb2TYJwUbDetyNC6").unwrap(),
+                "Electrum",
+                "G84dmJ8TKIDKMT9qBRhpX2sNmR0y5t+POcYnFFJCs66lJmAs3T8A6Sbpx7KA6yTQ9djQMabwQXRrDomOkIKGn18=",
+            ),
+            (
+                rust_bitcoin_key,
+                rust_bitcoin_address,
+                "rust-bitcoin MessageSignature test",
+                "IAM2qX24tYx/bdBTIgVLhD8QEAjrPlJpmjB4nZHdRYGIBa4DmVulAcwjPnWe6Q5iEwXH6F0pUCJP/ZeHPWS1h1o=",
+            ),
+        ];
+
+        for (priv_key, address, message, signature) in vectors.iter() {
+            assert_eq!(&priv_key.get_public_key().unwrap().to_p2pkh_address().unwrap(), address);
+            assert!(BSM::verify_message_base64(message.as_bytes(), signature, address).unwrap(), "{}", message);
+            assert!(!BSM::is_valid_message_base64(b"a different message from what was signed", signature, address));
+            assert_eq!(&BSM::sign_message_base64(priv_key, message.as_bytes()).unwrap(), signature);
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn verify_base64_message_rejects_malformed_signatures() {
+        let priv_key = PrivateKey::from_wif("L17y3TE8AgM6fiWFP4HsbaLnvuBJsQcFKYRoJoZULpTzeTCr2nEC").unwrap();
+        let address = priv_key.get_public_key().unwrap().to_p2pkh_address().unwrap();
+        let message = b"Hello Bitcoin!";
+
+        let mut compact_bytes = base64::decode(&BSM::sign_message_base64(&priv_key, message).unwrap()).unwrap();
+
+        assert!(BSM::verify_message_base64(message, "not base64!", &address).is_err());
+        assert!(BSM::verify_message_base64(message, &base64::encode(&compact_bytes[..64]), &address).is_err());
+
+        compact_bytes[0] = 35;
+        assert!(BSM::verify_message_base64(message, &base64::encode(&compact_bytes), &address).is_err());
+    }
 }
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod ecdsa_batch_tests {
+    use bsv_wasm::*;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    fn signed_tuples(count: usize) -> (Vec<[u8; 32]>, Vec<PublicKey>, Vec<Signature>) {
+        let mut digests = vec![];
+        let mut pub_keys = vec![];
+        let mut signatures = vec![];
+
+        for i in 0..count {
+            let private_key = PrivateKey::from_random();
+            let message = format!("message {}", i);
+
+            let mut digest = [0; 32];
+            digest.copy_from_slice(&Hash::sha_256d(message.as_bytes()).to_bytes());
+
+            digests.push(digest);
+            pub_keys.push(PublicKey::from_private_key(&private_key));
+            signatures.push(ECDSA::sign_with_deterministic_k(&private_key, message.as_bytes(), SigningHash::Sha256d, false).unwrap());
+        }
+
+        (digests, pub_keys, signatures)
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn verify_batch_all_valid() {
+        let (digests, pub_keys, signatures) = signed_tuples(10);
+
+        assert_eq!(ECDSA::verify_batch(&digests, &pub_keys, &signatures).unwrap(), Vec::<usize>::new());
+        assert_eq!(ECDSA::verify_batch(&[], &[], &[]).unwrap(), Vec::<usize>::new());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn verify_batch_reports_failed_indices() {
+        // Enough signatures to be split across threads
+        let (mut digests, mut pub_keys, signatures) = signed_tuples(300);
+
+        digests[3][0] ^= 1;
+        pub_keys[150] = pub_keys[151].clone();
+        digests[299] = digests[0];
+
+        assert_eq!(ECDSA::verify_batch(&digests, &pub_keys, &signatures).unwrap(), vec![3, 150, 299]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn verify_batch_accepts_high_s() {
+        let private_key = PrivateKey::from_hex("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262").unwrap();
+        let k = PrivateKey::from_hex("4200000000000000000000000000000000000000000000000000000000000005").unwrap();
+        let high_s = ECDSA::sign_with_k_unnormalised(&private_key, &k, b"Hello Bitcoin", SigningHash::Sha256d).unwrap();
+        assert!(!high_s.is_low_s());
+
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&Hash::sha_256d(b"Hello Bitcoin").to_bytes());
+
+        assert!(ECDSA::verify_batch(&[digest], &[PublicKey::from_private_key(&private_key)], &[high_s]).unwrap().is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn verify_batch_rejects_mismatched_lengths() {
+        let (digests, pub_keys, signatures) = signed_tuples(3);
+
+        assert!(ECDSA::verify_batch(&digests[..2], &pub_keys, &signatures).is_err());
+        assert!(ECDSA::verify_batch(&digests, &pub_keys, &signatures[..1]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn signature_batch_matches_verify_batch() {
+        let (mut digests, pub_keys, signatures) = signed_tuples(5);
+        digests[2][31] ^= 0xff;
+
+        let mut batch = SignatureBatch::new();
+        assert!(batch.is_empty());
+        for i in 0..5 {
+            batch.add(&digests[i], &pub_keys[i], &signatures[i]).unwrap();
+        }
+        assert_eq!(batch.get_length(), 5);
+
+        assert!(batch.add(&digests[0][..31], &pub_keys[0], &signatures[0]).is_err());
+        assert_eq!(batch.get_length(), 5);
+
+        assert_eq!(batch.verify(), vec![2]);
+        assert_eq!(batch.verify(), ECDSA::verify_batch(&digests, &pub_keys, &signatures).unwrap());
+    }
+}
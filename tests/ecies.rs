@@ -1,7 +1,7 @@
 #[cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
 #[cfg(test)]
 mod ecies_tests {
-    use bsv_wasm::{ECIESCiphertext, PrivateKey, ECIES};
+    use bsv_wasm::{ECIESCiphertext, ECIESEnvelope, ECIESMode, ECIESStreamDecryptor, ECIESStreamEncryptor, PrivateKey, ECIES};
     use std::io::Read;
     use wasm_bindgen_test::*;
     wasm_bindgen_test::wasm_bindgen_test_configure!();
@@ -149,4 +149,248 @@ mod ecies_tests {
 
         assert_eq!(plaintext, message);
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn cipher_keys_equality() {
+        let alice = PrivateKey::from_random();
+        let bob = PrivateKey::from_random();
+
+        let alice_keys = ECIES::derive_cipher_keys(&alice, &bob.get_public_key().unwrap()).unwrap();
+        let bob_keys = ECIES::derive_cipher_keys(&bob, &alice.get_public_key().unwrap()).unwrap();
+        let eve_keys = ECIES::derive_cipher_keys(&PrivateKey::from_random(), &bob.get_public_key().unwrap()).unwrap();
+
+        assert!(alice_keys.equals(&bob_keys));
+        assert!(!alice_keys.equals(&eve_keys));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn electrum_encrypt_decrypt_base64() {
+        let bob_priv_key = PrivateKey::from_random();
+        let message = b"Hello, Electrum.";
+
+        let encrypted = ECIES::encrypt_electrum(message, &bob_priv_key.get_public_key().unwrap()).unwrap();
+        assert!(encrypted.extract_public_key().is_ok());
+
+        let received_msg = ECIESCiphertext::from_base64(&encrypted.to_base64(), ECIESMode::Electrum).unwrap();
+        assert_eq!(received_msg.to_bytes(), encrypted.to_bytes());
+
+        let plaintext = ECIES::decrypt_electrum(&received_msg, &bob_priv_key).unwrap();
+        assert_eq!(plaintext, message);
+
+        let eve_priv_key = PrivateKey::from_random();
+        assert!(ECIES::decrypt_electrum(&received_msg, &eve_priv_key).is_err());
+    }
+
+    // Produced offline by an independent implementation of Electrum's ECPubkey.encrypt_message (ecc.py) with a fixed ephemeral key
+    #[test]
+    #[wasm_bindgen_test]
+    fn electrum_fixed_vector() {
+        let recipient_priv_key = PrivateKey::from_hex("1c6b3e3b8f9e0d3b4b0f5f8d8c9a3a2e7a1f0f3c5d6e7f8091a2b3c4d5e6f708").unwrap();
+        let ephemeral_priv_key = PrivateKey::from_hex("5f2c3b9e8d7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c").unwrap();
+        let message = b"Hello, Electrum SV.";
+        let electrum_ciphertext = "QklFMQN+iX+X0s8D8JPuZC+69A6JGSGxQUUobbzHsDF2q8bbgxJapIF/068VB2HFQ/Y7O1lzsvBVqUZ9IVjHyeppaN7FpiI9ImKzyH1BGI675pZmmV/PneWncChYN9JAb0hSG5o=";
+
+        let ciphertext = ECIESCiphertext::from_base64(electrum_ciphertext, ECIESMode::Electrum).unwrap();
+        assert_eq!(
+            ciphertext.extract_public_key().unwrap().to_hex().unwrap(),
+            "037e897f97d2cf03f093ee642fbaf40e891921b14145286dbcc7b03176abc6db83"
+        );
+        assert_eq!(ECIES::decrypt_electrum(&ciphertext, &recipient_priv_key).unwrap(), message);
+
+        // Encrypting with the same ephemeral key reproduces it byte for byte
+        let encrypted = ECIES::encrypt(message, &ephemeral_priv_key, &recipient_priv_key.get_public_key().unwrap(), false).unwrap();
+        assert_eq!(encrypted.to_base64(), electrum_ciphertext);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn electrum_decrypts_bie1_with_ephemeral_key() {
+        let bob_priv_key = PrivateKey::from_random();
+        let message = b"Hello, Bitcoin.";
+
+        let encrypted = ECIES::encrypt_with_ephemeral_private_key(message, &bob_priv_key.get_public_key().unwrap()).unwrap();
+        let received_msg = ECIESCiphertext::from_bytes_with_mode(&encrypted.to_bytes(), ECIESMode::Electrum).unwrap();
+
+        assert_eq!(ECIES::decrypt_electrum(&received_msg, &bob_priv_key).unwrap(), message);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn bie1_mode_detects_public_key() {
+        let alice_private_key = PrivateKey::from_random();
+        let bob_priv_key = PrivateKey::from_random();
+        let bob_pub_key = bob_priv_key.get_public_key().unwrap();
+
+        for message in [&b""[..], b"Hello, Bitcoin.", b"Exactly sixteen!", b"A message longer than a couple of AES blocks"] {
+            let with_key = ECIES::encrypt(message, &alice_private_key, &bob_pub_key, false).unwrap();
+            let parsed = ECIESCiphertext::from_bytes_with_mode(&with_key.to_bytes(), ECIESMode::BIE1).unwrap();
+            assert!(parsed.extract_public_key().is_ok());
+            assert_eq!(ECIES::decrypt(&parsed, &bob_priv_key, &alice_private_key.get_public_key().unwrap()).unwrap(), message);
+
+            let without_key = ECIES::encrypt(message, &alice_private_key, &bob_pub_key, true).unwrap();
+            let parsed = ECIESCiphertext::from_bytes_with_mode(&without_key.to_bytes(), ECIESMode::BIE1).unwrap();
+            assert!(parsed.extract_public_key().is_err());
+            assert_eq!(ECIES::decrypt(&parsed, &bob_priv_key, &alice_private_key.get_public_key().unwrap()).unwrap(), message);
+            assert!(ECIES::decrypt_electrum(&parsed, &bob_priv_key).is_err());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn malformed_ciphertext_is_an_error() {
+        let bob_pub_key = PrivateKey::from_random().get_public_key().unwrap();
+        let bytes = ECIES::encrypt_electrum(b"Hello, Bitcoin.", &bob_pub_key).unwrap().to_bytes();
+
+        assert!(ECIESCiphertext::from_bytes(&bytes[..40], true).is_err());
+        assert!(ECIESCiphertext::from_bytes(&[], false).is_err());
+        assert!(ECIESCiphertext::from_bytes_with_mode(&bytes[..60], ECIESMode::Electrum).is_err());
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(ECIESCiphertext::from_bytes_with_mode(&bad_magic, ECIESMode::Electrum).is_err());
+
+        assert!(ECIESCiphertext::from_base64("not base64!", ECIESMode::Electrum).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn envelope_decrypts_for_every_recipient() {
+        let members: Vec<PrivateKey> = (0..5).map(|_| PrivateKey::from_random()).collect();
+        let pub_keys: Vec<_> = members.iter().map(|x| x.get_public_key().unwrap()).collect();
+        let document = b"Quarterly accounts, members only".repeat(100);
+
+        let envelope = ECIESEnvelope::encrypt(&document, &pub_keys).unwrap();
+        assert_eq!(envelope.get_recipient_count(), 5);
+
+        let bytes = envelope.to_bytes().unwrap();
+        // Payload is only encrypted once
+        assert!(bytes.len() < document.len() * 2);
+
+        let received = ECIESEnvelope::from_bytes(&bytes).unwrap();
+        assert_eq!(received.to_bytes().unwrap(), bytes);
+        for member in &members {
+            assert!(received.has_recipient(&member.get_public_key().unwrap()).unwrap());
+            assert_eq!(received.decrypt(member).unwrap(), document);
+        }
+
+        let outsider = PrivateKey::from_random();
+        assert!(!received.has_recipient(&outsider.get_public_key().unwrap()).unwrap());
+        assert!(received.decrypt(&outsider).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn envelope_add_and_remove_recipients() {
+        let alice = PrivateKey::from_random();
+        let bob = PrivateKey::from_random();
+        let carol = PrivateKey::from_random();
+
+        // Duplicate recipients are only wrapped once
+        let mut envelope = ECIESEnvelope::encrypt(b"Shared notes", &[alice.get_public_key().unwrap(), alice.get_public_key().unwrap()]).unwrap();
+        assert_eq!(envelope.get_recipient_count(), 1);
+
+        assert!(envelope.add_recipient(&bob, &carol.get_public_key().unwrap()).is_err());
+        envelope.add_recipient(&alice, &bob.get_public_key().unwrap()).unwrap();
+        assert_eq!(envelope.get_recipient_count(), 2);
+        assert_eq!(envelope.decrypt(&bob).unwrap(), b"Shared notes");
+
+        assert!(envelope.remove_recipient(&alice.get_public_key().unwrap()).unwrap());
+        assert!(!envelope.remove_recipient(&carol.get_public_key().unwrap()).unwrap());
+        assert!(envelope.decrypt(&alice).is_err());
+        assert_eq!(envelope.decrypt(&bob).unwrap(), b"Shared notes");
+
+        assert!(ECIESEnvelope::encrypt(b"Nobody", &[]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn envelope_rejects_malformed_bytes() {
+        let alice = PrivateKey::from_random();
+        let bytes = ECIESEnvelope::encrypt(b"Shared notes", &[alice.get_public_key().unwrap()]).unwrap().to_bytes().unwrap();
+
+        for length in [0, 3, 5, 20, 30, 140] {
+            assert!(ECIESEnvelope::from_bytes(&bytes[..length]).is_err());
+        }
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(ECIESEnvelope::from_bytes(&bad_magic).is_err());
+
+        let mut modified = bytes.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(ECIESEnvelope::from_bytes(&modified).unwrap().decrypt(&alice).is_err());
+
+        let mut modified_key = bytes;
+        modified_key[30] ^= 1;
+        assert!(ECIESEnvelope::from_bytes(&modified_key).unwrap().decrypt(&alice).is_err());
+    }
+
+    fn encrypt_stream(message: &[u8], recipient: &PrivateKey, chunk_size: Option<u32>, write_size: usize) -> Vec<u8> {
+        let mut encryptor = ECIESStreamEncryptor::new(&recipient.get_public_key().unwrap(), chunk_size).unwrap();
+        let mut stream: Vec<u8> = message.chunks(write_size).flat_map(|x| encryptor.update(x).unwrap()).collect();
+        stream.extend_from_slice(&encryptor.finalise().unwrap());
+        stream
+    }
+
+    fn decrypt_stream(stream: &[u8], recipient: &PrivateKey, read_size: usize) -> Result<Vec<u8>, bsv_wasm::BSVErrors> {
+        let mut decryptor = ECIESStreamDecryptor::new(recipient);
+        let mut plaintext = vec![];
+        for chunk in stream.chunks(read_size) {
+            plaintext.extend_from_slice(&decryptor.update(chunk)?);
+        }
+        plaintext.extend_from_slice(&decryptor.finalise()?);
+        Ok(plaintext)
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn stream_encrypt_decrypt() {
+        let recipient = PrivateKey::from_random();
+        let message: Vec<u8> = (0..5000u32).map(|x| (x * 7) as u8).collect();
+
+        for (chunk_size, write_size, read_size) in [(Some(100), 1, 1000), (Some(100), 250, 7), (Some(1000), 1000, 116), (None, 5000, 5000)] {
+            let stream = encrypt_stream(&message, &recipient, chunk_size, write_size);
+            let chunks = message.len().div_ceil(chunk_size.unwrap_or(65536) as usize);
+            assert_eq!(stream.len(), 41 + message.len() + chunks * 16);
+            assert_eq!(decrypt_stream(&stream, &recipient, read_size).unwrap(), message);
+        }
+
+        // Empty and exactly one chunk
+        let stream = encrypt_stream(&[], &recipient, Some(100), 1);
+        assert_eq!(decrypt_stream(&stream, &recipient, 10).unwrap(), b"");
+        let stream = encrypt_stream(&message[..100], &recipient, Some(100), 100);
+        assert_eq!(decrypt_stream(&stream, &recipient, 141).unwrap(), &message[..100]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn stream_detects_truncation_and_tampering() {
+        let recipient = PrivateKey::from_random();
+        let message = vec![42; 350];
+        let stream = encrypt_stream(&message, &recipient, Some(100), 350);
+        let sealed_chunk = 116;
+
+        // Whole chunks dropped from the end
+        assert!(decrypt_stream(&stream[..41 + 3 * sealed_chunk], &recipient, 50).is_err());
+        assert!(decrypt_stream(&stream[..41], &recipient, 50).is_err());
+        assert!(decrypt_stream(&stream[..20], &recipient, 50).is_err());
+
+        // Chunks swapped
+        let mut reordered = stream[..41].to_vec();
+        reordered.extend_from_slice(&stream[41 + sealed_chunk..41 + 2 * sealed_chunk]);
+        reordered.extend_from_slice(&stream[41..41 + sealed_chunk]);
+        reordered.extend_from_slice(&stream[41 + 2 * sealed_chunk..]);
+        assert!(decrypt_stream(&reordered, &recipient, 50).is_err());
+
+        for i in [0, 10, 40, 41, 200, stream.len() - 1] {
+            let mut modified = stream.clone();
+            modified[i] ^= 1;
+            assert!(decrypt_stream(&modified, &recipient, 50).is_err());
+        }
+
+        assert!(decrypt_stream(&stream, &PrivateKey::from_random(), 50).is_err());
+        assert!(ECIESStreamEncryptor::new(&recipient.get_public_key().unwrap(), Some(0)).is_err());
+    }
 }
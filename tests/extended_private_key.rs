@@ -1,7 +1,7 @@
 #[cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
 #[cfg(test)]
 mod xpriv_tests {
-    use bsv_wasm::{hash::Hash, keypair::*};
+    use bsv_wasm::{hash::Hash, keypair::*, BSVErrors};
     extern crate wasm_bindgen_test;
     use rand_core::OsRng;
     use wasm_bindgen_test::*;
@@ -187,4 +187,135 @@ mod xpriv_tests {
             "xprv9s21ZrQH143K3kV5ByEVyeoaC6TbWS9T3UrQamHwMgpbTghuLXUfiSgeK1TRr1K9xWVcJKdtQawEM1RGwAfCzwPHJXSCEzTSze7ZnduyQaU"
         );
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn xprv_to_testnet_tprv_and_back() {
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        let key = ExtendedPrivateKey::from_string(xprv).unwrap();
+        assert_eq!(key.get_version(), XPRIV_VERSION_BYTE);
+
+        let tprv = key.to_string_with_version(TPRV_VERSION_BYTE).unwrap();
+        assert!(tprv.starts_with("tprv"));
+
+        let testnet_key = ExtendedPrivateKey::from_string(&tprv).unwrap();
+        assert_eq!(testnet_key.get_version(), TPRV_VERSION_BYTE);
+        assert_eq!(testnet_key.to_string().unwrap(), tprv);
+        assert_eq!(testnet_key.to_string_with_version(XPRIV_VERSION_BYTE).unwrap(), xprv);
+
+        // Children and xpubs keep the network of their parent
+        assert!(testnet_key.derive(0).unwrap().to_string().unwrap().starts_with("tprv"));
+        assert!(ExtendedPublicKey::from_xpriv(&testnet_key).to_string().unwrap().starts_with("tpub"));
+    }
+
+    #[test]
+    fn custom_version_bytes() {
+        let key = ExtendedPrivateKey::from_string("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+
+        // SLIP-132 zprv
+        let zprv = key.to_string_with_version(0x04b2430c).unwrap();
+        assert!(zprv.starts_with("zprv"));
+
+        assert!(matches!(ExtendedPrivateKey::from_string(&zprv), Err(BSVErrors::InvalidExtendedKeyVersion(0x04b2430c))));
+        let parsed = ExtendedPrivateKey::from_string_with_version(&zprv, 0x04b2430c).unwrap();
+        assert_eq!(parsed.to_string().unwrap(), zprv);
+
+        // The xpub keeps the SLIP-132 pairing
+        assert_eq!(parsed.get_public_version(), 0x04b24746);
+        assert!(ExtendedPublicKey::from_xpriv(&parsed).to_string().unwrap().starts_with("zpub"));
+        assert!(ExtendedPublicKey::from_xpriv(&parsed.derive(1).unwrap()).to_string().unwrap().starts_with("zpub"));
+    }
+
+    #[test]
+    fn slip132_version_pairs_neuter_to_matching_prefixes() {
+        let key = ExtendedPrivateKey::from_string("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+
+        for (private_version, private_prefix, public_prefix) in [
+            (0x049d7878, "yprv", "ypub"),
+            (0x04b2430c, "zprv", "zpub"),
+            (0x0295b005, "Yprv", "Ypub"),
+            (0x02aa7a99, "Zprv", "Zpub"),
+            (0x044a4e28, "uprv", "upub"),
+            (0x045f18bc, "vprv", "vpub"),
+            (0x024285b5, "Uprv", "Upub"),
+            (0x02575048, "Vprv", "Vpub"),
+        ] {
+            let encoded = key.to_string_with_version(private_version).unwrap();
+            assert!(encoded.starts_with(private_prefix), "{}", encoded);
+
+            let parsed = ExtendedPrivateKey::from_string_with_version(&encoded, private_version).unwrap();
+            let xpub = ExtendedPublicKey::from_xpriv(&parsed).to_string().unwrap();
+            assert!(xpub.starts_with(public_prefix), "{}", xpub);
+        }
+    }
+
+    #[test]
+    fn unknown_version_requires_a_version_pair() {
+        let key = ExtendedPrivateKey::from_string("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+        let custom = key.to_string_with_version(0x01020304).unwrap();
+
+        assert!(matches!(
+            ExtendedPrivateKey::from_string_with_version(&custom, 0x01020304),
+            Err(BSVErrors::InvalidExtendedKeyVersion(0x01020304))
+        ));
+
+        let parsed = ExtendedPrivateKey::from_string_with_version_pair(&custom, 0x01020304, 0x05060708).unwrap();
+        assert_eq!(parsed.to_string().unwrap(), custom);
+        assert_eq!(ExtendedPublicKey::from_xpriv(&parsed).get_version(), 0x05060708);
+
+        // The pair must still match the encoded private version
+        assert!(ExtendedPrivateKey::from_string_with_version_pair(&custom, XPRIV_VERSION_BYTE, XPUB_VERSION_BYTE).is_err());
+    }
+
+    #[test]
+    fn invalid_xprv_checksum() {
+        let result = ExtendedPrivateKey::from_string("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL");
+        assert!(matches!(result, Err(BSVErrors::InvalidExtendedKeyChecksum(_, _))));
+    }
+
+    #[test]
+    fn bip32_invalid_xprv_vectors() {
+        // BIP32 Test Vector 5
+        let invalid_prefix = [
+            // prvkey version / pubkey mismatch
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+            // invalid prvkey prefix 04
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+            // invalid prvkey prefix 01
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+        ];
+        for key in invalid_prefix {
+            assert!(matches!(ExtendedPrivateKey::from_string(key), Err(BSVErrors::InvalidExtendedKeyPrefix(_))), "{}", key);
+        }
+
+        // zero depth with non-zero parent fingerprint
+        let result = ExtendedPrivateKey::from_string("xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv");
+        assert!(matches!(result, Err(BSVErrors::InconsistentExtendedKey(_))));
+
+        // zero depth with non-zero index
+        let result = ExtendedPrivateKey::from_string("xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN");
+        assert!(matches!(result, Err(BSVErrors::InconsistentExtendedKey(_))));
+
+        // unknown extended key version
+        let result = ExtendedPrivateKey::from_string("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9");
+        assert!(matches!(result, Err(BSVErrors::InvalidExtendedKeyVersion(_))));
+
+        // private key 0 and n are not in 1..n-1
+        assert!(ExtendedPrivateKey::from_string("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx").is_err());
+        assert!(ExtendedPrivateKey::from_string("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn extended_private_key_equality() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+        let by_path = master.derive_from_path("m/0'/1").unwrap();
+        let by_child = master.derive(0x80000000).unwrap().derive(1).unwrap();
+
+        assert!(by_path.equals(&by_child));
+        assert!(!by_path.equals(&master.derive_from_path("m/0'/2").unwrap()));
+        assert!(master.equals(&ExtendedPrivateKey::from_string(&master.to_string().unwrap()).unwrap()));
+    }
 }
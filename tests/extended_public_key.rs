@@ -1,7 +1,7 @@
 #[cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
 #[cfg(test)]
 mod xpub_tests {
-    use bsv_wasm::{hash::Hash, keypair::*};
+    use bsv_wasm::{hash::Hash, keypair::*, BSVErrors};
     extern crate wasm_bindgen_test;
     use wasm_bindgen_test::*;
     wasm_bindgen_test::wasm_bindgen_test_configure!();
@@ -62,4 +62,51 @@ mod xpub_tests {
         // Cannot do hardened derivation
         assert!(pub_key.derive_from_path("m/0'/1'").is_err());
     }
+
+    #[test]
+    fn tpub_round_trip() {
+        let pub_key = ExtendedPublicKey::from_string("xpub67uA5wAUuv1ypp7rEY7jUZBZmwFSULFUArLBJrHr3amnymkUEYWzQJz13zLacZv33sSuxKVmerpZeFExapBNt8HpAqtTtWqDQRAgyqSKUHu").unwrap();
+        let tpub = pub_key.to_string_with_version(TPUB_VERSION_BYTE).unwrap();
+        assert!(tpub.starts_with("tpub"));
+
+        let testnet_key = ExtendedPublicKey::from_string(&tpub).unwrap();
+        assert_eq!(testnet_key.get_version(), TPUB_VERSION_BYTE);
+        assert!(testnet_key.derive(1).unwrap().to_string().unwrap().starts_with("tpub"));
+
+        assert!(matches!(
+            ExtendedPublicKey::from_string_with_version(&tpub, XPUB_VERSION_BYTE),
+            Err(BSVErrors::InvalidExtendedKeyVersion(TPUB_VERSION_BYTE))
+        ));
+    }
+
+    #[test]
+    fn bip32_invalid_xpub_vectors() {
+        // BIP32 Test Vector 5
+        let invalid_prefix = [
+            // pubkey version / prvkey mismatch
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+            // invalid pubkey prefix 04
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+            // invalid pubkey prefix 01
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+        ];
+        for key in invalid_prefix {
+            assert!(matches!(ExtendedPublicKey::from_string(key), Err(BSVErrors::InvalidExtendedKeyPrefix(_))), "{}", key);
+        }
+
+        // zero depth with non-zero parent fingerprint
+        let result = ExtendedPublicKey::from_string("xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ");
+        assert!(matches!(result, Err(BSVErrors::InconsistentExtendedKey(_))));
+
+        // zero depth with non-zero index
+        let result = ExtendedPublicKey::from_string("xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8");
+        assert!(matches!(result, Err(BSVErrors::InconsistentExtendedKey(_))));
+
+        // unknown extended key version
+        let result = ExtendedPublicKey::from_string("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4");
+        assert!(matches!(result, Err(BSVErrors::InvalidExtendedKeyVersion(_))));
+
+        // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+        assert!(ExtendedPublicKey::from_string("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY").is_err());
+    }
 }
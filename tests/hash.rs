@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod tests {
     extern crate wasm_bindgen_test;
-    use bsv_wasm::hash::Hash;
+    use bsv_wasm::hash::{DRBGHashes, Hash, HashAlgorithms, Hasher, HmacDrbg};
     use pbkdf2::{
         password_hash::{Ident, PasswordHasher, Salt, SaltString},
         Params, Pbkdf2,
@@ -64,4 +64,267 @@ mod tests {
             "d76d8ade1b94820eaf73369b79112d664456c0b2ed47189341d2306b946de5dd6dc40d473902e22b5b9eba28613aae6df58d6e66d58c8b6ba5cafe96bc0e1c29"
         );
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sha3_and_keccak_test_vectors() {
+        assert_eq!(Hash::sha3_256(b"").to_hex(), "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+        assert_eq!(Hash::sha3_256(b"abc").to_hex(), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+        assert_eq!(
+            Hash::sha3_512(b"abc").to_hex(),
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+        );
+        assert_eq!(Hash::keccak_256(b"").to_hex(), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+        assert_eq!(Hash::keccak_256(b"abc").to_hex(), "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sha3_absorbs_across_rate_boundaries() {
+        // SHA3-256 absorbs 136 bytes per permutation
+        for length in [135, 136, 137, 272, 1000] {
+            let message = vec![0xa3; length];
+            let mut hasher = Hasher::new(HashAlgorithms::SHA3_256);
+            for chunk in message.chunks(17) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalise(), Hash::sha3_256(&message), "{}", length);
+        }
+
+        assert_eq!(Hash::sha3_256(&vec![0xa3; 200]).to_hex(), "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sha512_256_test_vector() {
+        assert_eq!(Hash::sha_512_256(b"abc").to_hex(), "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn blake2b_test_vectors() {
+        assert_eq!(
+            Hash::blake2b_512(b"abc").to_hex(),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        assert_eq!(
+            Hash::blake2b_512(b"").to_hex(),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        assert_eq!(Hash::blake2b_256(b"abc").to_hex(), "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn blake2b_mac_key_length() {
+        let mac = Hash::blake2b_512_mac(b"Hello, Bitcoin.", &[7; 64]).unwrap();
+        assert_eq!(mac.to_bytes().len(), 64);
+        assert_ne!(mac, Hash::blake2b_512(b"Hello, Bitcoin."));
+        assert_ne!(mac, Hash::blake2b_512_mac(b"Hello, Bitcoin.", &[8; 64]).unwrap());
+
+        assert!(Hash::blake2b_512_mac(b"Hello, Bitcoin.", &[]).is_err());
+        assert!(Hash::blake2b_512_mac(b"Hello, Bitcoin.", &[7; 65]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn blake3_test_vectors() {
+        assert_eq!(Hash::blake3(b"").to_hex(), "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262");
+        assert_eq!(Hash::blake3(b"abc").to_hex(), "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85");
+
+        // The official vectors hash the repeating byte sequence 0..250 and exercise the chunk tree
+        let input = |length: usize| (0..length).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        for (length, expected) in [
+            (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+            (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11"),
+            (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+            (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+            (2048, "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a"),
+            (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+            (3072, "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2"),
+            (4096, "015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969"),
+        ] {
+            assert_eq!(Hash::blake3(&input(length)).to_hex(), expected, "{}", length);
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn blake3_keyed_test_vector() {
+        let key = b"whats the Elvish word for friend";
+        assert_eq!(Hash::blake3_keyed(b"", key).unwrap().to_hex(), "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26");
+
+        assert!(Hash::blake3_keyed(b"", &key[..31]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hmac_sha3_256_nist_sample() {
+        let key: Vec<u8> = (0..32).collect();
+        assert_eq!(
+            Hash::sha3_256_hmac(b"Sample message for keylen<blocklen", &key).to_hex(),
+            "4fe8e202c4f058e8dddc23d8c34e467343e23555e24fc2f025d598f558f67205"
+        );
+    }
+
+    const ALGORITHMS: [HashAlgorithms; 13] = [
+        HashAlgorithms::SHA1,
+        HashAlgorithms::SHA256,
+        HashAlgorithms::SHA256d,
+        HashAlgorithms::SHA512,
+        HashAlgorithms::RIPEMD160,
+        HashAlgorithms::Hash160,
+        HashAlgorithms::SHA512_256,
+        HashAlgorithms::SHA3_256,
+        HashAlgorithms::SHA3_512,
+        HashAlgorithms::Keccak256,
+        HashAlgorithms::BLAKE2b256,
+        HashAlgorithms::BLAKE2b512,
+        HashAlgorithms::BLAKE3,
+    ];
+
+    fn one_shot(algorithm: HashAlgorithms, input: &[u8]) -> Hash {
+        match algorithm {
+            HashAlgorithms::SHA1 => Hash::sha_1(input),
+            HashAlgorithms::SHA256 => Hash::sha_256(input),
+            HashAlgorithms::SHA256d => Hash::sha_256d(input),
+            HashAlgorithms::SHA512 => Hash::sha_512(input),
+            HashAlgorithms::RIPEMD160 => Hash::ripemd_160(input),
+            HashAlgorithms::Hash160 => Hash::hash_160(input),
+            HashAlgorithms::SHA512_256 => Hash::sha_512_256(input),
+            HashAlgorithms::SHA3_256 => Hash::sha3_256(input),
+            HashAlgorithms::SHA3_512 => Hash::sha3_512(input),
+            HashAlgorithms::Keccak256 => Hash::keccak_256(input),
+            HashAlgorithms::BLAKE2b256 => Hash::blake2b_256(input),
+            HashAlgorithms::BLAKE2b512 => Hash::blake2b_512(input),
+            HashAlgorithms::BLAKE3 => Hash::blake3(input),
+        }
+    }
+
+    fn one_shot_hmac(algorithm: HashAlgorithms, input: &[u8], key: &[u8]) -> Hash {
+        match algorithm {
+            HashAlgorithms::SHA1 => Hash::sha_1_hmac(input, key),
+            HashAlgorithms::SHA256 => Hash::sha_256_hmac(input, key),
+            HashAlgorithms::SHA256d => Hash::sha_256d_hmac(input, key),
+            HashAlgorithms::SHA512 => Hash::sha_512_hmac(input, key),
+            HashAlgorithms::RIPEMD160 => Hash::ripemd_160_hmac(input, key),
+            HashAlgorithms::Hash160 => Hash::hash_160_hmac(input, key),
+            HashAlgorithms::SHA512_256 => Hash::sha_512_256_hmac(input, key),
+            HashAlgorithms::SHA3_256 => Hash::sha3_256_hmac(input, key),
+            HashAlgorithms::SHA3_512 => Hash::sha3_512_hmac(input, key),
+            HashAlgorithms::Keccak256 => Hash::keccak_256_hmac(input, key),
+            // Hash has no HMAC for the BLAKE family (they have keyed modes), so compute it by definition
+            HashAlgorithms::BLAKE2b256 | HashAlgorithms::BLAKE2b512 => hmac_by_definition(algorithm, 128, input, key),
+            HashAlgorithms::BLAKE3 => hmac_by_definition(algorithm, 64, input, key),
+        }
+    }
+
+    fn hmac_by_definition(algorithm: HashAlgorithms, block_size: usize, input: &[u8], key: &[u8]) -> Hash {
+        let mut padded_key = key.to_vec();
+        padded_key.resize(block_size, 0);
+        let ipad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x36).collect();
+        let opad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x5c).collect();
+
+        let inner = one_shot(algorithm, &[&ipad[..], input].concat());
+        one_shot(algorithm, &[&opad[..], &inner.to_bytes()[..]].concat())
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn incremental_hashes_match_one_shot() {
+        let message: Vec<u8> = (0..1000u32).map(|x| (x * 7) as u8).collect();
+
+        for algorithm in ALGORITHMS {
+            let mut hasher = Hasher::new(algorithm);
+            for chunk in message.chunks(37) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!(hasher.finalise(), one_shot(algorithm, &message), "{:?}", algorithm);
+            assert_eq!(hasher.get_algorithm(), algorithm);
+            assert!(!hasher.is_hmac());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn incremental_hmacs_match_one_shot() {
+        let message: Vec<u8> = (0..1000u32).map(|x| (x * 13) as u8).collect();
+        let key = b"incremental hmac key";
+
+        for algorithm in ALGORITHMS {
+            let mut hasher = Hasher::new_hmac(algorithm, key);
+            for chunk in message.chunks(100) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!(hasher.finalise(), one_shot_hmac(algorithm, &message, key), "{:?}", algorithm);
+            assert!(hasher.is_hmac());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hmac_sha256_rfc4231_test_case_2() {
+        let mut hasher = Hasher::new_hmac(HashAlgorithms::SHA256, b"Jefe");
+        hasher.update(b"what do ya want ");
+        hasher.update(b"for nothing?");
+
+        assert_eq!(hasher.finalise().to_hex(), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn finalise_and_reset_start_a_new_message() {
+        let mut hasher = Hasher::new(HashAlgorithms::SHA256);
+        hasher.update(b"first message");
+        hasher.finalise();
+        hasher.update(b"Hello, Bitcoin.");
+        assert_eq!(hasher.finalise(), Hash::sha_256(b"Hello, Bitcoin."));
+
+        let mut hmac = Hasher::new_hmac(HashAlgorithms::SHA512, b"key");
+        hmac.update(b"discarded");
+        hmac.reset();
+        hmac.update(b"Hello, Bitcoin.");
+        assert_eq!(hmac.finalise(), Hash::sha_512_hmac(b"Hello, Bitcoin.", b"key"));
+
+        assert_eq!(Hasher::new(HashAlgorithms::Hash160).finalise(), Hash::hash_160(&[]));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hmac_drbg_is_deterministic() {
+        let mut a = HmacDrbg::new(DRBGHashes::SHA256, &[7; 32], &[1; 16], b"personal");
+        let mut b = HmacDrbg::new(DRBGHashes::SHA256, &[7; 32], &[1; 16], b"personal");
+        assert_eq!(a.generate(100, &[]).unwrap(), b.generate(100, &[]).unwrap());
+
+        let mut other = HmacDrbg::new(DRBGHashes::SHA256, &[7; 32], &[1; 16], b"other");
+        let next = a.generate(32, &[]).unwrap();
+        assert_ne!(other.generate(32, &[]).unwrap(), next);
+        assert_ne!(a.generate(32, &[]).unwrap(), next);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hmac_drbg_additional_input_and_reseed_change_output() {
+        let mut a = HmacDrbg::new(DRBGHashes::SHA512, &[7; 64], &[], &[]);
+        let mut b = HmacDrbg::new(DRBGHashes::SHA512, &[7; 64], &[], &[]);
+        assert_ne!(a.generate(64, b"additional").unwrap(), b.generate(64, &[]).unwrap());
+
+        let mut a = HmacDrbg::new(DRBGHashes::SHA512, &[7; 64], &[], &[]);
+        let mut b = HmacDrbg::new(DRBGHashes::SHA512, &[7; 64], &[], &[]);
+        a.reseed(&[9; 64], &[]);
+        assert_ne!(a.generate(64, &[]).unwrap(), b.generate(64, &[]).unwrap());
+        assert_eq!(a.get_hash(), DRBGHashes::SHA512);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hmac_drbg_request_limit() {
+        let mut drbg = HmacDrbg::new(DRBGHashes::SHA256, &[7; 32], &[], &[]);
+
+        assert_eq!(drbg.generate(65536, &[]).unwrap().len(), 65536);
+        assert!(drbg.generate(65537, &[]).is_err());
+        assert!(drbg.generate(0, &[]).unwrap().is_empty());
+    }
 }
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod kdf_tests {
     extern crate wasm_bindgen_test;
-    use bsv_wasm::{hash::Hash, KDF};
+    use bsv_wasm::{hash::Hash, Argon2Params, HKDFHashes, ScryptParams, ARGON2_MAX_ASSOCIATED_DATA_LENGTH, KDF, KDF_MAX_MEMORY_BYTES};
     use pbkdf2::{
         password_hash::{Ident, PasswordHasher, Salt, SaltString},
         Params, Pbkdf2,
@@ -36,4 +36,111 @@ mod kdf_tests {
         // validated against twetch/sycamore-pro and https://neurotechnics.com/tools/pbkdf2-test
         assert_eq!(kdf.get_hash().to_hex(), "f064d740b65941152755829e2b48578b259bc9bfc8c3af7b0d93a5ca677f259d");
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn scrypt_rfc7914_test_vectors() {
+        let kdf = KDF::scrypt(b"", Some(vec![]), &ScryptParams::new(4, 1, 1), 64).unwrap();
+        assert_eq!(
+            kdf.get_hash().to_hex(),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+
+        let kdf = KDF::scrypt(b"password", Some(b"NaCl".to_vec()), &ScryptParams::new(10, 8, 16), 64).unwrap();
+        assert_eq!(
+            kdf.get_hash().to_hex(),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+        assert_eq!(kdf.get_salt(), b"NaCl".to_vec());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn scrypt_rejects_invalid_params() {
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(0, 8, 1), 32).is_err());
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(10, 0, 1), 32).is_err());
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(10, 8, 0), 32).is_err());
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(10, 1 << 15, 1 << 15), 32).is_err());
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(10, 8, 1), 0).is_err());
+        // 128 * r * N above KDF_MAX_MEMORY_BYTES is refused without allocating
+        assert!(KDF::scrypt(b"password", None, &ScryptParams::new(24, 128, 1), 32).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn scrypt_random_salt_test() {
+        let kdf = KDF::scrypt(b"password", None, &ScryptParams::new(4, 1, 1), 32).unwrap();
+        let again = KDF::scrypt(b"password", Some(kdf.get_salt()), &ScryptParams::new(4, 1, 1), 32).unwrap();
+
+        assert_eq!(kdf.get_salt().len(), 16);
+        assert_eq!(kdf, again);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn argon2id_rfc9106_test_vector() {
+        let params = Argon2Params::new(32, 3, 4);
+        let kdf = KDF::argon2id_with_secret(&[1; 32], &[2; 16], &[3; 8], &[4; 12], &params, 32).unwrap();
+
+        assert_eq!(kdf.get_hash().to_hex(), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+        assert_eq!(kdf.get_salt(), vec![2; 16]);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn argon2id_salt_and_secret_change_output() {
+        let params = Argon2Params::new(32, 3, 4);
+        let kdf = KDF::argon2id(&[1; 32], Some(vec![2; 16]), &params, 32).unwrap();
+
+        assert_eq!(kdf, KDF::argon2id(&[1; 32], Some(vec![2; 16]), &params, 32).unwrap());
+        assert_ne!(kdf.get_hash(), KDF::argon2id(&[1; 32], Some(vec![3; 16]), &params, 32).unwrap().get_hash());
+        assert_ne!(kdf.get_hash(), KDF::argon2id_with_secret(&[1; 32], &[2; 16], &[3; 8], &[], &params, 32).unwrap().get_hash());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn argon2id_rejects_invalid_params() {
+        assert!(KDF::argon2id(b"password", Some(vec![0; 7]), &Argon2Params::new(32, 3, 4), 32).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new(31, 3, 4), 32).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new(32, 0, 4), 32).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new(32, 3, 0), 32).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new(32, 3, 4), 3).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new((KDF_MAX_MEMORY_BYTES / 1024) as u32 + 1, 1, 1), 32).is_err());
+        assert!(KDF::argon2id(b"password", None, &Argon2Params::new(u32::MAX, 1, 1), 32).is_err());
+        assert!(KDF::argon2id_with_secret(b"password", &[0; 16], &[], &[0; ARGON2_MAX_ASSOCIATED_DATA_LENGTH + 1], &Argon2Params::new(32, 3, 4), 32).is_err());
+        assert!(KDF::argon2id_with_secret(b"password", &[0; 16], &[], &[0; ARGON2_MAX_ASSOCIATED_DATA_LENGTH], &Argon2Params::new(32, 3, 4), 32).is_ok());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn argon2id_long_output_test() {
+        let params = Argon2Params::new(64, 1, 1);
+        let kdf = KDF::argon2id(b"password", Some(b"somesalt".to_vec()), &params, 100).unwrap();
+
+        assert_eq!(kdf.get_hash().to_bytes().len(), 100);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hkdf_rfc5869_test_vectors() {
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        let kdf = KDF::hkdf(&[0x0b; 22], &salt, &info, HKDFHashes::SHA256, 42).unwrap();
+        assert_eq!(kdf.get_hash().to_hex(), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+
+        // Test case 3: empty salt and info
+        let kdf = KDF::hkdf(&[0x0b; 22], &[], &[], HKDFHashes::SHA256, 42).unwrap();
+        assert_eq!(kdf.get_hash().to_hex(), "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hkdf_sha512_output_lengths() {
+        let kdf = KDF::hkdf(b"input key material", b"salt", b"info", HKDFHashes::SHA512, 255 * 64).unwrap();
+        let prefix = KDF::hkdf(b"input key material", b"salt", b"info", HKDFHashes::SHA512, 100).unwrap();
+
+        assert_eq!(&kdf.get_hash().to_bytes()[..100], &prefix.get_hash().to_bytes()[..]);
+        assert!(KDF::hkdf(b"input key material", b"salt", b"info", HKDFHashes::SHA512, 255 * 64 + 1).is_err());
+        assert!(KDF::hkdf(b"input key material", b"salt", b"info", HKDFHashes::SHA256, 0).is_err());
+    }
 }
@@ -55,4 +55,15 @@ mod private_key_tests {
 
         assert_eq!(private_key_hex, "ef235aacf90d9f4aadd8c92e4b2562e1d9eb97f0df9ba3b508258739cb013db2".to_lowercase())
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn private_key_equality() {
+        let key = PrivateKey::from_wif("L5EZftvrYaSudiozVRzTqLcHLNDoVn7H5HSfM9BAN6tMJX8oTWz6").unwrap();
+        let same_key = PrivateKey::from_hex("ef235aacf90d9f4aadd8c92e4b2562e1d9eb97f0df9ba3b508258739cb013db2").unwrap();
+
+        assert!(key.equals(&same_key));
+        assert!(!key.equals(&same_key.compress_public_key(false)));
+        assert!(!key.equals(&PrivateKey::from_random()));
+    }
 }
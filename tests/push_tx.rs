@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod push_tx_tests {
+    use bsv_wasm::*;
+    extern crate wasm_bindgen_test;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    // x coordinate of the generator point, R for k = 1
+    const GENERATOR_X: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn get_tx() -> Transaction {
+        Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap()
+    }
+
+    fn key_one() -> PrivateKey {
+        PrivateKey::from_hex("0000000000000000000000000000000000000000000000000000000000000001").unwrap()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn push_tx_signature_is_low_s_and_verifies() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+
+        for malleation in [PushTxMalleation::NLocktime, PushTxMalleation::NSequence] {
+            let mut tx = get_tx();
+            let sig = tx.sign_push_tx(&key_one(), &key_one(), SigHash::InputsOutputs, 0, &signing_script, 1000, malleation).unwrap();
+
+            // Passes the node's strict DER and low S checks
+            let parsed = SighashSignature::from_bytes(&sig.to_bytes().unwrap(), &sig.get_sighash_buffer()).unwrap();
+            assert_eq!(hex::encode(&parsed.get_signature().to_compact_bytes()[1..33]), GENERATOR_X);
+            assert!(tx.verify(&PublicKey::from_private_key(&key_one()), &sig));
+
+            // The returned preimage belongs to the (possibly tweaked) transaction
+            let preimage = SighashPreimage::from_bytes(&sig.get_sighash_buffer()).unwrap();
+            assert_eq!(preimage.get_n_locktime(), tx.get_n_locktime());
+            assert_eq!(preimage.get_sequence(), tx.get_input(0).unwrap().get_sequence());
+            assert_eq!(sig.get_sighash_buffer(), tx.sighash_preimage(SigHash::InputsOutputs, 0, &signing_script, 1000).unwrap());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sign_with_k_uses_given_nonce() {
+        let private_key = PrivateKey::from_hex("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262").unwrap();
+        let k = PrivateKey::from_hex("4200000000000000000000000000000000000000000000000000000000000002").unwrap();
+        let message = b"Hello Bitcoin";
+
+        let signature = ECDSA::sign_with_k(&private_key, &k, message, SigningHash::Sha256d).unwrap();
+        assert!(ECDSA::verify_digest(message, &PublicKey::from_private_key(&private_key), &signature, SigningHash::Sha256d).unwrap());
+        assert_eq!(signature.to_compact_bytes()[1..33], PublicKey::from_private_key(&k).to_compressed().unwrap().to_bytes().unwrap()[1..]);
+
+        // Deterministic for a fixed nonce
+        assert_eq!(ECDSA::sign_with_k(&private_key, &k, message, SigningHash::Sha256d).unwrap(), signature);
+
+        // This nonce gives a high S, which only the unnormalised variant returns as-is so it fails strict verification
+        let high_s_k = PrivateKey::from_hex("4200000000000000000000000000000000000000000000000000000000000005").unwrap();
+        let high_s = ECDSA::sign_with_k_unnormalised(&private_key, &high_s_k, message, SigningHash::Sha256d).unwrap();
+        assert!(!high_s.is_low_s());
+        assert!(ECDSA::verify_digest_strict(message, &PublicKey::from_private_key(&private_key), &high_s, SigningHash::Sha256d).is_err());
+
+        let low_s = ECDSA::sign_with_k(&private_key, &high_s_k, message, SigningHash::Sha256d).unwrap();
+        assert!(low_s.is_low_s());
+        assert_eq!(low_s, high_s.normalise_s().unwrap());
+        assert!(ECDSA::verify_digest_strict(message, &PublicKey::from_private_key(&private_key), &low_s, SigningHash::Sha256d).unwrap());
+    }
+}
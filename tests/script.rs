@@ -124,4 +124,19 @@ mod tests {
             "0a40eda5ff94de646c3928e4a8eff097feeb283d124b0e871b24962e75846144 21e8 OP_SIZE OP_4 OP_PICK OP_SHA256 OP_SWAP OP_SPLIT OP_DROP OP_EQUALVERIFY OP_DROP OP_CHECKSIG"
         );
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn codeseparators_in_push_data_are_kept() {
+        // OP_PUSH 2 abab OP_CODESEPARATOR OP_DUP OP_CODESEPARATOR OP_PUSHDATA1 2 abab OP_CHECKSIG
+        let mut script = Script::from_hex("02ababab76ab4c02ababac").unwrap();
+
+        assert_eq!(script.get_subscript(None).unwrap(), script);
+        assert_eq!(script.get_subscript(Some(0)).unwrap().to_hex(), "76ab4c02ababac");
+        assert_eq!(script.get_subscript(Some(1)).unwrap().to_hex(), "4c02ababac");
+        assert!(script.get_subscript(Some(2)).is_err());
+
+        script.remove_codeseparators();
+        assert_eq!(script.to_hex(), "02abab764c02ababac");
+    }
 }
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod shamir_tests {
+    use bsv_wasm::{BSVErrors, PrivateKey, SecretShare, Shamir};
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn any_threshold_subset_recombines_private_key() {
+        let priv_key = PrivateKey::from_wif("L17y3TE8AgM6fiWFP4HsbaLnvuBJsQcFKYRoJoZULpTzeTCr2nEC").unwrap();
+
+        let shares = Shamir::split_private_key(&priv_key, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for (a, b, c) in [(0, 1, 2), (0, 2, 4), (1, 3, 4), (4, 2, 3)] {
+            let subset = vec![shares[a].clone(), shares[b].clone(), shares[c].clone()];
+            let recombined = Shamir::combine_private_key(&subset).unwrap();
+            assert_eq!(recombined.to_hex(), priv_key.to_hex());
+        }
+
+        // Extra shares beyond the threshold are accepted
+        assert_eq!(Shamir::combine_private_key(&shares).unwrap().to_hex(), priv_key.to_hex());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn too_few_shares_are_rejected() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let shares = Shamir::split(&seed, 3, 5).unwrap();
+
+        let result = Shamir::combine(&shares[0..2]);
+        assert!(matches!(result, Err(BSVErrors::ShamirError(_))));
+
+        // Duplicates do not count towards the threshold
+        let result = Shamir::combine(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]);
+        assert!(matches!(result, Err(BSVErrors::ShamirError(_))));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn share_round_trips_and_detects_corruption() {
+        let seed = hex::decode("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap();
+        let shares = Shamir::split(&seed, 2, 3).unwrap();
+
+        let share_hex = shares[1].to_hex();
+        let decoded = SecretShare::from_hex(&share_hex).unwrap();
+        assert_eq!(decoded, shares[1]);
+        assert_eq!(decoded.get_threshold(), 2);
+        assert_eq!(decoded.get_index(), 2);
+
+        let mut corrupted = shares[1].to_bytes();
+        corrupted[8] ^= 0x01;
+        assert!(matches!(SecretShare::from_bytes(&corrupted), Err(BSVErrors::ShamirError(_))));
+
+        assert_eq!(Shamir::combine(&[shares[2].clone(), decoded]).unwrap(), seed);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn shares_from_different_splits_are_rejected() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let first = Shamir::split(&seed, 2, 3).unwrap();
+        let second = Shamir::split(&seed, 2, 3).unwrap();
+
+        let result = Shamir::combine(&[first[0].clone(), second[1].clone()]);
+        assert!(matches!(result, Err(BSVErrors::ShamirError(_))));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn invalid_thresholds_are_rejected() {
+        let seed = [1u8; 32];
+        assert!(Shamir::split(&seed, 0, 3).is_err());
+        // Every share of a 1-of-n split would be the secret itself
+        assert!(Shamir::split(&seed, 1, 3).is_err());
+        assert!(Shamir::split(&seed, 1, 1).is_err());
+        assert!(Shamir::split(&seed, 4, 3).is_err());
+        assert!(Shamir::split(&[], 2, 3).is_err());
+    }
+}
@@ -114,4 +114,265 @@ mod sighash_tests {
 
         assert!(tx.verify(&PublicKey::from_private_key(&priv_key), &sig));
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_signature_from_checksig_bytes() {
+        let priv_key = PrivateKey::from_wif("L31JUXCGspUREe9Gya8F2WWjeoRz3bb8AQzJjAP8ntGYp37oYdSx").unwrap();
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let mut tx = Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap();
+
+        let sig = tx.sign(&priv_key, SigHash::InputsOutput, 0, &signing_script, 0).unwrap();
+        let parsed = SighashSignature::from_bytes(&sig.to_bytes().unwrap(), &sig.get_sighash_buffer()).unwrap();
+
+        assert_eq!(parsed.get_sighash_type(), SigHash::InputsOutput);
+        assert_eq!(parsed.get_signature().to_der_bytes(), sig.get_signature().to_der_bytes());
+        assert_eq!(parsed.to_hex().unwrap(), sig.to_hex().unwrap());
+        assert!(tx.verify(&PublicKey::from_private_key(&priv_key), &parsed));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_signature_checksig_rules() {
+        let low_s = "30440220798bd19a0bb1fd5e1b3832e46ae69af687d87cbd179a81e60af719382860aee502206d849665f4010a54f40d9ac463629cbd758042745cebf7122818d9bfea2bce70";
+
+        // Taken from the scriptSig of the first input in the transactions above, valid DER but S is in the upper half of the curve order
+        let high_s = "3046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f2701";
+        assert!(matches!(SighashSignature::from_hex(high_s, &[]), Err(BSVErrors::HighSSignature)));
+
+        // Unnecessary zero padding on R
+        let padded_r = "3045022100798bd19a0bb1fd5e1b3832e46ae69af687d87cbd179a81e60af719382860aee502206d849665f4010a54f40d9ac463629cbd758042745cebf7122818d9bfea2bce70";
+        assert!(matches!(SighashSignature::from_hex(&format!("{}41", padded_r), &[]), Err(BSVErrors::NonStrictDERSignature(_))));
+
+        // Wrong sequence tag
+        assert!(matches!(SighashSignature::from_hex(&format!("31{}41", &low_s[2..]), &[]), Err(BSVErrors::NonStrictDERSignature(_))));
+
+        assert!(matches!(SighashSignature::from_bytes(&[], &[]), Err(BSVErrors::NonStrictDERSignature(_))));
+
+        for undefined in ["00", "04", "40", "80", "c4", "21"] {
+            assert!(matches!(SighashSignature::from_hex(&format!("{}{}", low_s, undefined), &[]), Err(BSVErrors::UndefinedSighashType(_))));
+        }
+
+        for defined in ["01", "02", "03", "41", "42", "43", "81", "82", "83", "c1", "c2", "c3"] {
+            let parsed = SighashSignature::from_hex(&format!("{}{}", low_s, defined), &[]).unwrap();
+            assert_eq!(parsed.get_sighash_type() as u8, u8::from_str_radix(defined, 16).unwrap());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_preimage_after_codeseparator() {
+        let priv_key = PrivateKey::from_wif("L31JUXCGspUREe9Gya8F2WWjeoRz3bb8AQzJjAP8ntGYp37oYdSx").unwrap();
+        // OP_PUSH 2 abab OP_DROP OP_CODESEPARATOR OP_DUP OP_DROP OP_CODESEPARATOR OP_CHECKSIG
+        let script = Script::from_hex("02abab75ab7675abac").unwrap();
+        let mut tx = Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap();
+
+        // BIP143 signs the subscript verbatim, including later OP_CODESEPARATORs
+        let preimage = tx.sighash_preimage_with_codeseparator(SigHash::InputsOutputs, 0, &script, 0, Some(0)).unwrap();
+        assert_eq!(SighashPreimage::from_bytes(&preimage).unwrap().get_script_code().to_hex(), "7675abac");
+        assert_eq!(preimage, tx.sighash_preimage(SigHash::InputsOutputs, 0, &Script::from_hex("7675abac").unwrap(), 0).unwrap());
+
+        let sig = tx.sign_with_codeseparator(&priv_key, SigHash::InputsOutputs, 0, &script, 0, Some(1)).unwrap();
+        assert_eq!(SighashPreimage::from_bytes(&sig.get_sighash_buffer()).unwrap().get_script_code().to_hex(), "ac");
+        assert!(tx.verify(&PublicKey::from_private_key(&priv_key), &sig));
+
+        // Legacy removes the remaining OP_CODESEPARATOR opcodes but never push data
+        let legacy_preimage = tx.sighash_preimage_with_codeseparator(SigHash::ALL, 0, &script, 0, Some(0)).unwrap();
+        assert_eq!(legacy_preimage, tx.sighash_preimage(SigHash::ALL, 0, &Script::from_hex("7675ac").unwrap(), 0).unwrap());
+
+        let legacy_preimage = tx.sighash_preimage(SigHash::ALL, 0, &script, 0).unwrap();
+        assert_eq!(legacy_preimage, tx.sighash_preimage(SigHash::ALL, 0, &Script::from_hex("02abab757675ac").unwrap(), 0).unwrap());
+
+        assert!(tx.sighash_preimage_with_codeseparator(SigHash::InputsOutputs, 0, &script, 0, Some(2)).is_err());
+
+        let forkid_preimage = tx.sighash_preimage_with_codeseparator(SigHash::InputsOutputs, 0, &script, 0, Some(1)).unwrap();
+        assert_eq!(
+            tx.sighash_digest(SigHash::InputsOutputs, 0, &script, 0, false, Some(1)).unwrap(),
+            Hash::sha_256d(&forkid_preimage).to_bytes()
+        );
+        assert!(tx.sighash_digest(SigHash::InputsOutputs, 0, &script, 0, false, Some(2)).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn legacy_sighash_single_blanks_earlier_outputs() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let mut tx = Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap();
+
+        // Built by hand: other inputs have empty scripts and nSequence 0, outputs before the signed one are blanked
+        let mut expected = Transaction::new(tx.get_version(), tx.get_n_locktime());
+        let mut other_input = tx.get_input(0).unwrap();
+        other_input.set_script(&Script::default());
+        other_input.set_sequence(0);
+        expected.add_input(&other_input);
+        let mut signed_input = tx.get_input(1).unwrap();
+        signed_input.set_script(&signing_script);
+        expected.add_input(&signed_input);
+        expected.add_output(&TxOut::new(u64::MAX, &Script::default()));
+        expected.add_output(&tx.get_output(1).unwrap());
+        let expected = [expected.to_bytes().unwrap(), vec![0x03, 0, 0, 0]].concat();
+
+        assert_eq!(tx.sighash_preimage(SigHash::SINGLE, 1, &signing_script, 0).unwrap(), expected);
+        assert_eq!(tx.sighash_digest(SigHash::SINGLE, 1, &signing_script, 0, true, None).unwrap(), Hash::sha_256d(&expected).to_bytes());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn legacy_sighash_single_bug() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let source = Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap();
+
+        // Two inputs but only one output, so input 1 has no matching output
+        let mut tx = Transaction::new(1, 0);
+        tx.add_input(&source.get_input(0).unwrap());
+        tx.add_input(&source.get_input(1).unwrap());
+        tx.add_output(&source.get_output(0).unwrap());
+
+        let mut one = vec![0; 32];
+        one[0] = 1;
+
+        for sighash in [SigHash::SINGLE, SigHash::Legacy_InputOutput] {
+            assert_eq!(tx.sighash_digest(sighash, 1, &signing_script, 0, true, None).unwrap(), one);
+            assert!(tx.sighash_digest(sighash, 1, &signing_script, 0, false, None).is_err());
+            assert!(tx.sighash_preimage(sighash, 1, &signing_script, 0).is_err());
+        }
+
+        // Only legacy SIGHASH_SINGLE has the bug, FORKID signs zero hashOutputs
+        let forkid_digest = tx.sighash_digest(SigHash::InputsOutput, 1, &signing_script, 0, true, None).unwrap();
+        assert_ne!(forkid_digest, one);
+        assert!(tx.sighash_digest(SigHash::InputsOutput, 1, &signing_script, 0, false, None).is_err());
+        assert!(tx.sighash_digest(SigHash::SINGLE, 2, &signing_script, 0, true, None).is_err());
+        assert_ne!(tx.sighash_digest(SigHash::SINGLE, 0, &signing_script, 0, true, None).unwrap(), one);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_flags_predicates() {
+        let flags = SighashFlags::from(SigHash::InputOutput);
+        assert!(flags.has_forkid() && flags.is_anyonecanpay() && flags.is_single() && flags.is_defined());
+        assert_eq!(flags.base_type(), 3);
+        assert_eq!(flags, SighashFlags::SINGLE | SighashFlags::ANYONECANPAY | SighashFlags::FORKID);
+        assert_eq!(flags.to_sighash().unwrap(), SigHash::InputOutput);
+
+        let flags = SighashFlags::new(2, true, false);
+        assert!(!flags.has_forkid() && flags.is_anyonecanpay() && flags.is_none());
+        assert_eq!(flags.to_sighash().unwrap(), SigHash::Legacy_Input);
+
+        for undefined in [0x00, 0x04, 0x20, 0x21, 0x44, 0xc0] {
+            let flags = SighashFlags::from_u8(undefined);
+            assert!(!flags.is_defined());
+            assert!(flags.to_sighash().is_err());
+        }
+
+        let sig = hex::decode("30440220798bd19a0bb1fd5e1b3832e46ae69af687d87cbd179a81e60af719382860aee502206d849665f4010a54f40d9ac463629cbd758042745cebf7122818d9bfea2bce7043").unwrap();
+        assert_eq!(SighashFlags::from_checksig_bytes(&sig).unwrap(), SighashFlags::from(SigHash::InputsOutput));
+        assert!(SighashFlags::from_checksig_bytes(&[]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_preimage_with_flags() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let get_tx = || {
+            Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap()
+        };
+
+        for sighash in [0x01, 0x02, 0x03, 0x41, 0x42, 0x43, 0x81, 0x82, 0x83, 0xc1, 0xc2, 0xc3] {
+            let defined = SigHash::try_from(sighash).unwrap();
+            assert_eq!(
+                get_tx().sighash_preimage_with_flags(&SighashFlags::from_u8(sighash), 1, &signing_script, 0, None).unwrap(),
+                get_tx().sighash_preimage(defined, 1, &signing_script, 0).unwrap()
+            );
+        }
+
+        // Undefined base types are hashed as ALL, only the trailing sighash type differs
+        for (undefined, equivalent) in [(0x00, SigHash::ALL), (0x44, SigHash::InputsOutputs), (0xa0, SigHash::Legacy_InputOutputs)] {
+            let preimage = get_tx().sighash_preimage_with_flags(&SighashFlags::from_u8(undefined), 1, &signing_script, 0, None).unwrap();
+            let mut expected = get_tx().sighash_preimage(equivalent, 1, &signing_script, 0).unwrap();
+            let len = expected.len();
+            expected[len - 4] = undefined;
+            assert_eq!(preimage, expected);
+        }
+
+        // Cached hashes from one sighash type must not leak into another
+        let mut tx = get_tx();
+        tx.sighash_preimage(SigHash::InputsOutputs, 0, &signing_script, 0).unwrap();
+        tx.sighash_preimage(SigHash::InputsOutput, 0, &signing_script, 0).unwrap();
+        for sighash in [SigHash::InputOutputs, SigHash::Inputs, SigHash::InputsOutput, SigHash::InputsOutputs] {
+            assert_eq!(
+                tx.sighash_preimage(sighash, 1, &signing_script, 0).unwrap(),
+                get_tx().sighash_preimage(sighash, 1, &signing_script, 0).unwrap()
+            );
+        }
+    }
+
+    // tests/data/sighash.json takes either the original client's format (one original digest) or the node's (regular and no forkid
+    // digests), so the node's file can be dropped in. Digests are displayed reversed and every vector signs a zero amount.
+    #[test]
+    #[wasm_bindgen_test]
+    fn sighash_consensus_vectors() {
+        let vectors: Vec<serde_json::Value> = serde_json::from_str(include_str!("data/sighash.json")).unwrap();
+
+        let digest = |vector: &[serde_json::Value], forkid_enabled: bool| {
+            let mut tx = Transaction::from_hex(vector[0].as_str().unwrap()).unwrap();
+            let script = Script::from_hex(vector[1].as_str().unwrap()).unwrap();
+            let n_tx_in = vector[2].as_u64().unwrap() as usize;
+            let hash_type = vector[3].as_i64().unwrap() as i32 as u32;
+
+            let mut digest = tx.sighash_digest_with_hash_type(hash_type, n_tx_in, &script, 0, true, None, forkid_enabled).unwrap();
+            digest.reverse();
+            digest.to_hex()
+        };
+
+        let mut checked = 0;
+        for vector in vectors.iter().filter_map(|v| v.as_array()).filter(|v| v.len() > 1) {
+            let has_forkid = vector[3].as_i64().unwrap() & 0x40 != 0;
+            let (regular, no_forkid) = match vector.len() {
+                6 => (vector[4].as_str(), vector[5].as_str().unwrap()),
+                // Without the FORKID bit the regular digest is the original one
+                _ => (if has_forkid { None } else { vector[4].as_str() }, vector[4].as_str().unwrap()),
+            };
+
+            assert_eq!(digest(vector, false), no_forkid, "{:?}", vector);
+            if let Some(regular) = regular {
+                assert_eq!(digest(vector, true), regular, "{:?}", vector);
+            }
+            checked += 1;
+        }
+
+        assert_eq!(checked, vectors.len() - 1);
+    }
+
+    // The FORKID preimage is BIP143's with a different nHashType, so its native P2WPKH example checks hashPrevouts, hashSequence,
+    // hashOutputs and the digest of input 1 (nSequence 0xffffffff) against input 0's 0xffffffee
+    #[test]
+    #[wasm_bindgen_test]
+    fn forkid_preimage_matches_bip143_example() {
+        let mut tx = Transaction::from_hex("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000").unwrap();
+        let script_code = Script::from_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap();
+
+        let preimage = tx.sighash_preimage(SigHash::InputsOutputs, 1, &script_code, 600000000).unwrap();
+        assert_eq!(preimage[4..36].to_hex(), "96b827c8483d4e9b96712b6713a7b68d6e8003a781feba36c31143470b4efd37");
+        assert_eq!(preimage[36..68].to_hex(), "52b0a642eea2fb7ae638c36f6252b6750293dbe574a806984b8e4d8548339a3b");
+        assert_eq!(
+            preimage[preimage.len() - 40..preimage.len() - 8].to_hex(),
+            "863ef3e1a92afbfdb97f31ad0fc7683ee943e9abcf2501590ff8f6551f47e5e5"
+        );
+        assert_eq!(preimage[preimage.len() - 4..].to_hex(), "41000000");
+
+        let bip143_preimage = [&preimage[..preimage.len() - 4], &[1, 0, 0, 0]].concat();
+        assert_eq!(Hash::sha_256d(&bip143_preimage).to_hex(), "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn hash_inputs_takes_sighash_or_flags() {
+        let mut tx = Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap();
+
+        let hashed_inputs = tx.hash_inputs(SigHash::InputsOutputs);
+        assert_eq!(hashed_inputs, tx.hash_inputs(SighashFlags::from_u8(0x41)));
+        assert_eq!(hashed_inputs, tx.hash_inputs(SighashFlags::from_u8(0x1e)));
+        assert_eq!(tx.hash_inputs(SigHash::InputOutputs), vec![0; 32]);
+        assert_eq!(tx.hash_inputs(SighashFlags::from_u8(0x80)), vec![0; 32]);
+    }
 }
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod sighash_preimage_tests {
+    use bsv_wasm::*;
+    extern crate wasm_bindgen_test;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    fn get_tx() -> Transaction {
+        Transaction::from_hex("01000000029e8d016a7b0dc49a325922d05da1f916d1e4d4f0cb840c9727f3d22ce8d1363f000000008c493046022100e9318720bee5425378b4763b0427158b1051eec8b08442ce3fbfbf7b30202a44022100d4172239ebd701dae2fbaaccd9f038e7ca166707333427e3fb2a2865b19a7f27014104510c67f46d2cbb29476d1f0b794be4cb549ea59ab9cc1e731969a7bf5be95f7ad5e7f904e5ccf50a9dc1714df00fbeb794aa27aaff33260c1032d931a75c56f2ffffffffa3195e7a1ab665473ff717814f6881485dc8759bebe97e31c301ffe7933a656f020000008b48304502201c282f35f3e02a1f32d2089265ad4b561f07ea3c288169dedcf2f785e6065efa022100e8db18aadacb382eed13ee04708f00ba0a9c40e3b21cf91da8859d0f7d99e0c50141042b409e1ebbb43875be5edde9c452c82c01e3903d38fa4fd89f3887a52cb8aea9dc8aec7e2c9d5b3609c03eb16259a2537135a1bf0f9c5fbbcbdbaf83ba402442ffffffff02206b1000000000001976a91420bb5c3bfaef0231dc05190e7f1c8e22e098991e88acf0ca0100000000001976a9149e3e2d23973a04ec1b02be97c30ab9f2f27c3b2c88ac00000000").unwrap()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn parse_and_reserialise_bip143_preimage() {
+        let mut tx = get_tx();
+        let signing_script = Script::from_asm_string("OP_DUP OP_HASH160 20bb5c3bfaef0231dc05190e7f1c8e22e098991e OP_EQUALVERIFY OP_CHECKSIG").unwrap();
+        let buffer = tx.sighash_preimage(SigHash::InputsOutputs, 1, &signing_script, 1500).unwrap();
+
+        let preimage = SighashPreimage::from_bytes(&buffer).unwrap();
+        let input = tx.get_input(1).unwrap();
+
+        assert_eq!(preimage.get_version(), tx.get_version());
+        assert_eq!(preimage.get_prev_tx_id(None), input.get_prev_tx_id(None));
+        assert_eq!(preimage.get_vout(), input.get_vout());
+        assert_eq!(preimage.get_script_code(), signing_script);
+        assert_eq!(preimage.get_value(), 1500);
+        assert_eq!(preimage.get_sequence(), input.get_sequence());
+        assert_eq!(preimage.get_n_locktime(), tx.get_n_locktime());
+        assert_eq!(preimage.get_sighash_type(), SigHash::InputsOutputs);
+        assert_eq!(preimage.get_hash_prevouts(), Hash::sha_256d(&input_outpoints(&tx)).to_bytes());
+
+        assert_eq!(preimage.to_bytes(), buffer);
+        assert_eq!(SighashPreimage::from_hex(&preimage.to_hex()).unwrap(), preimage);
+        assert_eq!(preimage.get_sighash_hash(), Hash::sha_256d(&buffer).to_bytes());
+    }
+
+    fn input_outpoints(tx: &Transaction) -> Vec<u8> {
+        (0..tx.get_ninputs()).flat_map(|i| tx.get_input(i).unwrap().get_outpoint_bytes(Some(true))).collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn modified_preimage_matches_transaction() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let mut preimage = SighashPreimage::from_bytes(&get_tx().sighash_preimage(SigHash::Input, 0, &signing_script, 0).unwrap()).unwrap();
+
+        let mut tx = get_tx();
+        let mut input = tx.get_input(0).unwrap();
+        input.set_sequence(0xfffffffe);
+        tx.set_input(0, &input);
+
+        preimage.set_value(1000);
+        preimage.set_sequence(0xfffffffe);
+
+        assert_eq!(preimage.to_bytes(), tx.sighash_preimage(SigHash::Input, 0, &signing_script, 1000).unwrap());
+
+        assert!(preimage.set_hash_outputs(&[0; 31]).is_err());
+        assert!(preimage.set_sighash_type(SigHash::ALL).is_err());
+        preimage.set_sighash_type(SigHash::InputsOutputs).unwrap();
+        assert_eq!(preimage.get_sighash_type(), SigHash::InputsOutputs);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn reject_invalid_preimages() {
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let buffer = get_tx().sighash_preimage(SigHash::InputsOutputs, 0, &signing_script, 0).unwrap();
+
+        // Truncated preimages are caught by the script code length check before the fixed size fields are read
+        assert!(matches!(SighashPreimage::from_bytes(&buffer[..buffer.len() - 1]), Err(BSVErrors::InvalidSighashPreimage(_))));
+        assert!(matches!(SighashPreimage::from_bytes(&[buffer.clone(), vec![0]].concat()), Err(BSVErrors::InvalidSighashPreimage(_))));
+        assert!(matches!(SighashPreimage::from_bytes(&buffer[..50]), Err(BSVErrors::DeserialiseSighashPreimage(_, _))));
+
+        // Legacy sighash types sign a serialised transaction, not a BIP143 preimage
+        let legacy = get_tx().sighash_preimage(SigHash::ALL, 0, &signing_script, 0).unwrap();
+        assert!(SighashPreimage::from_bytes(&legacy).is_err());
+
+        let mut undefined_sighash = buffer.clone();
+        let len = undefined_sighash.len();
+        undefined_sighash[len - 4] = 0x44;
+        assert!(matches!(SighashPreimage::from_bytes(&undefined_sighash), Err(BSVErrors::UndefinedSighashType(0x44))));
+
+        let mut no_forkid = buffer;
+        no_forkid[len - 4] = 0x01;
+        assert!(matches!(SighashPreimage::from_bytes(&no_forkid), Err(BSVErrors::InvalidSighashPreimage(_))));
+    }
+}
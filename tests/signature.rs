@@ -83,4 +83,161 @@ mod tests {
         assert_eq!(uncompacted_sig.to_compact_bytes(), signature.to_compact_bytes());
         assert_eq!(uncompacted_sig.to_der_bytes(), signature.to_der_bytes());
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn compact_header_encodes_recovery_id_and_compression() {
+        let key = PrivateKey::from_wif("L4rGfRz3Q994Xns9wWti75K2CjxrCuzCqUAwN6yW7ia9nj4SDG32").unwrap();
+        let signature = key.sign_message(b"Hello").unwrap();
+
+        let compressed = signature.to_compact_bytes_with_compression(true);
+        let uncompressed = signature.to_compact_bytes_with_compression(false);
+        assert_eq!(compressed[0], uncompressed[0] + 4);
+        assert_eq!(compressed[1..], uncompressed[1..]);
+
+        let from_uncompressed = Signature::from_compact_bytes(&uncompressed).unwrap();
+        assert_eq!(from_uncompressed.to_compact_bytes_with_compression(false), uncompressed);
+        assert_eq!(from_uncompressed.to_compact_bytes_with_compression(true), compressed);
+
+        let from_base64 = Signature::from_compact_base64(&signature.to_compact_base64_with_compression(false)).unwrap();
+        assert_eq!(from_base64, from_uncompressed);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn from_compact_rejects_invalid_input() {
+        let key = PrivateKey::from_wif("L4rGfRz3Q994Xns9wWti75K2CjxrCuzCqUAwN6yW7ia9nj4SDG32").unwrap();
+        let mut compact = key.sign_message(b"Hello").unwrap().to_compact_bytes();
+
+        assert!(Signature::from_compact_bytes(&[]).is_err());
+        assert!(Signature::from_compact_bytes(&compact[..64]).is_err());
+
+        compact[0] = 26;
+        assert!(Signature::from_compact_bytes(&compact).is_err());
+        compact[0] = 35;
+        assert!(Signature::from_compact_bytes(&compact).is_err());
+
+        // r = 0
+        compact[0] = 31;
+        compact[1..33].copy_from_slice(&[0; 32]);
+        assert!(Signature::from_compact_bytes(&compact).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn signing_always_produces_low_s() {
+        let key = PrivateKey::from_random();
+
+        for i in 0..64u8 {
+            let deterministic = ECDSA::sign_with_deterministic_k(&key, &[i], SigningHash::Sha256d, false).unwrap();
+            assert!(deterministic.is_low_s());
+
+            let random = ECDSA::sign_with_random_k(&key, &[i], SigningHash::Sha256d, false).unwrap();
+            assert!(random.is_low_s());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn normalise_high_s_signature() {
+        let private_key = PrivateKey::from_hex("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262").unwrap();
+        let public_key = PublicKey::from_private_key(&private_key);
+        let message = b"Hello Bitcoin";
+
+        // This fixed nonce gives a high S
+        let k = PrivateKey::from_hex("4200000000000000000000000000000000000000000000000000000000000005").unwrap();
+        let high_s = ECDSA::sign_with_k_unnormalised(&private_key, &k, message, SigningHash::Sha256d).unwrap();
+        assert!(!high_s.is_low_s());
+
+        // Consensus accepts high S, standardness does not
+        assert!(ECDSA::verify_digest(message, &public_key, &high_s, SigningHash::Sha256d).unwrap());
+        assert!(matches!(
+            ECDSA::verify_digest_strict(message, &public_key, &high_s, SigningHash::Sha256d),
+            Err(BSVErrors::HighSSignature)
+        ));
+
+        let low_s = high_s.normalise_s().unwrap();
+        assert!(low_s.is_low_s());
+        assert_eq!(low_s.to_compact_bytes()[1..33], high_s.to_compact_bytes()[1..33], "R is unchanged");
+        assert!(ECDSA::verify_digest_strict(message, &public_key, &low_s, SigningHash::Sha256d).unwrap());
+
+        // Flipping S flips the recovery id, so both recover the signer
+        assert_eq!(high_s.recover_public_key(message, SigningHash::Sha256d).unwrap(), public_key);
+        assert_eq!(low_s.recover_public_key(message, SigningHash::Sha256d).unwrap(), public_key);
+
+        // Already low S signatures are unchanged
+        assert_eq!(low_s.normalise_s().unwrap(), low_s);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn rfc6979_nonce_secp256k1_vectors() {
+        let one = PrivateKey::from_hex("0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        let n_minus_one = PrivateKey::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140").unwrap();
+
+        let k = ECDSA::rfc6979_nonce(&one, &Hash::sha_256(b"Satoshi Nakamoto").to_bytes(), &[]).unwrap();
+        assert_eq!(k.to_hex(), "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15");
+
+        let k = ECDSA::rfc6979_nonce(&one, &Hash::sha_256(b"All those moments will be lost in time, like tears in rain. Time to die...").to_bytes(), &[]).unwrap();
+        assert_eq!(k.to_hex(), "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3");
+
+        let k = ECDSA::rfc6979_nonce(&n_minus_one, &Hash::sha_256(b"Satoshi Nakamoto").to_bytes(), &[]).unwrap();
+        assert_eq!(k.to_hex(), "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90");
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn rfc6979_nonce_reproduces_deterministic_signatures() {
+        let private_key = PrivateKey::from_random();
+        let preimage = b"Hello, Bitcoin.";
+
+        for (hash_algo, digest) in [
+            (SigningHash::Sha256, Hash::sha_256(preimage)),
+            (SigningHash::Sha256d, Hash::sha_256d(preimage)),
+            (SigningHash::Sha3_256, Hash::sha3_256(preimage)),
+            (SigningHash::Keccak256, Hash::keccak_256(preimage)),
+            (SigningHash::Sha512_256, Hash::sha_512_256(preimage)),
+            (SigningHash::Blake2b256, Hash::blake2b_256(preimage)),
+            (SigningHash::Blake3, Hash::blake3(preimage)),
+        ] {
+            let k = ECDSA::rfc6979_nonce(&private_key, &digest.to_bytes(), &[]).unwrap();
+            let reproduced = ECDSA::sign_with_k(&private_key, &k, preimage, hash_algo).unwrap();
+            let signature = ECDSA::sign_with_deterministic_k(&private_key, preimage, hash_algo, false).unwrap();
+
+            assert_eq!(reproduced.to_der_bytes(), signature.to_der_bytes());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn rfc6979_nonce_extra_entropy_and_digest_length() {
+        let private_key = PrivateKey::from_random();
+        let digest = Hash::sha_256(b"Hello, Bitcoin.").to_bytes();
+        let k = ECDSA::rfc6979_nonce(&private_key, &digest, &[]).unwrap();
+
+        assert_ne!(ECDSA::rfc6979_nonce(&private_key, &digest, &[1; 32]).unwrap().to_hex(), k.to_hex());
+        assert_eq!(ECDSA::rfc6979_nonce(&private_key, &[digest.clone(), vec![0xff; 32]].concat(), &[]).unwrap().to_hex(), k.to_hex());
+        assert!(ECDSA::rfc6979_nonce(&private_key, &[], &[]).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn sign_verify_and_recover_with_additional_signing_hashes() {
+        let private_key = PrivateKey::from_random();
+        let public_key = PublicKey::from_private_key(&private_key);
+        let preimage = b"Hello, Bitcoin.";
+
+        for hash_algo in [SigningHash::Sha3_256, SigningHash::Keccak256, SigningHash::Sha512_256, SigningHash::Blake2b256, SigningHash::Blake3] {
+            for reverse_k in [false, true] {
+                let deterministic = ECDSA::sign_with_deterministic_k(&private_key, preimage, hash_algo, reverse_k).unwrap();
+                let random = ECDSA::sign_with_random_k(&private_key, preimage, hash_algo, reverse_k).unwrap();
+
+                for signature in [deterministic, random] {
+                    assert!(ECDSA::verify_digest_strict(preimage, &public_key, &signature, hash_algo).unwrap());
+                    assert_eq!(signature.recover_public_key(preimage, hash_algo).unwrap(), public_key);
+                    assert!(!ECDSA::verify_digest(preimage, &public_key, &signature, SigningHash::Sha256).unwrap_or(false));
+                }
+            }
+        }
+    }
 }
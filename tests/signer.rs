@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod signer_tests {
+    use bsv_wasm::*;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    /**
+     * Stands in for a key held elsewhere: signs with a fixed nonce that gives a high S for the test message.
+     */
+    struct FixedNonceSigner {
+        key: PrivateKey,
+        k: PrivateKey,
+    }
+
+    impl Signer for FixedNonceSigner {
+        fn get_public_key(&self) -> Result<PublicKey, BSVErrors> {
+            Ok(PublicKey::from_private_key(&self.key))
+        }
+
+        fn sign(&self, preimage: &[u8], hash_algo: SigningHash, _reverse_k: bool) -> Result<Signature, BSVErrors> {
+            ECDSA::sign_with_k_unnormalised(&self.key, &self.k, preimage, hash_algo)
+        }
+    }
+
+    fn fixed_nonce_signer() -> FixedNonceSigner {
+        FixedNonceSigner {
+            key: PrivateKey::from_hex("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262").unwrap(),
+            k: PrivateKey::from_hex("4200000000000000000000000000000000000000000000000000000000000005").unwrap(),
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn private_key_signer_matches_private_key_signing() {
+        let priv_key = PrivateKey::from_wif("L31JUXCGspUREe9Gya8F2WWjeoRz3bb8AQzJjAP8ntGYp37oYdSx").unwrap();
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let mut tx = Transaction::new(1, 0);
+        tx.add_input(&TxIn::new(&[0; 32], 0, &Script::default(), None));
+        tx.add_output(&TxOut::new(1000, &signing_script));
+
+        let signature = tx.sign(&priv_key, SigHash::InputsOutputs, 0, &signing_script, 1000).unwrap();
+        let signer_signature = tx.sign_with_signer(&priv_key, SigHash::InputsOutputs, 0, &signing_script, 1000).unwrap();
+        assert_eq!(signer_signature.to_hex().unwrap(), signature.to_hex().unwrap());
+
+        let message = b"Hello Bitcoin!";
+        assert_eq!(BSM::sign_message_with_signer(&priv_key, message).unwrap(), BSM::sign_message(&priv_key, message).unwrap());
+        assert_eq!(BSM::sign_message_base64_with_signer(&priv_key, message).unwrap(), BSM::sign_message_base64(&priv_key, message).unwrap());
+        assert_eq!(
+            ECDSA::sign_with_signer(&priv_key, message, SigningHash::Sha256, false).unwrap(),
+            ECDSA::sign_with_deterministic_k(&priv_key, message, SigningHash::Sha256, false).unwrap()
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn external_signer_output_is_normalised_to_low_s() {
+        let signer = fixed_nonce_signer();
+        let message = b"Hello Bitcoin";
+
+        let raw = signer.sign(message, SigningHash::Sha256d, false).unwrap();
+        assert!(!raw.is_low_s());
+
+        let signature = ECDSA::sign_with_signer(&signer, message, SigningHash::Sha256d, false).unwrap();
+        assert!(signature.is_low_s());
+        assert!(ECDSA::verify_digest_strict(message, &signer.get_public_key().unwrap(), &signature, SigningHash::Sha256d).unwrap());
+        assert_eq!(signature.recover_public_key(message, SigningHash::Sha256d).unwrap(), signer.get_public_key().unwrap());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn external_signer_signs_transactions_and_messages() {
+        let signer = fixed_nonce_signer();
+        let public_key = signer.get_public_key().unwrap();
+        let signing_script = Script::from_asm_string("OP_0 OP_RETURN").unwrap();
+        let mut tx = Transaction::new(1, 0);
+        tx.add_input(&TxIn::new(&[0; 32], 0, &Script::default(), None));
+        tx.add_output(&TxOut::new(1000, &signing_script));
+
+        let signature = tx.sign_with_signer(&signer, SigHash::InputsOutputs, 0, &signing_script, 1000).unwrap();
+        assert!(tx.verify(&public_key, &signature));
+
+        let message = b"Hello Bitcoin!";
+        let base64_signature = BSM::sign_message_base64_with_signer(&signer, message).unwrap();
+        assert!(BSM::verify_message_base64(message, &base64_signature, &public_key.to_p2pkh_address().unwrap()).unwrap());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn js_signer_awaits_promise_callbacks() {
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+        use wasm_bindgen_futures::JsFuture;
+
+        let key = PrivateKey::from_hex("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262").unwrap();
+        let message = b"Hello Bitcoin!";
+        let expected = BSM::sign_message(&key, message).unwrap();
+
+        // Resolves the signature later, like a hardware wallet
+        let der = expected.to_der_bytes();
+        let sign_fn =
+            Closure::wrap(Box::new(move |_digest: js_sys::Uint8Array| js_sys::Promise::resolve(&js_sys::Uint8Array::from(&der[..]).into())) as Box<dyn Fn(js_sys::Uint8Array) -> js_sys::Promise>);
+        let signer = JsSigner::new(&PublicKey::from_private_key(&key), sign_fn.as_ref().unchecked_ref::<js_sys::Function>().clone());
+
+        let base64_signature = JsFuture::from(BSM::sign_message_base64_with_signer_async(&signer, message)).await.unwrap();
+        assert_eq!(base64_signature.as_string().unwrap(), expected.to_compact_base64());
+
+        let reject_fn = Closure::wrap(Box::new(|_digest: js_sys::Uint8Array| js_sys::Promise::reject(&JsValue::from_str("User cancelled"))) as Box<dyn Fn(js_sys::Uint8Array) -> js_sys::Promise>);
+        let rejecting_signer = JsSigner::new(&PublicKey::from_private_key(&key), reject_fn.as_ref().unchecked_ref::<js_sys::Function>().clone());
+        assert!(JsFuture::from(BSM::sign_message_with_signer_async(&rejecting_signer, message)).await.is_err());
+    }
+}
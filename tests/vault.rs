@@ -0,0 +1,172 @@
+#[cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
+#[cfg(test)]
+mod vault_tests {
+    use bsv_wasm::{Argon2Params, BSVErrors, ExtendedPrivateKey, PrivateKey, ScryptParams, Vault, VaultKDF, VaultSecretType, VAULT_MAX_ARGON2_ITERATIONS};
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test::wasm_bindgen_test_configure!();
+
+    fn fast_scrypt() -> VaultKDF {
+        VaultKDF::scrypt(&ScryptParams::new(4, 8, 1))
+    }
+
+    fn fast_argon2id() -> VaultKDF {
+        VaultKDF::argon2id(&Argon2Params::new(64, 1, 1))
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn private_key_vault_round_trips_through_json() {
+        let key = PrivateKey::from_random();
+        let vault = Vault::from_private_key(&key, b"correct horse", &fast_argon2id()).unwrap();
+
+        let json = vault.to_json_string().unwrap();
+        let restored = Vault::from_json_string(&json).unwrap();
+
+        assert_eq!(restored, vault);
+        assert_eq!(restored.get_secret_type(), VaultSecretType::PrivateKey);
+        assert_eq!(restored.decrypt_private_key(b"correct horse").unwrap().to_wif().unwrap(), key.to_wif().unwrap());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn vault_json_is_self_describing() {
+        let vault = Vault::from_mnemonic("abandon abandon about", b"pw", &fast_scrypt()).unwrap();
+        let json = vault.to_json().unwrap();
+
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["secret_type"], "mnemonic");
+        assert_eq!(json["kdf"]["algorithm"], "scrypt");
+        assert_eq!(json["kdf"]["log_n"], 4);
+        assert_eq!(json["kdf"]["r"], 8);
+        assert_eq!(json["kdf"]["p"], 1);
+        assert_eq!(json["cipher"], "aes-256-gcm");
+        assert_eq!(json["salt"].as_str().unwrap().len(), 32);
+        assert_eq!(json["nonce"].as_str().unwrap().len(), 24);
+        assert_eq!(json["mac"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn extended_private_key_and_mnemonic_vaults_decrypt() {
+        let xprv = ExtendedPrivateKey::from_random().unwrap();
+        let vault = Vault::from_extended_private_key(&xprv, b"pw", &fast_scrypt()).unwrap();
+        assert_eq!(vault.decrypt_extended_private_key(b"pw").unwrap().to_string().unwrap(), xprv.to_string().unwrap());
+
+        let mnemonic = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let vault = Vault::from_mnemonic(mnemonic, b"pw", &fast_argon2id()).unwrap();
+        assert_eq!(vault.decrypt_mnemonic(b"pw").unwrap(), mnemonic);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn slip132_and_custom_version_xprv_vaults_decrypt() {
+        let key = ExtendedPrivateKey::from_random().unwrap();
+        let zprv = ExtendedPrivateKey::from_string_with_version(&key.to_string_with_version(0x04b2430c).unwrap(), 0x04b2430c).unwrap();
+        let custom = ExtendedPrivateKey::from_string_with_version_pair(&key.to_string_with_version(0x01020304).unwrap(), 0x01020304, 0x05060708).unwrap();
+
+        for xprv in [zprv, custom].iter() {
+            let json = Vault::from_extended_private_key(xprv, b"pw", &fast_scrypt()).unwrap().to_json_string().unwrap();
+            let decrypted = Vault::from_json_string(&json).unwrap().decrypt_extended_private_key(b"pw").unwrap();
+
+            assert!(&decrypted == xprv);
+            assert_eq!(decrypted.to_string().unwrap(), xprv.to_string().unwrap());
+            assert_eq!(decrypted.get_public_version(), xprv.get_public_version());
+        }
+
+        // The version bytes are authenticated
+        let json = Vault::from_extended_private_key(&key, b"pw", &fast_scrypt()).unwrap().to_json_string().unwrap();
+        let retagged = Vault::from_json_string(&json.replace("\"public\":76067358", "\"public\":78792518")).unwrap();
+        assert!(matches!(retagged.decrypt_extended_private_key(b"pw"), Err(BSVErrors::InvalidAuthenticationTag)));
+
+        // Only xprv vaults carry them
+        let wif_json = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_scrypt()).unwrap().to_json_string().unwrap();
+        assert!(!wif_json.contains("extended_key_versions"));
+        assert!(Vault::from_json_string(&json.replace(",\"extended_key_versions\":{\"private\":76066276,\"public\":76067358}", "")).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn wrong_password_fails() {
+        let vault = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_scrypt()).unwrap();
+
+        assert!(matches!(vault.decrypt_private_key(b"not pw"), Err(BSVErrors::InvalidAuthenticationTag)));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn decrypting_as_wrong_type_fails() {
+        let vault = Vault::from_mnemonic("abandon abandon about", b"pw", &fast_scrypt()).unwrap();
+
+        assert!(matches!(vault.decrypt_private_key(b"pw"), Err(BSVErrors::VaultError(_))));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn tampered_header_fails() {
+        let vault = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_scrypt()).unwrap();
+        let json = vault.to_json_string().unwrap();
+
+        let weaker = Vault::from_json_string(&json.replace("\"r\":8", "\"r\":1")).unwrap();
+        assert!(weaker.decrypt_private_key(b"pw").is_err());
+
+        let retyped = Vault::from_json_string(&json.replace("\"wif\"", "\"mnemonic\"")).unwrap();
+        assert!(matches!(retyped.decrypt_mnemonic(b"pw"), Err(BSVErrors::InvalidAuthenticationTag)));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn from_json_rejects_unknown_versions_and_fields() {
+        let vault = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_scrypt()).unwrap();
+        let json = vault.to_json_string().unwrap();
+
+        assert!(Vault::from_json_string(&json.replace("\"version\":1", "\"version\":2")).is_err());
+        assert!(Vault::from_json_string(&json.replace("\"scrypt\"", "\"bcrypt\"")).is_err());
+        assert!(Vault::from_json_string(&json.replace("\"aes-256-gcm\"", "\"aes-128-cbc\"")).is_err());
+        assert!(Vault::from_json_string("{}").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn from_json_rejects_hostile_kdf_parameters() {
+        let scrypt_json = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_scrypt()).unwrap().to_json_string().unwrap();
+        let argon2_json = Vault::from_private_key(&PrivateKey::from_random(), b"pw", &fast_argon2id()).unwrap().to_json_string().unwrap();
+
+        // Each of these would tie up gigabytes of memory or minutes of CPU if it reached the KDF
+        let hostile = [
+            scrypt_json.replace("\"log_n\":4", "\"log_n\":31"),
+            scrypt_json.replace("\"log_n\":4", "\"log_n\":21"),
+            scrypt_json.replace("\"p\":1", "\"p\":1000000"),
+            scrypt_json.replace("\"log_n\":4", "\"log_n\":20").replace("\"r\":8", "\"r\":16"),
+            argon2_json.replace("\"memory_kib\":64", "\"memory_kib\":4294967295"),
+            argon2_json.replace("\"iterations\":1", "\"iterations\":4294967295"),
+            argon2_json.replace("\"parallelism\":1", "\"parallelism\":16777215"),
+        ];
+
+        for json in hostile.iter() {
+            assert_ne!(json, &scrypt_json);
+            assert_ne!(json, &argon2_json);
+            assert!(matches!(Vault::from_json_string(json), Err(BSVErrors::VaultError(_))), "{}", json);
+        }
+
+        let too_slow = VaultKDF::argon2id(&Argon2Params::new(64, VAULT_MAX_ARGON2_ITERATIONS + 1, 1));
+        assert!(Vault::from_private_key(&PrivateKey::from_random(), b"pw", &too_slow).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn upgrade_re_encrypts_with_new_kdf() {
+        let key = PrivateKey::from_random();
+        let vault = Vault::from_private_key(&key, b"pw", &fast_scrypt()).unwrap();
+
+        let stronger = VaultKDF::argon2id(&Argon2Params::new(128, 2, 1));
+        let upgraded = vault.upgrade(b"pw", &stronger).unwrap();
+
+        assert_eq!(upgraded.get_kdf(), stronger);
+        assert_eq!(upgraded.get_kdf().get_algorithm(), "argon2id");
+        assert_eq!(upgraded.get_kdf().get_argon2_params().unwrap().get_memory_kib(), 128);
+        assert_eq!(upgraded.get_secret_type(), VaultSecretType::PrivateKey);
+        assert_eq!(upgraded.decrypt_private_key(b"pw").unwrap().to_wif().unwrap(), key.to_wif().unwrap());
+
+        assert!(vault.upgrade(b"not pw", &stronger).is_err());
+    }
+}